@@ -0,0 +1,312 @@
+//! Benchmark suite with machine-readable results, to compare commits on the same machine.
+//!
+//! Run with `cargo bench --bench suite -- [options]`:
+//! - `--rows N`, `--cols N` - Size of the synthetic regression data (default 10000 x 16)
+//! - `--epochs N` - Epochs of the end-to-end `train` benchmark (default 20)
+//! - `--time SECONDS` - Measuring time per benchmark (default 1)
+//! - `--filter TEXT` - Only run benchmarks whose name contains `TEXT`
+//! - `--save PATH` - Results file (default `target/bench-results/<commit>.json`)
+//! - `--baseline PATH` - Earlier results file to compare medians with; exits with 1 on a regression
+//! - `--threshold PERCENT` - Slowdown of the median reported as a regression (default 10)
+//!
+//! A typical workflow saves a baseline on one commit and passes it as `--baseline` on the next.
+
+use std::error::Error;
+use std::fs;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use ferrite_rs::data_utils::{csv_read_input_output, csv_write};
+use ferrite_rs::matrix_operations::matrix_mul;
+use ferrite_rs::multivariate_regression::gradient::Gradient;
+use ferrite_rs::multivariate_regression::loss::LossFn;
+use ferrite_rs::multivariate_regression::normalization::normalization::{normalize_data, NormalizationParameterType};
+use ferrite_rs::multivariate_regression::regularization::regularization::Regularization;
+use ferrite_rs::multivariate_regression::training::train::train;
+use ferrite_rs::multivariate_regression::training::train_config::TrainConfigBuilder;
+use ferrite_rs::multivariate_regression::update_weight::{update_weight, MiniBatchSize, TrainingWorkspace, UpdatationMethod};
+use ndarray::{Array1, Array2, Axis};
+use ndarray_rand::rand::rngs::StdRng;
+use ndarray_rand::rand::SeedableRng;
+use ndarray_rand::rand_distr::Uniform;
+use ndarray_rand::RandomExt;
+use serde::{Deserialize, Serialize};
+
+/// Command line options, see the module documentation
+struct Options {
+    rows: usize,
+    cols: usize,
+    epochs: usize,
+    time: Duration,
+    filter: Option<String>,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+impl Options {
+    fn parse() -> Result<Self, Box<dyn Error>> {
+        let mut options = Options {
+            rows: 10_000,
+            cols: 16,
+            epochs: 20,
+            time: Duration::from_secs(1),
+            filter: None,
+            save: None,
+            baseline: None,
+            threshold: 10.0,
+        };
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+            match arg.as_str() {
+                "--bench" => {} // Passed by `cargo bench`
+                "--rows" => options.rows = value()?.parse()?,
+                "--cols" => options.cols = value()?.parse()?,
+                "--epochs" => options.epochs = value()?.parse()?,
+                "--time" => options.time = Duration::from_secs_f64(value()?.parse()?),
+                "--filter" => options.filter = Some(value()?),
+                "--save" => options.save = Some(value()?.into()),
+                "--baseline" => options.baseline = Some(value()?.into()),
+                "--threshold" => options.threshold = value()?.parse()?,
+                _ => return Err(format!("Unknown option {}", arg).into()),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Work done by one iteration, reported as a rate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Throughput {
+    unit: String, // "rows/s", "bytes/s" or "flop/s"
+    per_iteration: f64,
+    per_second: f64,
+}
+
+/// Timing summary of one benchmark, in nanoseconds per iteration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BenchResult {
+    name: String,
+    iterations: usize,
+    mean_ns: f64,
+    median_ns: f64,
+    min_ns: f64,
+    max_ns: f64,
+    stddev_ns: f64,
+    throughput: Option<Throughput>,
+}
+
+/// Contents of a results file
+#[derive(Debug, Serialize, Deserialize)]
+struct Report {
+    commit: String,
+    timestamp: u64, // Seconds since the Unix epoch
+    rows: usize,
+    cols: usize,
+    epochs: usize,
+    results: Vec<BenchResult>,
+}
+
+struct Suite {
+    options: Options,
+    results: Vec<BenchResult>,
+}
+
+impl Suite {
+    /// Times `routine` on fresh `setup()` values until the time budget is spent; setup time is excluded
+    fn bench<S, T>(
+        &mut self,
+        name: &str,
+        throughput: Option<(&str, f64)>,
+        mut setup: impl FnMut() -> S,
+        mut routine: impl FnMut(S) -> T,
+    ) {
+        if self.options.filter.as_ref().is_some_and(|filter| !name.contains(filter.as_str())) {
+            return;
+        }
+        black_box(routine(setup())); // Warm-up
+
+        let mut samples = Vec::new();
+        let mut spent = Duration::ZERO;
+        while (spent < self.options.time || samples.len() < 5) && samples.len() < 1_000_000 {
+            let input = setup();
+            let start = Instant::now();
+            let output = routine(input);
+            let elapsed = start.elapsed();
+            black_box(output);
+            spent += elapsed;
+            samples.push(elapsed.as_nanos() as f64);
+        }
+
+        samples.sort_by(|a, b| a.total_cmp(b));
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        let median = samples[samples.len() / 2];
+        let result = BenchResult {
+            name: name.to_string(),
+            iterations: samples.len(),
+            mean_ns: mean,
+            median_ns: median,
+            min_ns: samples[0],
+            max_ns: samples[samples.len() - 1],
+            stddev_ns: variance.sqrt(),
+            throughput: throughput.map(|(unit, per_iteration)| Throughput {
+                unit: unit.to_string(),
+                per_iteration,
+                per_second: per_iteration / (median * 1e-9),
+            }),
+        };
+        println!(
+            "{:<40} {:>10} {:>14} {:>14}   {}",
+            result.name,
+            result.iterations,
+            format_ns(result.median_ns),
+            format_ns(result.stddev_ns),
+            result.throughput.as_ref().map(|t| format!("{:.3e} {}", t.per_second, t.unit)).unwrap_or_default()
+        );
+        self.results.push(result);
+    }
+}
+
+fn format_ns(ns: f64) -> String {
+    match ns {
+        ns if ns >= 1e9 => format!("{:.3} s", ns * 1e-9),
+        ns if ns >= 1e6 => format!("{:.3} ms", ns * 1e-6),
+        ns if ns >= 1e3 => format!("{:.3} us", ns * 1e-3),
+        ns => format!("{:.0} ns", ns),
+    }
+}
+
+/// Seeded regression data `y = X w + noise` with inputs in [-1, 1]
+fn synthetic_data(rows: usize, cols: usize) -> (Array2<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let x = Array2::random_using((rows, cols), Uniform::new(-1.0, 1.0), &mut rng);
+    let w = Array1::random_using(cols, Uniform::new(-2.0, 2.0), &mut rng);
+    let noise = Array1::random_using(rows, Uniform::new(-0.1, 0.1), &mut rng);
+    let y = (x.dot(&w) + noise + 3.0).insert_axis(Axis(1));
+    (x, y)
+}
+
+fn git_commit() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn run_benchmarks(suite: &mut Suite) -> Result<(), Box<dyn Error>> {
+    let (rows, cols, epochs) = (suite.options.rows, suite.options.cols, suite.options.epochs);
+    let (x, y) = synthetic_data(rows, cols);
+
+    for (m, k, n) in [(64, 64, 64), (256, 256, 256), (1024, 1024, 1), (rows, cols + 1, 1), (cols + 1, rows, 1)] {
+        let mut rng = StdRng::seed_from_u64(7);
+        let a = Array2::random_using((m, k), Uniform::new(-1.0, 1.0), &mut rng);
+        let b = Array2::random_using((k, n), Uniform::new(-1.0, 1.0), &mut rng);
+        let flop = 2.0 * (m * k * n) as f64;
+        suite.bench(&format!("matrix_mul/{}x{}x{}", m, k, n), Some(("flop/s", flop)), || (), |_| matrix_mul(&a, &b));
+    }
+
+    // One epoch on the normalized-range data, bias as row 0 of the weights
+    let grad = Gradient::new(LossFn::SquaredError, Regularization::l2(0.01));
+    for (label, optimizer) in [("BGD", UpdatationMethod::BGD), ("MiniBatchGD", UpdatationMethod::MiniBatchGD), ("SGD", UpdatationMethod::SGD)] {
+        let mut weight = Array2::<f64>::zeros((cols + 1, 1));
+        let mut workspace = TrainingWorkspace::new();
+        suite.bench(&format!("epoch/{}", label), Some(("rows/s", rows as f64)), || (), |_| {
+            update_weight(&x, &y, None, &mut weight, &optimizer, &Some(MiniBatchSize::Medium), &grad, 1e-3, false, &mut workspace)
+        });
+    }
+
+    let csv_path = std::env::temp_dir().join(format!("ferrite_bench_{}.csv", std::process::id()));
+    let headers: Vec<String> = (0..cols).map(|c| format!("x{}", c)).chain(["y".to_string()]).collect();
+    csv_write(csv_path.to_string_lossy().to_string(), headers, &ndarray::concatenate![Axis(1), x, y])?;
+    let bytes = fs::metadata(&csv_path)?.len() as f64;
+    suite.bench("csv/read_input_output", Some(("bytes/s", bytes)), || (), |_| {
+        csv_read_input_output(csv_path.to_string_lossy().to_string(), vec!["y".to_string()], Vec::new()).expect("CSV was just written")
+    });
+    fs::remove_file(&csv_path)?;
+
+    for (label, parameter_type) in [
+        ("MinMax", NormalizationParameterType::MinMaxParameter),
+        ("Z", NormalizationParameterType::ZParameter),
+        ("MaxAbs", NormalizationParameterType::MaxAbsParameter),
+        ("Robust", NormalizationParameterType::RobustParameter),
+    ] {
+        suite.bench(&format!("normalization/{}", label), Some(("rows/s", rows as f64)), || x.clone(), |mut input| {
+            normalize_data(&parameter_type, &mut input).expect("Synthetic data normalizes");
+            input
+        });
+    }
+
+    for (label, optimizer) in [("BGD", UpdatationMethod::BGD), ("MiniBatchGD", UpdatationMethod::MiniBatchGD)] {
+        let config = TrainConfigBuilder::new().epochs(epochs).learning_rate(0.1).optimizer(optimizer).build();
+        suite.bench(
+            &format!("train/{}/{}x{}/{}_epochs", label, rows, cols, epochs),
+            Some(("rows/s", (rows * epochs) as f64)),
+            || (x.clone(), y.clone(), config.clone()),
+            |(input, output, config)| train(input, output, config),
+        );
+    }
+    Ok(())
+}
+
+/// Prints the median change of every benchmark present in both runs; returns the regressed names
+fn compare(baseline: &Report, results: &[BenchResult], threshold: f64) -> Vec<String> {
+    println!("\nComparison with {} ({} x {}, {} epochs):", baseline.commit, baseline.rows, baseline.cols, baseline.epochs);
+    let mut regressions = Vec::new();
+    for result in results {
+        let Some(old) = baseline.results.iter().find(|old| old.name == result.name) else {
+            continue;
+        };
+        let change = 100.0 * (result.median_ns / old.median_ns - 1.0);
+        let verdict = if change > threshold {
+            regressions.push(result.name.clone());
+            "REGRESSION"
+        } else if change < -threshold {
+            "improved"
+        } else {
+            ""
+        };
+        println!("{:<40} {:>14} -> {:>14} {:>+8.1}%  {}", result.name, format_ns(old.median_ns), format_ns(result.median_ns), change, verdict);
+    }
+    regressions
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = Options::parse()?;
+    println!("{:<40} {:>10} {:>14} {:>14}   throughput", "benchmark", "samples", "median", "stddev");
+    let mut suite = Suite { options, results: Vec::new() };
+    run_benchmarks(&mut suite)?;
+    let Suite { options, results } = suite;
+
+    let commit = git_commit();
+    let save = options.save.clone().unwrap_or_else(|| PathBuf::from(format!("target/bench-results/{}.json", commit)));
+    if let Some(parent) = save.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let report = Report {
+        commit,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        rows: options.rows,
+        cols: options.cols,
+        epochs: options.epochs,
+        results,
+    };
+    fs::write(&save, serde_json::to_string_pretty(&report)?)?;
+    println!("\nResults written to {}", save.display());
+
+    if let Some(path) = &options.baseline {
+        let baseline: Report = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let regressions = compare(&baseline, &report.results, options.threshold);
+        if !regressions.is_empty() {
+            eprintln!("{} benchmark(s) slower than the baseline by more than {}%", regressions.len(), options.threshold);
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
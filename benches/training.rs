@@ -0,0 +1,91 @@
+//! Per-step cost of the training loop: the workspace based `update_weight` against the
+//! allocating path it replaced (copy the batch, `multiply`, `calculate_gradient`, `weight - lr * g`).
+//!
+//! Run with `cargo bench --bench training`. Counts heap allocations through a wrapping allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use ferrite_rs::matrix_operations::{LinearOperator, Transpose};
+use ferrite_rs::multivariate_regression::gradient::Gradient;
+use ferrite_rs::multivariate_regression::loss::LossFn;
+use ferrite_rs::multivariate_regression::regularization::regularization::Regularization;
+use ferrite_rs::multivariate_regression::update_weight::{update_weight, MiniBatchSize, TrainingWorkspace, UpdatationMethod};
+use ndarray::{s, Array2};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Epoch of the allocating loop, on a design matrix that carries the bias column
+fn allocating_epoch(design: &Array2<f64>, output: &Array2<f64>, weight: &mut Array2<f64>, batch_size: usize, grad: &Gradient, lr: f64) {
+    for start in (0..design.nrows()).step_by(batch_size) {
+        let end = (start + batch_size).min(design.nrows());
+        let batch_ip = design.slice_rows(start..end);
+        let batch_op = output.slice(s![start..end, ..]).to_owned();
+        let pred = batch_ip.multiply(Transpose::No, &weight.view()).unwrap();
+        let gradient = grad.calculate_gradient(&batch_ip, &pred, &batch_op, weight, None);
+        *weight -= &(lr * gradient);
+    }
+}
+
+/// Mean seconds and allocations per epoch of `epoch`
+fn measure(epochs: usize, mut epoch: impl FnMut()) -> (f64, f64) {
+    epoch(); // Warm-up, sizes the workspace
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let timer = Instant::now();
+    for _ in 0..epochs {
+        epoch();
+    }
+    let elapsed = timer.elapsed().as_secs_f64() / epochs as f64;
+    (elapsed, (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64 / epochs as f64)
+}
+
+fn main() {
+    let (rows, cols) = (4096, 32);
+    let input = Array2::from_shape_fn((rows, cols), |(i, j)| ((i * 31 + j * 17) % 23) as f64 / 23.0);
+    let design = ndarray::concatenate![ndarray::Axis(1), Array2::ones((rows, 1)), input];
+    let grad = Gradient::new(LossFn::SquaredError, Regularization::l2(0.01));
+
+    println!("{:<12} {:>8} {:>14} {:>14} {:>12} {:>12} {:>8}", "optimizer", "outputs", "allocating_us", "workspace_us", "alloc_allocs", "ws_allocs", "speedup");
+    // 2 outputs use the fixed-width kernels, 6 the wide one
+    for outputs in [2, 6] {
+        let output = Array2::from_shape_fn((rows, outputs), |(i, o)| input.row(i).sum() + o as f64);
+        for (name, optimizer, batch_size, epochs) in [
+            ("BGD", UpdatationMethod::BGD, rows, 200),
+            ("MiniBatchGD", UpdatationMethod::MiniBatchGD, MiniBatchSize::Medium as usize, 20),
+            ("SGD", UpdatationMethod::SGD, 1, 5),
+        ] {
+            let mut weight = Array2::<f64>::zeros((cols + 1, outputs));
+            let (old_time, old_allocs) = measure(epochs, || allocating_epoch(&design, &output, &mut weight, batch_size, &grad, 1e-3));
+            black_box(&weight);
+
+            let mut weight = Array2::<f64>::zeros((cols + 1, outputs));
+            let mut workspace = TrainingWorkspace::new();
+            let (new_time, new_allocs) = measure(epochs, || {
+                update_weight(&input, &output, None, &mut weight, &optimizer, &Some(MiniBatchSize::Medium), &grad, 1e-3, false, &mut workspace)
+            });
+            black_box(&weight);
+
+            println!(
+                "{:<12} {:>8} {:>14.1} {:>14.1} {:>12.0} {:>12.0} {:>7.2}x",
+                name, outputs, old_time * 1e6, new_time * 1e6, old_allocs, new_allocs, old_time / new_time
+            );
+        }
+    }
+}
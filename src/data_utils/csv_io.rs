@@ -4,14 +4,52 @@ use std::io::BufReader;
 use csv::{ReaderBuilder, WriterBuilder};
 use ndarray::Array2;
 
+/// Cell values treated as missing by the CSV readers (empty cells are always missing)
+pub const DEFAULT_NA_TOKENS: [&str; 8] = ["NA", "N/A", "NaN", "nan", "null", "NULL", "None", "?"];
+
+/// Function to parse a single CSV cell, mapping empty cells and NA tokens to `NaN`
+///
+/// # Parameters:
+/// - `cell: &str` - Raw cell content
+/// - `na_tokens: &[String]` - Cell values to be treated as missing
+///
+/// # Returns:
+/// - `Result<f64, Box<dyn Error>>` - Parsed value, `NaN` if missing, error if the cell is not numeric
+pub fn parse_cell(cell: &str, na_tokens: &[String]) -> Result<f64, Box<dyn Error>> {
+    let cell = cell.trim();
+    if cell.is_empty() || na_tokens.iter().any(|token| token == cell) {
+        return Ok(f64::NAN);
+    }
+    cell.parse::<f64>()
+        .map_err(|_| format!("Unable to parse '{}' as a number", cell).into())
+}
+
+fn default_na_tokens() -> Vec<String> {
+    DEFAULT_NA_TOKENS.iter().map(|token| token.to_string()).collect()
+}
+
 /// Function to read and parse a CSV file without headers
 ///
 /// # Parameters:
 /// - `filepath: String` - Relative path of the CSV file wrt Cargo.toml file of the project
 ///
+/// Empty cells and `DEFAULT_NA_TOKENS` are read as `NaN`.
+///
 /// # Returns:
 /// - `Result<Array2<f64>, Box<dyn Error>>` - 2D Array of the CSV file without headers
 pub fn csv_read(filepath: String) -> Result<Array2<f64>, Box<dyn Error>> {
+    csv_read_with_na(filepath, default_na_tokens())
+}
+
+/// Function to read and parse a CSV file without headers, with custom missing-value tokens
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the CSV file wrt Cargo.toml file of the project
+/// - `na_tokens: Vec<String>` - Cell values to be read as `NaN` (empty cells are always missing)
+///
+/// # Returns:
+/// - `Result<Array2<f64>, Box<dyn Error>>` - 2D Array of the CSV file without headers
+pub fn csv_read_with_na(filepath: String, na_tokens: Vec<String>) -> Result<Array2<f64>, Box<dyn Error>> {
     let file = File::open(filepath)?;
     let reader = BufReader::new(file);
 
@@ -28,11 +66,11 @@ pub fn csv_read(filepath: String) -> Result<Array2<f64>, Box<dyn Error>> {
         if rows == 0 {
             cols = record.len();
         }
-        let row: Vec<f64> = record
-            .iter()
-            .map(|s| s.parse::<f64>().unwrap_or(f64::NAN)) // Convert to f64
-            .collect();
-        data.extend(row);
+        for (col, cell) in record.iter().enumerate() {
+            let value = parse_cell(cell, &na_tokens)
+                .map_err(|e| format!("Row {}, column {}: {}", rows + 1, col, e))?;
+            data.push(value);
+        }
         rows += 1;
     }
 
@@ -47,12 +85,33 @@ pub fn csv_read(filepath: String) -> Result<Array2<f64>, Box<dyn Error>> {
 /// - `output_columns: Vec<String>` - Column names to extract as output
 /// - `input_exclude_columns: Vec<String>` - Column names to be excluded from input
 ///
+/// Empty cells and `DEFAULT_NA_TOKENS` are read as `NaN`.
+///
 /// # Returns:
 /// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input Array, Output Array)
 pub fn csv_read_input_output(
     filepath: String,
     output_columns: Vec<String>,
     input_exclude_columns: Vec<String>,
+) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+    csv_read_input_output_with_na(filepath, output_columns, input_exclude_columns, default_na_tokens())
+}
+
+/// Function to parse CSV and extract input & output columns, with custom missing-value tokens
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the CSV file wrt Cargo.toml file of the project
+/// - `output_columns: Vec<String>` - Column names to extract as output
+/// - `input_exclude_columns: Vec<String>` - Column names to be excluded from input
+/// - `na_tokens: Vec<String>` - Cell values to be read as `NaN` (empty cells are always missing)
+///
+/// # Returns:
+/// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input Array, Output Array)
+pub fn csv_read_input_output_with_na(
+    filepath: String,
+    output_columns: Vec<String>,
+    input_exclude_columns: Vec<String>,
+    na_tokens: Vec<String>,
 ) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
     let file = File::open(filepath)?;
     let reader = BufReader::new(file);
@@ -80,10 +139,10 @@ pub fn csv_read_input_output(
     for result in csv_reader.records() {
         let record = result?;
         for &i in &input_indices {
-            input_data.push(record[i].parse::<f64>().unwrap_or(0.0));
+            input_data.push(parse_column(&record[i], &headers[i], row_count, &na_tokens)?);
         }
         for &i in &output_indices {
-            output_data.push(record[i].parse::<f64>().unwrap_or(0.0));
+            output_data.push(parse_column(&record[i], &headers[i], row_count, &na_tokens)?);
         }
         row_count += 1;
     }
@@ -97,11 +156,16 @@ pub fn csv_read_input_output(
     Ok((input_array, output_array))
 }
 
+fn parse_column(cell: &str, header: &str, row: usize, na_tokens: &[String]) -> Result<f64, Box<dyn Error>> {
+    parse_cell(cell, na_tokens)
+        .map_err(|e| format!("Row {}, column '{}': {}", row + 1, header, e).into())
+}
+
 /// Function to save a 2D Array as a CSV file
 ///
 /// # Parameters:
 /// - `filepath: String` - Relative path of the CSV file wrt Cargo.toml file of the project
-/// - `headers: Vec<String>` - Column names
+/// - `headers: Vec<String>` - Column names, written as the first row unless empty
 /// - `array: Array2<f64>` - The array to be saved
 ///
 /// # Returns:
@@ -114,7 +178,9 @@ pub fn csv_write(
     let mut writer = WriterBuilder::new().from_path(filepath)?;
 
     // Write headers
-    // writer.write_record(&headers)?;
+    if !headers.is_empty() {
+        writer.write_record(&headers)?;
+    }
 
     // Write data
     for row in array.axis_iter(ndarray::Axis(0)) {
@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+use csv::{QuoteStyle, ReaderBuilder, WriterBuilder};
+use ndarray::{Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::data_utils::{parse_cell, DEFAULT_NA_TOKENS};
+
+/// Type of a dataset column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnType {
+    Float,
+    Integer,
+    Text, // Kept as raw strings, stored as `NaN` in the numeric matrix
+}
+
+/// Reference to a dataset column by header name or position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ColumnSelector {
+    Name(String),
+    Index(usize),
+}
+
+impl ColumnSelector {
+    /// Position of the column among `names`
+    pub fn resolve(&self, names: &[String]) -> Result<usize, Box<dyn Error>> {
+        match self {
+            ColumnSelector::Name(name) => names
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| format!("Column '{}' not found", name).into()),
+            ColumnSelector::Index(index) if *index < names.len() => Ok(*index),
+            ColumnSelector::Index(index) => {
+                Err(format!("Column index {} out of range for {} columns", index, names.len()).into())
+            }
+        }
+    }
+}
+
+impl From<&str> for ColumnSelector {
+    fn from(name: &str) -> Self {
+        ColumnSelector::Name(name.to_string())
+    }
+}
+
+impl From<String> for ColumnSelector {
+    fn from(name: String) -> Self {
+        ColumnSelector::Name(name)
+    }
+}
+
+impl From<usize> for ColumnSelector {
+    fn from(index: usize) -> Self {
+        ColumnSelector::Index(index)
+    }
+}
+
+/// Options used to read and write delimited text files
+#[derive(Debug, Clone)]
+pub struct CsvConfig {
+    pub delimiter: u8,
+    pub quote: u8,
+    pub quoting: bool,
+    pub has_headers: bool,
+    pub na_tokens: Vec<String>,
+    pub column_types: Vec<(ColumnSelector, ColumnType)>,
+}
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            quoting: true,
+            has_headers: true,
+            na_tokens: DEFAULT_NA_TOKENS.iter().map(|token| token.to_string()).collect(),
+            column_types: Vec::new(),
+        }
+    }
+}
+
+pub struct CsvConfigBuilder {
+    config: CsvConfig,
+}
+
+impl Default for CsvConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsvConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: CsvConfig::default(),
+        }
+    }
+
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.config.delimiter = delimiter;
+        self
+    }
+
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.config.quote = quote;
+        self
+    }
+
+    pub fn quoting(mut self, quoting: bool) -> Self {
+        self.config.quoting = quoting;
+        self
+    }
+
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.config.has_headers = has_headers;
+        self
+    }
+
+    pub fn na_tokens(mut self, na_tokens: Vec<String>) -> Self {
+        self.config.na_tokens = na_tokens;
+        self
+    }
+
+    /// Overrides the inferred type of a column
+    pub fn column_type(mut self, column: impl Into<ColumnSelector>, column_type: ColumnType) -> Self {
+        self.config.column_types.push((column.into(), column_type));
+        self
+    }
+
+    pub fn build(self) -> CsvConfig {
+        self.config
+    }
+}
+
+/// Tabular data carrying column names and types alongside its numeric matrix
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    names: Vec<String>,
+    types: Vec<ColumnType>,
+    data: Array2<f64>,
+    text: HashMap<usize, Vec<String>>,
+}
+
+impl Dataset {
+    /// Creates a dataset of `Float` columns from a matrix
+    pub fn new(names: Vec<String>, data: Array2<f64>) -> Result<Self, Box<dyn Error>> {
+        if names.len() != data.ncols() {
+            return Err(format!("Got {} column names for {} columns", names.len(), data.ncols()).into());
+        }
+        Ok(Self {
+            types: vec![ColumnType::Float; names.len()],
+            names,
+            data,
+            text: HashMap::new(),
+        })
+    }
+
+    /// Function to read a delimited text file into a typed dataset
+    ///
+    /// # Parameters:
+    /// - `filepath: String` - Relative path of the CSV file wrt Cargo.toml file of the project
+    /// - `config: &CsvConfig` - Delimiter, quoting, header and column type options
+    ///
+    /// Column types not set in `config` are inferred: a column is `Integer` or `Float`
+    /// if every non-missing cell parses as such, otherwise `Text`.
+    ///
+    /// # Returns:
+    /// - `Result<Dataset, Box<dyn Error>>`
+    pub fn read_csv(filepath: String, config: &CsvConfig) -> Result<Self, Box<dyn Error>> {
+        let file = File::open(filepath)?;
+        let mut csv_reader = ReaderBuilder::new()
+            .delimiter(config.delimiter)
+            .quote(config.quote)
+            .quoting(config.quoting)
+            .has_headers(config.has_headers)
+            .from_reader(BufReader::new(file));
+
+        let mut cells: Vec<Vec<String>> = Vec::new();
+        for result in csv_reader.records() {
+            let record = result?;
+            cells.push(record.iter().map(|cell| cell.to_string()).collect());
+        }
+
+        let cols = match cells.first() {
+            Some(row) => row.len(),
+            None if config.has_headers => csv_reader.headers()?.len(),
+            None => 0,
+        };
+        let names: Vec<String> = if config.has_headers {
+            csv_reader.headers()?.iter().map(|name| name.to_string()).collect()
+        } else {
+            (0..cols).map(|i| format!("column_{}", i)).collect()
+        };
+
+        let mut types = Vec::with_capacity(cols);
+        for col in 0..cols {
+            types.push(infer_type(cells.iter().map(|row| row[col].as_str()), &config.na_tokens));
+        }
+        for (selector, column_type) in &config.column_types {
+            types[selector.resolve(&names)?] = *column_type;
+        }
+
+        let mut data = Array2::<f64>::zeros((cells.len(), cols));
+        let mut text = HashMap::new();
+        for col in 0..cols {
+            if types[col] == ColumnType::Text {
+                data.column_mut(col).fill(f64::NAN);
+                text.insert(col, cells.iter().map(|row| row[col].clone()).collect());
+                continue;
+            }
+            for (row, values) in cells.iter().enumerate() {
+                data[(row, col)] = parse_cell(&values[col], &config.na_tokens)
+                    .map_err(|e| format!("Row {}, column '{}': {}", row + 1, names[col], e))?;
+            }
+        }
+
+        Ok(Self { names, types, data, text })
+    }
+
+    /// Function to write the dataset as a delimited text file
+    ///
+    /// # Parameters:
+    /// - `filepath: String` - Relative path of the CSV file wrt Cargo.toml file of the project
+    /// - `config: &CsvConfig` - Delimiter, quoting and header options
+    ///
+    /// Missing values are written as empty cells.
+    ///
+    /// # Returns:
+    /// - `Result<(), Box<dyn Error>>` - Error if an `Integer` column holds a non-integral value
+    pub fn write_csv(&self, filepath: String, config: &CsvConfig) -> Result<(), Box<dyn Error>> {
+        let mut writer = WriterBuilder::new()
+            .delimiter(config.delimiter)
+            .quote(config.quote)
+            .quote_style(if config.quoting { QuoteStyle::Necessary } else { QuoteStyle::Never })
+            .from_path(filepath)?;
+
+        if config.has_headers {
+            writer.write_record(&self.names)?;
+        }
+
+        for (row, values) in self.data.axis_iter(Axis(0)).enumerate() {
+            let record = values
+                .iter()
+                .enumerate()
+                .map(|(col, &value)| match self.types[col] {
+                    ColumnType::Text => Ok(self.text[&col][row].clone()),
+                    // Empty cells are always read back as missing
+                    _ if value.is_nan() => Ok(String::new()),
+                    ColumnType::Integer if value.fract() != 0. => Err(format!(
+                        "Row {}, column '{}': {} is not an integer",
+                        row + 1,
+                        self.names[col],
+                        value
+                    )),
+                    ColumnType::Integer => Ok(format!("{:.0}", value)),
+                    ColumnType::Float => Ok(value.to_string()),
+                })
+                .collect::<Result<Vec<String>, String>>()?;
+            writer.write_record(&record)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn column_types(&self) -> &[ColumnType] {
+        &self.types
+    }
+
+    /// Numeric matrix of every column (`Text` columns are `NaN`)
+    pub fn data(&self) -> &Array2<f64> {
+        &self.data
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.data.nrows()
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.data.ncols()
+    }
+
+    /// Raw values of a `Text` column
+    pub fn text_column(&self, column: &ColumnSelector) -> Result<&[String], Box<dyn Error>> {
+        let index = self.column_index(column)?;
+        self.text
+            .get(&index)
+            .map(|values| values.as_slice())
+            .ok_or_else(|| format!("Column '{}' is not a text column", self.names[index]).into())
+    }
+
+    pub fn column_index(&self, column: &ColumnSelector) -> Result<usize, Box<dyn Error>> {
+        column.resolve(&self.names)
+    }
+
+    /// Numeric matrix of the selected columns, in the given order
+    pub fn select(&self, columns: &[ColumnSelector]) -> Result<Array2<f64>, Box<dyn Error>> {
+        let indices = self.numeric_indices(columns)?;
+        Ok(self.data.select(Axis(1), &indices))
+    }
+
+    /// Sub-dataset of the selected columns, keeping names, types and text values
+    pub fn select_dataset(&self, columns: &[ColumnSelector]) -> Result<Dataset, Box<dyn Error>> {
+        let indices = columns
+            .iter()
+            .map(|column| self.column_index(column))
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        let mut text = HashMap::new();
+        for (new_index, &old_index) in indices.iter().enumerate() {
+            if let Some(values) = self.text.get(&old_index) {
+                text.insert(new_index, values.clone());
+            }
+        }
+
+        Ok(Dataset {
+            names: indices.iter().map(|&i| self.names[i].clone()).collect(),
+            types: indices.iter().map(|&i| self.types[i]).collect(),
+            data: self.data.select(Axis(1), &indices),
+            text,
+        })
+    }
+
+    /// Function to extract input & output matrices
+    ///
+    /// # Parameters:
+    /// - `output_columns: &[ColumnSelector]` - Columns to extract as output
+    /// - `input_exclude_columns: &[ColumnSelector]` - Columns to be excluded from input
+    ///
+    /// `Text` columns are never used as input.
+    ///
+    /// # Returns:
+    /// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input Array, Output Array)
+    pub fn input_output(
+        &self,
+        output_columns: &[ColumnSelector],
+        input_exclude_columns: &[ColumnSelector],
+    ) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+        let output_indices = self.numeric_indices(output_columns)?;
+        let excluded = input_exclude_columns
+            .iter()
+            .map(|column| self.column_index(column))
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        let input_indices: Vec<usize> = (0..self.ncols())
+            .filter(|i| !output_indices.contains(i) && !excluded.contains(i))
+            .filter(|&i| self.types[i] != ColumnType::Text)
+            .collect();
+
+        Ok((
+            self.data.select(Axis(1), &input_indices),
+            self.data.select(Axis(1), &output_indices),
+        ))
+    }
+
+    /// Names of the columns `input_output` uses as input
+    pub fn input_names(
+        &self,
+        output_columns: &[ColumnSelector],
+        input_exclude_columns: &[ColumnSelector],
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut skipped = Vec::new();
+        for column in output_columns.iter().chain(input_exclude_columns) {
+            skipped.push(self.column_index(column)?);
+        }
+        Ok((0..self.ncols())
+            .filter(|i| !skipped.contains(i) && self.types[*i] != ColumnType::Text)
+            .map(|i| self.names[i].clone())
+            .collect())
+    }
+
+    /// Appends the columns of `other` (e.g. predictions next to passed-through ID columns)
+    pub fn hstack(&self, other: &Dataset) -> Result<Dataset, Box<dyn Error>> {
+        if self.nrows() != other.nrows() {
+            return Err(format!("Cannot join datasets with {} and {} rows", self.nrows(), other.nrows()).into());
+        }
+
+        let mut text = self.text.clone();
+        for (&index, values) in &other.text {
+            text.insert(index + self.ncols(), values.clone());
+        }
+
+        Ok(Dataset {
+            names: self.names.iter().chain(&other.names).cloned().collect(),
+            types: self.types.iter().chain(&other.types).copied().collect(),
+            data: ndarray::concatenate(Axis(1), &[self.data.view(), other.data.view()])?,
+            text,
+        })
+    }
+
+    fn numeric_indices(&self, columns: &[ColumnSelector]) -> Result<Vec<usize>, Box<dyn Error>> {
+        columns
+            .iter()
+            .map(|column| {
+                let index = self.column_index(column)?;
+                if self.types[index] == ColumnType::Text {
+                    return Err(format!("Column '{}' is a text column", self.names[index]).into());
+                }
+                Ok(index)
+            })
+            .collect()
+    }
+}
+
+fn infer_type<'a>(cells: impl Iterator<Item = &'a str>, na_tokens: &[String]) -> ColumnType {
+    let mut column_type = ColumnType::Integer;
+    for cell in cells {
+        let cell = cell.trim();
+        if cell.is_empty() || na_tokens.iter().any(|token| token == cell) {
+            continue;
+        }
+        if cell.parse::<i64>().is_ok() {
+            continue;
+        }
+        if cell.parse::<f64>().is_ok() {
+            column_type = ColumnType::Float;
+        } else {
+            return ColumnType::Text;
+        }
+    }
+    column_type
+}
@@ -0,0 +1,139 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use ndarray::Array2;
+use serde_json::{Map, Value};
+use crate::data_utils::{parse_cell, DEFAULT_NA_TOKENS};
+
+/// Function to read selected fields of a JSON-lines file
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the JSON-lines file wrt Cargo.toml file of the project
+/// - `fields: Vec<String>` - Fields to extract, in order; nested fields are addressed as `"outer.inner"`
+///
+/// Missing fields and `null` are read as `NaN`, booleans as 0/1 and numeric strings are parsed.
+///
+/// # Returns:
+/// - `Result<Array2<f64>, Box<dyn Error>>` - 2D Array with one row per record and one column per field
+pub fn jsonl_read(filepath: String, fields: Vec<String>) -> Result<Array2<f64>, Box<dyn Error>> {
+    let records = read_records(filepath)?;
+    records_to_array(&records, &fields)
+}
+
+/// Function to parse a JSON-lines file and extract input & output fields
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the JSON-lines file wrt Cargo.toml file of the project
+/// - `output_fields: Vec<String>` - Fields to extract as output
+/// - `input_exclude_fields: Vec<String>` - Fields to be excluded from input
+///
+/// Input fields are the top-level fields of the first record that hold numbers,
+/// booleans or `null`, in file order.
+///
+/// # Returns:
+/// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input Array, Output Array)
+pub fn jsonl_read_input_output(
+    filepath: String,
+    output_fields: Vec<String>,
+    input_exclude_fields: Vec<String>,
+) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+    let records = read_records(filepath)?;
+
+    let input_fields: Vec<String> = match records.first() {
+        Some(first) => first
+            .iter()
+            .filter(|(key, _)| !output_fields.contains(key) && !input_exclude_fields.contains(key))
+            .filter(|(_, value)| matches!(value, Value::Number(_) | Value::Bool(_) | Value::Null))
+            .map(|(key, _)| key.clone())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Ok((
+        records_to_array(&records, &input_fields)?,
+        records_to_array(&records, &output_fields)?,
+    ))
+}
+
+/// Function to save a 2D Array as a JSON-lines file, one object per row
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the JSON-lines file wrt Cargo.toml file of the project
+/// - `headers: Vec<String>` - Field names of the columns
+/// - `array: &Array2<f64>` - The array to be saved (`NaN` is written as `null`)
+///
+/// # Returns:
+/// - `Result<(), Box<dyn Error>>`
+pub fn jsonl_write(filepath: String, headers: Vec<String>, array: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+    if headers.len() != array.ncols() {
+        return Err(format!("Got {} headers for {} columns", headers.len(), array.ncols()).into());
+    }
+
+    let mut writer = BufWriter::new(File::create(filepath)?);
+    for row in array.rows() {
+        let record: Map<String, Value> = headers
+            .iter()
+            .zip(row.iter())
+            .map(|(name, &value)| {
+                let value = serde_json::Number::from_f64(value).map(Value::Number).unwrap_or(Value::Null);
+                (name.clone(), value)
+            })
+            .collect();
+        serde_json::to_writer(&mut writer, &record)?;
+        writer.write_all(b"\n")?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_records(filepath: String) -> Result<Vec<Map<String, Value>>, Box<dyn Error>> {
+    let reader = BufReader::new(File::open(filepath)?);
+    let mut records = Vec::new();
+
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line)? {
+            Value::Object(record) => records.push(record),
+            _ => return Err(format!("Line {} is not a JSON object", line_number + 1).into()),
+        }
+    }
+
+    Ok(records)
+}
+
+fn records_to_array(records: &[Map<String, Value>], fields: &[String]) -> Result<Array2<f64>, Box<dyn Error>> {
+    let na_tokens: Vec<String> = DEFAULT_NA_TOKENS.iter().map(|token| token.to_string()).collect();
+    let mut data = Vec::with_capacity(records.len() * fields.len());
+
+    for (row, record) in records.iter().enumerate() {
+        for field in fields {
+            let value = match lookup(record, field) {
+                None | Some(Value::Null) => f64::NAN,
+                Some(Value::Bool(flag)) => if *flag { 1.0 } else { 0.0 },
+                Some(Value::Number(number)) => number.as_f64().unwrap_or(f64::NAN),
+                Some(Value::String(text)) => parse_cell(text, &na_tokens)
+                    .map_err(|e| format!("Record {}, field '{}': {}", row + 1, field, e))?,
+                Some(_) => return Err(format!("Record {}, field '{}' is not a scalar", row + 1, field).into()),
+            };
+            data.push(value);
+        }
+    }
+
+    Ok(Array2::from_shape_vec((records.len(), fields.len()), data)?)
+}
+
+fn lookup<'a>(record: &'a Map<String, Value>, field: &str) -> Option<&'a Value> {
+    if let Some(value) = record.get(field) {
+        return Some(value);
+    }
+    let mut parts = field.split('.');
+    let mut value = record.get(parts.next()?)?;
+    for part in parts {
+        value = value.as_object()?.get(part)?;
+    }
+    Some(value)
+}
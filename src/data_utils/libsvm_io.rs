@@ -0,0 +1,129 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use ndarray::Array2;
+use crate::matrix_operations::CsrMatrix;
+
+/// Parsed LIBSVM rows: one label and a list of (zero-based column, value) pairs per row
+pub(crate) struct LibsvmRows {
+    pub(crate) labels: Vec<f64>,
+    pub(crate) rows: Vec<Vec<(usize, f64)>>,
+    pub(crate) n_features: usize,
+}
+
+/// Function to read a LIBSVM / SVMlight sparse text file
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the file wrt Cargo.toml file of the project
+/// - `n_features: Option<usize>` - Number of input columns, inferred from the largest index if `None`
+///
+/// Each line is `label index:value index:value ... # comment`. Indices are one-based
+/// unless an index `0` appears in the file. `qid:` entries are ignored.
+///
+/// The input is returned as a `CsrMatrix`, never densified; call `to_dense` for an `Array2`.
+///
+/// # Returns:
+/// - `Result<(CsrMatrix, Array2<f64>), Box<dyn Error>>` - Tuple (Sparse Input Matrix, Output Array)
+pub fn libsvm_read(
+    filepath: String,
+    n_features: Option<usize>,
+) -> Result<(CsrMatrix, Array2<f64>), Box<dyn Error>> {
+    let parsed = libsvm_parse(filepath, n_features)?;
+
+    let triplets = parsed
+        .rows
+        .iter()
+        .enumerate()
+        .flat_map(|(row, entries)| entries.iter().map(move |&(col, value)| (row, col, value)))
+        .collect();
+    let input = CsrMatrix::from_triplets((parsed.rows.len(), parsed.n_features), triplets)?;
+    let output = Array2::from_shape_vec((parsed.labels.len(), 1), parsed.labels)?;
+
+    Ok((input, output))
+}
+
+/// Function to save input & output arrays as a LIBSVM file (one-based indices, zeros omitted)
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the file wrt Cargo.toml file of the project
+/// - `input: &Array2<f64>` - Input array
+/// - `output: &Array2<f64>` - Output array with a single column of labels
+///
+/// # Returns:
+/// - `Result<(), Box<dyn Error>>`
+pub fn libsvm_write(filepath: String, input: &Array2<f64>, output: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+    if output.ncols() != 1 || output.nrows() != input.nrows() {
+        return Err("LIBSVM output must be a single column with one label per input row".into());
+    }
+
+    let mut writer = BufWriter::new(File::create(filepath)?);
+    for (row, label) in input.rows().into_iter().zip(output.column(0)) {
+        write!(writer, "{}", label)?;
+        for (col, value) in row.iter().enumerate().filter(|(_, &value)| value != 0.0) {
+            write!(writer, " {}:{}", col + 1, value)?;
+        }
+        writeln!(writer)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+pub(crate) fn libsvm_parse(filepath: String, n_features: Option<usize>) -> Result<LibsvmRows, Box<dyn Error>> {
+    let reader = BufReader::new(File::open(filepath)?);
+    let mut labels = Vec::new();
+    let mut rows = Vec::new();
+    let mut zero_based = false;
+    let mut max_index = 0;
+
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let content = line.split('#').next().unwrap_or("").trim();
+        if content.is_empty() {
+            continue;
+        }
+
+        let mut tokens = content.split_whitespace();
+        let label = tokens.next().unwrap_or_default();
+        labels.push(
+            label
+                .parse::<f64>()
+                .map_err(|_| format!("Line {}: invalid label '{}'", line_number + 1, label))?,
+        );
+
+        let mut entries = Vec::new();
+        for token in tokens {
+            let (index, value) = token
+                .split_once(':')
+                .ok_or(format!("Line {}: invalid entry '{}'", line_number + 1, token))?;
+            if index == "qid" {
+                continue;
+            }
+            let index: usize = index
+                .parse()
+                .map_err(|_| format!("Line {}: invalid index '{}'", line_number + 1, index))?;
+            let value: f64 = value
+                .parse()
+                .map_err(|_| format!("Line {}: invalid value '{}'", line_number + 1, value))?;
+            zero_based |= index == 0;
+            max_index = max_index.max(index);
+            entries.push((index, value));
+        }
+        rows.push(entries);
+    }
+
+    let offset = if zero_based { 0 } else { 1 };
+    let inferred = if rows.iter().all(|entries| entries.is_empty()) { 0 } else { max_index + 1 - offset };
+    let n_features = n_features.unwrap_or(inferred);
+    if inferred > n_features {
+        return Err(format!("File has {} features but n_features is {}", inferred, n_features).into());
+    }
+
+    for entries in rows.iter_mut() {
+        for entry in entries.iter_mut() {
+            entry.0 -= offset;
+        }
+    }
+
+    Ok(LibsvmRows { labels, rows, n_features })
+}
@@ -0,0 +1,77 @@
+use ndarray::{Array2, Axis};
+
+/// Per-column summary of missing (`NaN`) values in a dataset
+#[derive(Debug, Clone)]
+pub struct MissingReport {
+    pub rows: usize,
+    pub missing_counts: Vec<usize>,
+    pub column_names: Option<Vec<String>>,
+}
+
+impl MissingReport {
+    /// Total number of missing cells
+    pub fn total_missing(&self) -> usize {
+        self.missing_counts.iter().sum()
+    }
+
+    /// Whether any cell is missing
+    pub fn has_missing(&self) -> bool {
+        self.total_missing() > 0
+    }
+
+    /// Indices of the columns that contain at least one missing cell
+    pub fn columns_with_missing(&self) -> Vec<usize> {
+        self.missing_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Fraction of missing cells for each column
+    pub fn missing_fractions(&self) -> Vec<f64> {
+        self.missing_counts
+            .iter()
+            .map(|&count| if self.rows == 0 { 0.0 } else { count as f64 / self.rows as f64 })
+            .collect()
+    }
+
+    /// Attaches column names (e.g. CSV headers) to the report
+    pub fn with_column_names(mut self, column_names: Vec<String>) -> Self {
+        self.column_names = Some(column_names);
+        self
+    }
+
+    pub fn print(&self) {
+        println!("Missing values ({} rows):", self.rows);
+        for (i, (count, fraction)) in self.missing_counts.iter().zip(self.missing_fractions()).enumerate() {
+            let name = self
+                .column_names
+                .as_ref()
+                .and_then(|names| names.get(i).cloned())
+                .unwrap_or_else(|| format!("column {}", i));
+            println!("  {}: {} ({:.2}%)", name, count, fraction * 100.0);
+        }
+    }
+}
+
+/// Function to count missing (`NaN`) values per column
+///
+/// # Parameters:
+/// - `data: &Array2<f64>` - Data matrix as returned by the CSV readers
+///
+/// # Returns:
+/// - `MissingReport` - Missing counts for every column
+pub fn missing_report(data: &Array2<f64>) -> MissingReport {
+    let missing_counts = data
+        .axis_iter(Axis(1))
+        .map(|column| column.iter().filter(|v| v.is_nan()).count())
+        .collect();
+
+    MissingReport {
+        rows: data.nrows(),
+        missing_counts,
+        column_names: None,
+    }
+}
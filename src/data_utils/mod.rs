@@ -1,5 +1,19 @@
 mod train_test_split;
 mod csv_io;
+mod missing;
+mod dataset;
+mod npy_io;
+mod jsonl_io;
+mod libsvm_io;
+#[cfg(feature = "parquet")]
+mod parquet_io;
 
 pub use train_test_split::*;
-pub use csv_io::*;
\ No newline at end of file
+pub use csv_io::*;
+pub use missing::*;
+pub use dataset::*;
+pub use npy_io::*;
+pub use jsonl_io::*;
+pub use libsvm_io::*;
+#[cfg(feature = "parquet")]
+pub use parquet_io::*;
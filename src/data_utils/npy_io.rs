@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use ndarray::Array2;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Function to read a NumPy `.npy` file
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the `.npy` file wrt Cargo.toml file of the project
+///
+/// Integer, unsigned, boolean and floating point dtypes of either byte order are
+/// converted to `f64`. 1-D arrays are read as a single column, Fortran-ordered
+/// arrays are reordered.
+///
+/// # Returns:
+/// - `Result<Array2<f64>, Box<dyn Error>>` - 2D Array of the stored values
+pub fn npy_read(filepath: String) -> Result<Array2<f64>, Box<dyn Error>> {
+    let file = File::open(filepath)?;
+    let len = file.metadata()?.len();
+    npy_from_reader(BufReader::new(file), len)
+}
+
+/// Function to save a 2D Array as a NumPy `.npy` file (`<f8`, C order)
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the `.npy` file wrt Cargo.toml file of the project
+/// - `array: &Array2<f64>` - The array to be saved
+///
+/// # Returns:
+/// - `Result<(), Box<dyn Error>>`
+pub fn npy_write(filepath: String, array: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+    let mut writer = BufWriter::new(File::create(filepath)?);
+    npy_to_writer(&mut writer, array)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Function to read every array of a NumPy `.npz` archive
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the `.npz` file wrt Cargo.toml file of the project
+///
+/// # Returns:
+/// - `Result<HashMap<String, Array2<f64>>, Box<dyn Error>>` - Arrays keyed by their name (without `.npy`)
+pub fn npz_read(filepath: String) -> Result<HashMap<String, Array2<f64>>, Box<dyn Error>> {
+    let file = File::open(filepath)?;
+    let mut archive = ZipArchive::new(BufReader::new(file))?;
+    let mut arrays = HashMap::new();
+
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i)?;
+        let name = entry.name().trim_end_matches(".npy").to_string();
+        let len = entry.size();
+        arrays.insert(name, npy_from_reader(entry, len)?);
+    }
+
+    Ok(arrays)
+}
+
+/// Function to extract input & output arrays from a NumPy `.npz` archive
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the `.npz` file wrt Cargo.toml file of the project
+/// - `input_key: &str` - Name of the input array (e.g. `"x"`)
+/// - `output_key: &str` - Name of the output array (e.g. `"y"`)
+///
+/// # Returns:
+/// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input Array, Output Array)
+pub fn npz_read_input_output(
+    filepath: String,
+    input_key: &str,
+    output_key: &str,
+) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+    let mut arrays = npz_read(filepath)?;
+    let input = arrays.remove(input_key).ok_or(format!("Array '{}' not found in archive", input_key))?;
+    let output = arrays.remove(output_key).ok_or(format!("Array '{}' not found in archive", output_key))?;
+    if input.nrows() != output.nrows() {
+        return Err(format!("Input has {} rows but output has {}", input.nrows(), output.nrows()).into());
+    }
+    Ok((input, output))
+}
+
+/// Function to save named 2D Arrays as an uncompressed NumPy `.npz` archive
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the `.npz` file wrt Cargo.toml file of the project
+/// - `arrays: &[(&str, &Array2<f64>)]` - Arrays and the names they are stored under
+///
+/// # Returns:
+/// - `Result<(), Box<dyn Error>>`
+pub fn npz_write(filepath: String, arrays: &[(&str, &Array2<f64>)]) -> Result<(), Box<dyn Error>> {
+    let mut zip = ZipWriter::new(File::create(filepath)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+    for (name, array) in arrays {
+        zip.start_file(format!("{}.npy", name), options)?;
+        npy_to_writer(&mut zip, array)?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Parses a `.npy` stream of `len` bytes, checking every size in the header against `len`
+/// before allocating for it
+fn npy_from_reader<R: Read>(mut reader: R, len: u64) -> Result<Array2<f64>, Box<dyn Error>> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic[..6] != NPY_MAGIC {
+        return Err("Not a NumPy .npy file".into());
+    }
+
+    let (header_len, prefix_len) = if magic[6] == 1 {
+        let mut header_len = [0u8; 2];
+        reader.read_exact(&mut header_len)?;
+        (u16::from_le_bytes(header_len) as usize, 10)
+    } else {
+        let mut header_len = [0u8; 4];
+        reader.read_exact(&mut header_len)?;
+        (u32::from_le_bytes(header_len) as usize, 12)
+    };
+    let mut remaining = len.saturating_sub(prefix_len);
+    if header_len as u64 > remaining {
+        return Err(format!("The .npy header claims {} bytes but only {} remain", header_len, remaining).into());
+    }
+    remaining -= header_len as u64;
+    let mut header = vec![0u8; header_len];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8(header)?;
+
+    let descr = header_value(&header, "descr")?.trim_matches(|c| c == '\'' || c == '"').to_string();
+    let fortran_order = header_value(&header, "fortran_order")?.trim() == "True";
+    let shape: Vec<usize> = header_value(&header, "shape")?
+        .trim_matches(|c| c == '(' || c == ')')
+        .split(',')
+        .map(|dim| dim.trim())
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.parse::<usize>())
+        .collect::<Result<_, _>>()?;
+
+    let (rows, cols) = match shape.as_slice() {
+        [] => (1, 1),
+        [n] => (*n, 1),
+        [rows, cols] => (*rows, *cols),
+        _ => return Err(format!("Only 1-D and 2-D arrays are supported, got shape {:?}", shape).into()),
+    };
+
+    let (big_endian, kind, size) = parse_descr(&descr)?;
+    let data_len = rows
+        .checked_mul(cols)
+        .and_then(|count| count.checked_mul(size))
+        .ok_or(format!("The .npy shape {:?} is too large", shape))?;
+    if data_len as u64 > remaining {
+        return Err(format!(
+            "The .npy shape {:?} needs {} bytes of data but only {} remain",
+            shape, data_len, remaining
+        ).into());
+    }
+    let mut bytes = vec![0u8; data_len];
+    reader.read_exact(&mut bytes)?;
+
+    let values: Vec<f64> = bytes
+        .chunks_exact(size)
+        .map(|chunk| decode(chunk, big_endian, kind))
+        .collect::<Result<_, _>>()?;
+
+    if fortran_order {
+        Ok(Array2::from_shape_vec((cols, rows), values)?.reversed_axes().as_standard_layout().to_owned())
+    } else {
+        Ok(Array2::from_shape_vec((rows, cols), values)?)
+    }
+}
+
+fn npy_to_writer<W: Write>(writer: &mut W, array: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+    let mut header = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, {}), }}",
+        array.nrows(),
+        array.ncols()
+    );
+    // Pad so that the data starts on a 64-byte boundary
+    let unpadded = NPY_MAGIC.len() + 2 + 2 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    writer.write_all(NPY_MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for value in array.iter() {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, Box<dyn Error>> {
+    let start = header
+        .find(&format!("'{}':", key))
+        .ok_or(format!("Missing '{}' in .npy header", key))?
+        + key.len()
+        + 3;
+    let rest = header[start..].trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')').map(|i| i + 1)
+    } else {
+        rest.find(',').or_else(|| rest.find('}'))
+    }
+    .ok_or("Malformed .npy header")?;
+    Ok(&rest[..end])
+}
+
+fn parse_descr(descr: &str) -> Result<(bool, char, usize), Box<dyn Error>> {
+    let mut chars = descr.chars();
+    let (big_endian, kind) = match chars.next() {
+        Some('>') => (true, chars.next()),
+        Some('<') | Some('|') | Some('=') => (false, chars.next()),
+        other => (false, other),
+    };
+    let kind = kind.ok_or("Empty dtype in .npy header")?;
+    let size: usize = chars.as_str().parse()?;
+    match (kind, size) {
+        ('f', 4 | 8) | ('i', 1 | 2 | 4 | 8) | ('u', 1 | 2 | 4 | 8) | ('b', 1) => Ok((big_endian, kind, size)),
+        _ => Err(format!("Unsupported dtype '{}'", descr).into()),
+    }
+}
+
+fn decode(chunk: &[u8], big_endian: bool, kind: char) -> Result<f64, Box<dyn Error>> {
+    let mut bytes = chunk.to_vec();
+    if big_endian {
+        bytes.reverse();
+    }
+    Ok(match (kind, bytes.len()) {
+        ('f', 4) => f32::from_le_bytes(bytes[..].try_into()?) as f64,
+        ('f', 8) => f64::from_le_bytes(bytes[..].try_into()?),
+        ('i', 1) => bytes[0] as i8 as f64,
+        ('i', 2) => i16::from_le_bytes(bytes[..].try_into()?) as f64,
+        ('i', 4) => i32::from_le_bytes(bytes[..].try_into()?) as f64,
+        ('i', 8) => i64::from_le_bytes(bytes[..].try_into()?) as f64,
+        ('u', 1) | ('b', 1) => bytes[0] as f64,
+        ('u', 2) => u16::from_le_bytes(bytes[..].try_into()?) as f64,
+        ('u', 4) => u32::from_le_bytes(bytes[..].try_into()?) as f64,
+        ('u', 8) => u64::from_le_bytes(bytes[..].try_into()?) as f64,
+        _ => return Err(format!("Unsupported dtype '{}{}'", kind, bytes.len()).into()),
+    })
+}
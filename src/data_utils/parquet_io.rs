@@ -0,0 +1,287 @@
+use std::error::Error;
+use std::fs::File;
+use std::sync::Arc;
+use arrow::array::{Array, ArrayRef, Float64Array, RecordBatch};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+use ndarray::Array2;
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::{ArrowWriter, ProjectionMask};
+use parquet::file::properties::WriterProperties;
+
+/// Function to read selected columns of a Parquet file
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the Parquet file wrt Cargo.toml file of the project
+/// - `columns: Vec<String>` - Columns to read, in order; only these are decoded
+///
+/// Numeric and boolean columns are converted to `f64`, nulls are read as `NaN`.
+///
+/// # Returns:
+/// - `Result<Array2<f64>, Box<dyn Error>>` - 2D Array with one column per selected column
+pub fn parquet_read(filepath: String, columns: Vec<String>) -> Result<Array2<f64>, Box<dyn Error>> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(filepath)?)?;
+    let indices = projection_indices(builder.schema(), &columns)?;
+    let mask = ProjectionMask::roots(builder.parquet_schema(), indices);
+
+    let batches = builder.with_projection(mask).build()?.collect::<Result<Vec<_>, _>>()?;
+    batches_to_array(&batches, &columns)
+}
+
+/// Function to parse a Parquet file and extract input & output columns
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the Parquet file wrt Cargo.toml file of the project
+/// - `output_columns: Vec<String>` - Column names to extract as output
+/// - `input_exclude_columns: Vec<String>` - Column names to be excluded from input
+///
+/// Inputs are the remaining numeric and boolean columns; other columns are not decoded.
+///
+/// # Returns:
+/// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input Array, Output Array)
+pub fn parquet_read_input_output(
+    filepath: String,
+    output_columns: Vec<String>,
+    input_exclude_columns: Vec<String>,
+) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+    let batches = parquet_batches(filepath, output_columns, input_exclude_columns)?;
+    let (input_cols, output_cols) = (batches.input_columns.len(), batches.output_columns.len());
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    for batch in batches {
+        let (input, output) = batch?;
+        inputs.push(input);
+        outputs.push(output);
+    }
+    Ok((stack_rows(&inputs, input_cols)?, stack_rows(&outputs, output_cols)?))
+}
+
+/// Function to stream a Parquet file one row group at a time
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the Parquet file wrt Cargo.toml file of the project
+/// - `output_columns: Vec<String>` - Column names to extract as output
+/// - `input_exclude_columns: Vec<String>` - Column names to be excluded from input
+///
+/// # Returns:
+/// - `Result<ParquetBatches, Box<dyn Error>>` - Iterator of (Input Array, Output Array) per row group
+pub fn parquet_batches(
+    filepath: String,
+    output_columns: Vec<String>,
+    input_exclude_columns: Vec<String>,
+) -> Result<ParquetBatches, Box<dyn Error>> {
+    // The footer is parsed once here; every row group then reuses it and the open file
+    let file = File::open(&filepath)?;
+    let metadata = ArrowReaderMetadata::load(&file, Default::default())?;
+    let input_columns = input_columns(metadata.schema(), &output_columns, &input_exclude_columns);
+    let columns: Vec<String> = input_columns.iter().chain(&output_columns).cloned().collect();
+    let indices = projection_indices(metadata.schema(), &columns)?;
+    let mask = ProjectionMask::roots(metadata.metadata().file_metadata().schema_descr(), indices);
+    let row_groups = metadata.metadata().num_row_groups();
+
+    Ok(ParquetBatches {
+        file,
+        metadata,
+        mask,
+        input_columns,
+        output_columns,
+        row_group: 0,
+        row_groups,
+    })
+}
+
+/// Row-group iterator over a Parquet file, created by `parquet_batches`
+pub struct ParquetBatches {
+    file: File,
+    metadata: ArrowReaderMetadata,
+    mask: ProjectionMask,
+    input_columns: Vec<String>,
+    output_columns: Vec<String>,
+    row_group: usize,
+    row_groups: usize,
+}
+
+impl ParquetBatches {
+    /// Names of the input columns, in the order they appear in every batch
+    pub fn input_columns(&self) -> &[String] {
+        &self.input_columns
+    }
+
+    pub fn num_row_groups(&self) -> usize {
+        self.row_groups
+    }
+
+    fn read_row_group(&self, row_group: usize) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+        let builder = ParquetRecordBatchReaderBuilder::new_with_metadata(self.file.try_clone()?, self.metadata.clone());
+        let batches = builder
+            .with_projection(self.mask.clone())
+            .with_row_groups(vec![row_group])
+            .build()?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((
+            batches_to_array(&batches, &self.input_columns)?,
+            batches_to_array(&batches, &self.output_columns)?,
+        ))
+    }
+}
+
+impl Iterator for ParquetBatches {
+    type Item = Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row_group >= self.row_groups {
+            return None;
+        }
+        let batch = self.read_row_group(self.row_group);
+        self.row_group += 1;
+        Some(batch)
+    }
+}
+
+/// Function to save a 2D Array as a Parquet file of `Float64` columns
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the Parquet file wrt Cargo.toml file of the project
+/// - `headers: Vec<String>` - Column names
+/// - `array: &Array2<f64>` - The array to be saved (`NaN` is written as null)
+/// - `row_group_size: Option<usize>` - Maximum rows per row group, Parquet default if `None`
+///
+/// # Returns:
+/// - `Result<(), Box<dyn Error>>`
+pub fn parquet_write(
+    filepath: String,
+    headers: Vec<String>,
+    array: &Array2<f64>,
+    row_group_size: Option<usize>,
+) -> Result<(), Box<dyn Error>> {
+    let batch = array_to_batch(&headers, array)?;
+    let mut properties = WriterProperties::builder();
+    if let Some(size) = row_group_size {
+        properties = properties.set_max_row_group_size(size);
+    }
+
+    let mut writer = ArrowWriter::try_new(File::create(filepath)?, batch.schema(), Some(properties.build()))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+/// Function to parse an Arrow IPC (Feather v2) file and extract input & output columns
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the Arrow IPC file wrt Cargo.toml file of the project
+/// - `output_columns: Vec<String>` - Column names to extract as output
+/// - `input_exclude_columns: Vec<String>` - Column names to be excluded from input
+///
+/// # Returns:
+/// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input Array, Output Array)
+pub fn arrow_ipc_read_input_output(
+    filepath: String,
+    output_columns: Vec<String>,
+    input_exclude_columns: Vec<String>,
+) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+    let schema = FileReader::try_new(File::open(&filepath)?, None)?.schema();
+    let input_columns = input_columns(&schema, &output_columns, &input_exclude_columns);
+    let columns: Vec<String> = input_columns.iter().chain(&output_columns).cloned().collect();
+    let projection = projection_indices(&schema, &columns)?;
+
+    let reader = FileReader::try_new(File::open(filepath)?, Some(projection))?;
+    let batches = reader.collect::<Result<Vec<_>, _>>()?;
+    Ok((
+        batches_to_array(&batches, &input_columns)?,
+        batches_to_array(&batches, &output_columns)?,
+    ))
+}
+
+/// Function to save a 2D Array as an Arrow IPC file of `Float64` columns
+///
+/// # Parameters:
+/// - `filepath: String` - Relative path of the Arrow IPC file wrt Cargo.toml file of the project
+/// - `headers: Vec<String>` - Column names
+/// - `array: &Array2<f64>` - The array to be saved (`NaN` is written as null)
+///
+/// # Returns:
+/// - `Result<(), Box<dyn Error>>`
+pub fn arrow_ipc_write(filepath: String, headers: Vec<String>, array: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+    let batch = array_to_batch(&headers, array)?;
+    let mut writer = FileWriter::try_new(File::create(filepath)?, &batch.schema())?;
+    writer.write(&batch)?;
+    writer.finish()?;
+    Ok(())
+}
+
+fn is_numeric(data_type: &DataType) -> bool {
+    data_type.is_numeric() || *data_type == DataType::Boolean
+}
+
+fn input_columns(schema: &SchemaRef, output_columns: &[String], input_exclude_columns: &[String]) -> Vec<String> {
+    schema
+        .fields()
+        .iter()
+        .filter(|field| is_numeric(field.data_type()))
+        .map(|field| field.name().clone())
+        .filter(|name| !output_columns.contains(name) && !input_exclude_columns.contains(name))
+        .collect()
+}
+
+fn projection_indices(schema: &SchemaRef, columns: &[String]) -> Result<Vec<usize>, Box<dyn Error>> {
+    let mut indices = columns
+        .iter()
+        .map(|name| schema.index_of(name).map_err(|_| format!("Column '{}' not found", name).into()))
+        .collect::<Result<Vec<usize>, Box<dyn Error>>>()?;
+    indices.sort_unstable();
+    indices.dedup();
+    Ok(indices)
+}
+
+fn batches_to_array(batches: &[RecordBatch], columns: &[String]) -> Result<Array2<f64>, Box<dyn Error>> {
+    let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+    let mut array = Array2::<f64>::zeros((rows, columns.len()));
+
+    let mut offset = 0;
+    for batch in batches {
+        for (col, name) in columns.iter().enumerate() {
+            let column = batch.column_by_name(name).ok_or(format!("Column '{}' not found", name))?;
+            let values = cast(column, &DataType::Float64)
+                .map_err(|e| format!("Column '{}' is not numeric: {}", name, e))?;
+            let values = values
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .ok_or(format!("Column '{}' is not numeric", name))?;
+            for row in 0..values.len() {
+                array[(offset + row, col)] = if values.is_null(row) { f64::NAN } else { values.value(row) };
+            }
+        }
+        offset += batch.num_rows();
+    }
+
+    Ok(array)
+}
+
+fn array_to_batch(headers: &[String], array: &Array2<f64>) -> Result<RecordBatch, Box<dyn Error>> {
+    if headers.len() != array.ncols() {
+        return Err(format!("Got {} headers for {} columns", headers.len(), array.ncols()).into());
+    }
+
+    let fields: Vec<Field> = headers.iter().map(|name| Field::new(name, DataType::Float64, true)).collect();
+    let columns: Vec<ArrayRef> = array
+        .columns()
+        .into_iter()
+        .map(|column| {
+            let values: Float64Array = column.iter().map(|&v| if v.is_nan() { None } else { Some(v) }).collect();
+            Arc::new(values) as ArrayRef
+        })
+        .collect();
+
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)?)
+}
+
+fn stack_rows(arrays: &[Array2<f64>], ncols: usize) -> Result<Array2<f64>, Box<dyn Error>> {
+    if arrays.is_empty() {
+        return Ok(Array2::zeros((0, ncols)));
+    }
+    let views: Vec<_> = arrays.iter().map(|array| array.view()).collect();
+    Ok(ndarray::concatenate(ndarray::Axis(0), &views)?)
+}
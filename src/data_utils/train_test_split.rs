@@ -1,7 +1,13 @@
 use std::error::Error;
 use ndarray::{Array2, Axis};
 use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
 use rand::thread_rng;
+use crate::model_selection::stratify;
+use crate::utils::rng;
+
+/// Tuple of split matrices: (x_train, y_train, x_test, y_test)
+pub type TrainTestSplit = (Array2<f64>, Array2<f64>, Array2<f64>, Array2<f64>);
 
 /// Function to split dataset into train and test sets
 ///
@@ -11,13 +17,13 @@ use rand::thread_rng;
 /// - `split_ratio: f64` - Ratio for the training set (e.g., 0.8 for 80% train, 20% test)
 ///
 /// # Returns:
-/// - `Result<(Array2<f64>, Array2<f64>, Array2<f64>, Array2<f64>), Box<dyn Error>>`
+/// - `Result<TrainTestSplit, Box<dyn Error>>`
 ///   - Tuple containing (x_train, y_train, x_test, y_test)
 pub fn train_test_split(
     x: Array2<f64>,
     y: Array2<f64>,
     split_ratio: f64,
-) -> Result<(Array2<f64>, Array2<f64>, Array2<f64>, Array2<f64>), Box<dyn Error>> {
+) -> Result<TrainTestSplit, Box<dyn Error>> {
     if split_ratio <= 0.0 || split_ratio >= 1.0 {
         return Err("Split ratio should be between 0 and 1".into());
     }
@@ -38,4 +44,224 @@ pub fn train_test_split(
     let y_test = y.select(Axis(0), test_indices);
 
     Ok((x_train, y_train, x_test, y_test))
-}
\ No newline at end of file
+}
+
+/// Tuple of split matrices: (x_train, y_train, x_val, y_val, x_test, y_test)
+pub type TrainValTestSplit = (Array2<f64>, Array2<f64>, Array2<f64>, Array2<f64>, Array2<f64>, Array2<f64>);
+
+/// Size of a held-out set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TestSize {
+    Fraction(f64), // Fraction of all rows, in (0, 1)
+    Count(usize),  // Absolute number of rows
+}
+
+impl TestSize {
+    /// Number of rows out of `n_samples`
+    pub fn resolve(&self, n_samples: usize) -> Result<usize, Box<dyn Error>> {
+        let count = match *self {
+            TestSize::Fraction(fraction) => {
+                if fraction <= 0.0 || fraction >= 1.0 {
+                    return Err("Test fraction should be between 0 and 1".into());
+                }
+                (n_samples as f64 * fraction).round() as usize
+            }
+            TestSize::Count(count) => count,
+        };
+        if count == 0 || count >= n_samples {
+            return Err(format!("Cannot hold out {} of {} rows", count, n_samples).into());
+        }
+        Ok(count)
+    }
+}
+
+/// How rows are assigned to the held-out set
+#[derive(Debug, Clone, PartialEq)]
+pub enum SplitStrategy {
+    Shuffle,           // Uniform random
+    Stratified(usize), // Keeps the class proportions of this column of `y`
+    Grouped(Vec<i64>), // One group id per row; a group never straddles the split
+    Chronological,     // Rows are time ordered; the last rows are held out, no shuffling
+}
+
+/// Function to compute the row indices of a train/test split
+///
+/// # Parameters:
+/// - `y: &Array2<f64>` - Target matrix (used for stratification and the row count)
+/// - `test_size: TestSize` - Size of the test set
+/// - `strategy: &SplitStrategy` - How rows are assigned to the test set
+/// - `seed: Option<u64>` - Seed for reproducible splits
+///
+/// # Returns:
+/// - `Result<(Vec<usize>, Vec<usize>), Box<dyn Error>>` - Sorted (train, test) row indices
+pub fn split_indices(
+    y: &Array2<f64>,
+    test_size: TestSize,
+    strategy: &SplitStrategy,
+    seed: Option<u64>,
+) -> Result<(Vec<usize>, Vec<usize>), Box<dyn Error>> {
+    check_strategy(y, strategy)?;
+    let rows: Vec<usize> = (0..y.nrows()).collect();
+    let n_test = test_size.resolve(rows.len())?;
+    split_rows(&rows, n_test, y, strategy, &mut rng(seed))
+}
+
+/// Function to split dataset into train and test sets with a chosen strategy
+///
+/// # Parameters:
+/// - `x: Array2<f64>` - Feature matrix
+/// - `y: Array2<f64>` - Target matrix
+/// - `test_size: TestSize` - Size of the test set
+/// - `strategy: &SplitStrategy` - How rows are assigned to the test set
+/// - `seed: Option<u64>` - Seed for reproducible splits
+///
+/// # Returns:
+/// - `Result<TrainTestSplit, Box<dyn Error>>`
+///   - Tuple containing (x_train, y_train, x_test, y_test)
+pub fn train_test_split_with(
+    x: Array2<f64>,
+    y: Array2<f64>,
+    test_size: TestSize,
+    strategy: &SplitStrategy,
+    seed: Option<u64>,
+) -> Result<TrainTestSplit, Box<dyn Error>> {
+    check_rows(&x, &y)?;
+    let (train, test) = split_indices(&y, test_size, strategy, seed)?;
+    Ok((
+        x.select(Axis(0), &train),
+        y.select(Axis(0), &train),
+        x.select(Axis(0), &test),
+        y.select(Axis(0), &test),
+    ))
+}
+
+/// Function to split dataset into train, validation and test sets
+///
+/// Both held-out sizes are relative to the full dataset. The test set is taken
+/// first, then the validation set from the remaining rows, with the same strategy.
+///
+/// # Parameters:
+/// - `x: Array2<f64>` - Feature matrix
+/// - `y: Array2<f64>` - Target matrix
+/// - `val_size: TestSize` - Size of the validation set
+/// - `test_size: TestSize` - Size of the test set
+/// - `strategy: &SplitStrategy` - How rows are assigned to the held-out sets
+/// - `seed: Option<u64>` - Seed for reproducible splits
+///
+/// # Returns:
+/// - `Result<TrainValTestSplit, Box<dyn Error>>`
+///   - Tuple containing (x_train, y_train, x_val, y_val, x_test, y_test)
+pub fn train_val_test_split(
+    x: Array2<f64>,
+    y: Array2<f64>,
+    val_size: TestSize,
+    test_size: TestSize,
+    strategy: &SplitStrategy,
+    seed: Option<u64>,
+) -> Result<TrainValTestSplit, Box<dyn Error>> {
+    check_rows(&x, &y)?;
+    check_strategy(&y, strategy)?;
+    let n_samples = y.nrows();
+    let n_test = test_size.resolve(n_samples)?;
+    let n_val = val_size.resolve(n_samples)?;
+    if n_test + n_val >= n_samples {
+        return Err(format!("Cannot hold out {} validation and {} test rows of {}", n_val, n_test, n_samples).into());
+    }
+
+    let mut rng = rng(seed);
+    let rows: Vec<usize> = (0..n_samples).collect();
+    let (rest, test) = split_rows(&rows, n_test, &y, strategy, &mut rng)?;
+    let (train, val) = split_rows(&rest, n_val, &y, strategy, &mut rng)?;
+    Ok((
+        x.select(Axis(0), &train),
+        y.select(Axis(0), &train),
+        x.select(Axis(0), &val),
+        y.select(Axis(0), &val),
+        x.select(Axis(0), &test),
+        y.select(Axis(0), &test),
+    ))
+}
+
+fn check_rows(x: &Array2<f64>, y: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+    if x.nrows() != y.nrows() {
+        return Err(format!("x has {} rows but y has {}", x.nrows(), y.nrows()).into());
+    }
+    Ok(())
+}
+
+fn check_strategy(y: &Array2<f64>, strategy: &SplitStrategy) -> Result<(), Box<dyn Error>> {
+    match strategy {
+        SplitStrategy::Stratified(column) if *column >= y.ncols() => {
+            Err(format!("Label column {} out of range for {} columns", column, y.ncols()).into())
+        }
+        SplitStrategy::Grouped(groups) if groups.len() != y.nrows() => {
+            Err(format!("Got {} group ids for {} rows", groups.len(), y.nrows()).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Moves `n_test` of `rows` to the test set; returns sorted (train, test)
+fn split_rows(
+    rows: &[usize],
+    n_test: usize,
+    y: &Array2<f64>,
+    strategy: &SplitStrategy,
+    rng: &mut StdRng,
+) -> Result<(Vec<usize>, Vec<usize>), Box<dyn Error>> {
+    let mut test = match strategy {
+        SplitStrategy::Shuffle => {
+            let mut shuffled = rows.to_vec();
+            shuffled.shuffle(rng);
+            shuffled.truncate(n_test);
+            shuffled
+        }
+        SplitStrategy::Chronological => rows[rows.len() - n_test..].to_vec(),
+        SplitStrategy::Stratified(column) => {
+            let classes = stratify(rows.iter().map(|&i| y[(i, *column)].to_bits()));
+            // Largest remainder allocation of the test rows over the classes
+            let exact: Vec<f64> = classes.iter().map(|c| c.len() as f64 * n_test as f64 / rows.len() as f64).collect();
+            let mut counts: Vec<usize> = exact.iter().map(|e| e.floor() as usize).collect();
+            let mut by_remainder: Vec<usize> = (0..classes.len()).collect();
+            by_remainder.sort_by(|&a, &b| (exact[b] - exact[b].floor()).total_cmp(&(exact[a] - exact[a].floor())));
+            let missing = n_test - counts.iter().sum::<usize>();
+            by_remainder.iter().take(missing).for_each(|&c| counts[c] += 1);
+
+            let mut test = Vec::with_capacity(n_test);
+            for (members, count) in classes.into_iter().zip(counts) {
+                let mut members: Vec<usize> = members.into_iter().map(|i| rows[i]).collect();
+                members.shuffle(rng);
+                test.extend_from_slice(&members[..count]);
+            }
+            test
+        }
+        SplitStrategy::Grouped(groups) => {
+            let mut members = stratify(rows.iter().map(|&i| groups[i] as u64));
+            if members.len() < 2 {
+                return Err("Grouped split needs at least 2 groups".into());
+            }
+            members.shuffle(rng);
+            // Whole groups are added until the test set is large enough, keeping one group for training
+            let mut test = Vec::with_capacity(n_test);
+            for group in &members[..members.len() - 1] {
+                if test.len() >= n_test {
+                    break;
+                }
+                test.extend(group.iter().map(|&i| rows[i]));
+            }
+            test
+        }
+    };
+    test.sort_unstable();
+
+    let mut train = Vec::with_capacity(rows.len() - test.len());
+    let mut held_out = test.iter().peekable();
+    for &row in rows {
+        if held_out.peek() == Some(&&row) {
+            held_out.next();
+        } else {
+            train.push(row);
+        }
+    }
+    Ok((train, test))
+}
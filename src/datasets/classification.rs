@@ -0,0 +1,207 @@
+use std::error::Error;
+use ndarray::{s, Array2};
+use ndarray_rand::rand_distr::{StandardNormal, Uniform};
+use ndarray_rand::RandomExt;
+use rand::Rng;
+use crate::datasets::sampling::{group_labels, group_sizes, shuffle_rows};
+use crate::utils::rng;
+
+#[derive(Debug, Clone)]
+pub struct ClassificationConfig {
+    pub n_samples: usize,
+    pub n_features: usize,
+    pub n_informative: usize, // Features carrying the class clusters
+    pub n_redundant: usize,   // Random linear combinations of the informative features
+    pub n_classes: usize,
+    pub class_sep: f64,       // Half the side of the hypercube whose vertices hold the class centers
+    pub flip_y: f64,          // Fraction of labels replaced by a random class
+    pub seed: Option<u64>,
+}
+
+impl Default for ClassificationConfig {
+    fn default() -> Self {
+        Self {
+            n_samples: 100,
+            n_features: 20,
+            n_informative: 2,
+            n_redundant: 2,
+            n_classes: 2,
+            class_sep: 1.,
+            flip_y: 0.01,
+            seed: None,
+        }
+    }
+}
+
+pub struct ClassificationConfigBuilder {
+    config: ClassificationConfig,
+}
+
+impl Default for ClassificationConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClassificationConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: ClassificationConfig::default(),
+        }
+    }
+
+    pub fn n_samples(mut self, n_samples: usize) -> Self {
+        self.config.n_samples = n_samples;
+        self
+    }
+
+    pub fn n_features(mut self, n_features: usize) -> Self {
+        self.config.n_features = n_features;
+        self
+    }
+
+    pub fn n_informative(mut self, n_informative: usize) -> Self {
+        self.config.n_informative = n_informative;
+        self
+    }
+
+    pub fn n_redundant(mut self, n_redundant: usize) -> Self {
+        self.config.n_redundant = n_redundant;
+        self
+    }
+
+    pub fn n_classes(mut self, n_classes: usize) -> Self {
+        self.config.n_classes = n_classes;
+        self
+    }
+
+    pub fn class_sep(mut self, class_sep: f64) -> Self {
+        self.config.class_sep = class_sep;
+        self
+    }
+
+    pub fn flip_y(mut self, flip_y: f64) -> Self {
+        self.config.flip_y = flip_y;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> ClassificationConfig {
+        self.config
+    }
+}
+
+/// Function to generate a classification problem of Gaussian clusters on hypercube vertices
+///
+/// # Parameters:
+/// - `config: &ClassificationConfig` - Shape and difficulty of the problem
+///
+/// Each class is a standard normal cluster around its own vertex of a hypercube in the informative
+/// features, sheared by a random linear map. Columns are ordered informative, redundant, then pure
+/// noise; rows are shuffled.
+///
+/// # Returns:
+/// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input, Labels `0..n_classes` in one column)
+pub fn make_classification(config: &ClassificationConfig) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+    let ClassificationConfig { n_samples, n_features, n_informative, n_redundant, n_classes, class_sep, flip_y, seed } = *config;
+    if n_informative + n_redundant > n_features {
+        return Err(format!(
+            "n_informative ({}) + n_redundant ({}) cannot exceed n_features ({})",
+            n_informative, n_redundant, n_features
+        ).into());
+    }
+    if n_classes < 2 {
+        return Err("At least 2 classes are required".into());
+    }
+    if n_informative == 0 || (n_informative < usize::BITS as usize && n_classes > 1 << n_informative) {
+        return Err(format!("{} informative features have fewer hypercube vertices than {} classes", n_informative, n_classes).into());
+    }
+    if !(0. ..=1.).contains(&flip_y) {
+        return Err(format!("flip_y must be in [0, 1], got {}", flip_y).into());
+    }
+
+    let mut rng = rng(seed);
+    let sizes = group_sizes(n_samples, n_classes);
+    let mut labels = group_labels(&sizes);
+
+    // Class k sits on the vertex spelled by the bits of k, in a randomly signed orthant
+    let signs: Vec<f64> = (0..n_informative).map(|_| if rng.gen::<bool>() { 1. } else { -1. }).collect();
+    let mut informative = Array2::<f64>::random_using((n_samples, n_informative), StandardNormal, &mut rng);
+    let shear = Array2::random_using((n_informative, n_informative), Uniform::new(-1., 1.), &mut rng);
+    informative = informative.dot(&shear);
+    for (mut row, &label) in informative.rows_mut().into_iter().zip(labels.iter()) {
+        for (dimension, value) in row.iter_mut().enumerate() {
+            let bit = dimension < usize::BITS as usize && (label as usize >> dimension) & 1 == 1;
+            *value += signs[dimension] * if bit { class_sep } else { -class_sep };
+        }
+    }
+
+    let mut input = Array2::<f64>::random_using((n_samples, n_features), StandardNormal, &mut rng);
+    input.slice_mut(s![.., ..n_informative]).assign(&informative);
+    let mixing = Array2::random_using((n_informative, n_redundant), Uniform::new(-1., 1.), &mut rng);
+    input
+        .slice_mut(s![.., n_informative..n_informative + n_redundant])
+        .assign(&informative.dot(&mixing));
+
+    for label in labels.iter_mut() {
+        if rng.gen::<f64>() < flip_y {
+            *label = rng.gen_range(0..n_classes) as f64;
+        }
+    }
+
+    Ok(shuffle_rows(input, labels, &mut rng))
+}
+
+/// Tuple of generated matrices: (input, cluster labels, centers with one row per cluster)
+pub type BlobData = (Array2<f64>, Array2<f64>, Array2<f64>);
+
+/// Centers of the clusters drawn by `make_blobs`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Centers {
+    Count(usize),        // Centers drawn uniformly from the box [-10, 10] in every feature
+    Fixed(Array2<f64>),  // One center per row
+}
+
+/// Function to generate isotropic Gaussian clusters
+///
+/// # Parameters:
+/// - `n_samples: usize` - Number of rows, split evenly over the clusters
+/// - `n_features: usize` - Number of columns, ignored for `Centers::Fixed`
+/// - `centers: Centers` - Number or coordinates of the cluster centers
+/// - `cluster_std: f64` - Standard deviation of every cluster
+/// - `seed: Option<u64>` - Seed for reproducible data
+///
+/// # Returns:
+/// - `Result<BlobData, Box<dyn Error>>` - Tuple (Input, Cluster Labels, Centers)
+pub fn make_blobs(
+    n_samples: usize,
+    n_features: usize,
+    centers: Centers,
+    cluster_std: f64,
+    seed: Option<u64>,
+) -> Result<BlobData, Box<dyn Error>> {
+    if cluster_std < 0. {
+        return Err(format!("Cluster standard deviation must be non-negative, got {}", cluster_std).into());
+    }
+    let mut rng = rng(seed);
+    let centers = match centers {
+        Centers::Count(count) => Array2::random_using((count, n_features), Uniform::new(-10., 10.), &mut rng),
+        Centers::Fixed(centers) => centers,
+    };
+    if centers.nrows() == 0 {
+        return Err("At least one center is required".into());
+    }
+
+    let labels = group_labels(&group_sizes(n_samples, centers.nrows()));
+    let mut input = Array2::<f64>::random_using((n_samples, centers.ncols()), StandardNormal, &mut rng) * cluster_std;
+    for (mut row, &label) in input.rows_mut().into_iter().zip(labels.iter()) {
+        row += &centers.row(label as usize);
+    }
+
+    let (input, labels) = shuffle_rows(input, labels, &mut rng);
+    Ok((input, labels, centers))
+}
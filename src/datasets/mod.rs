@@ -0,0 +1,8 @@
+mod sampling;
+mod regression;
+mod classification;
+mod shapes;
+
+pub use regression::*;
+pub use classification::*;
+pub use shapes::*;
@@ -0,0 +1,247 @@
+use std::error::Error;
+use std::f64::consts::PI;
+use ndarray::{Array1, Array2, Axis};
+use ndarray_rand::rand_distr::{StandardNormal, Uniform};
+use ndarray_rand::RandomExt;
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
+use rand::Rng;
+use crate::utils::rng;
+
+/// Tuple of generated matrices: (input, output, true coefficients of shape `(n_features, n_targets)`)
+pub type RegressionData = (Array2<f64>, Array2<f64>, Array2<f64>);
+
+#[derive(Debug, Clone)]
+pub struct RegressionConfig {
+    pub n_samples: usize,
+    pub n_features: usize,
+    pub n_informative: usize, // Features with a non-zero coefficient, picked at random
+    pub n_targets: usize,
+    pub bias: f64,            // Intercept added to every target
+    pub noise: f64,           // Standard deviation of the Gaussian noise on the output
+    pub correlation: f64,     // Pairwise correlation of the features, in [0, 1)
+    pub density: f64,         // Fraction of input entries kept non-zero, in (0, 1]
+    pub seed: Option<u64>,
+}
+
+impl Default for RegressionConfig {
+    fn default() -> Self {
+        Self {
+            n_samples: 100,
+            n_features: 10,
+            n_informative: 10,
+            n_targets: 1,
+            bias: 0.,
+            noise: 0.,
+            correlation: 0.,
+            density: 1.,
+            seed: None,
+        }
+    }
+}
+
+pub struct RegressionConfigBuilder {
+    config: RegressionConfig,
+}
+
+impl Default for RegressionConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegressionConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: RegressionConfig::default(),
+        }
+    }
+
+    pub fn n_samples(mut self, n_samples: usize) -> Self {
+        self.config.n_samples = n_samples;
+        self
+    }
+
+    /// Sets the number of features; all of them are informative unless `n_informative` is also set
+    pub fn n_features(mut self, n_features: usize) -> Self {
+        self.config.n_features = n_features;
+        self.config.n_informative = n_features;
+        self
+    }
+
+    pub fn n_informative(mut self, n_informative: usize) -> Self {
+        self.config.n_informative = n_informative;
+        self
+    }
+
+    pub fn n_targets(mut self, n_targets: usize) -> Self {
+        self.config.n_targets = n_targets;
+        self
+    }
+
+    pub fn bias(mut self, bias: f64) -> Self {
+        self.config.bias = bias;
+        self
+    }
+
+    pub fn noise(mut self, noise: f64) -> Self {
+        self.config.noise = noise;
+        self
+    }
+
+    pub fn correlation(mut self, correlation: f64) -> Self {
+        self.config.correlation = correlation;
+        self
+    }
+
+    pub fn density(mut self, density: f64) -> Self {
+        self.config.density = density;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> RegressionConfig {
+        self.config
+    }
+}
+
+/// Function to generate a linear regression problem with known coefficients
+///
+/// # Parameters:
+/// - `config: &RegressionConfig` - Shape, noise and feature structure of the problem
+///
+/// Features are standard normal. With `correlation` every pair of features has that correlation,
+/// and with `density < 1` each entry is zeroed with probability `1 - density`, giving an input
+/// suited to `CsrMatrix::from_dense`. Informative coefficients are drawn from U(0, 100), the others
+/// are zero, and `output = input . coefficients + bias + noise * N(0, 1)`.
+///
+/// # Returns:
+/// - `Result<RegressionData, Box<dyn Error>>` - Tuple (Input, Output, True Coefficients)
+pub fn make_regression(config: &RegressionConfig) -> Result<RegressionData, Box<dyn Error>> {
+    let RegressionConfig { n_samples, n_features, n_informative, n_targets, bias, noise, correlation, density, seed } = *config;
+    if n_informative > n_features {
+        return Err(format!("n_informative ({}) cannot exceed n_features ({})", n_informative, n_features).into());
+    }
+    if !(0. ..1.).contains(&correlation) {
+        return Err(format!("Correlation must be in [0, 1), got {}", correlation).into());
+    }
+    if !(density > 0. && density <= 1.) {
+        return Err(format!("Density must be in (0, 1], got {}", density).into());
+    }
+    if noise < 0. {
+        return Err(format!("Noise must be non-negative, got {}", noise).into());
+    }
+
+    let mut rng = rng(seed);
+    let input = correlated_features(n_samples, n_features, correlation, density, &mut rng);
+
+    let mut informative: Vec<usize> = (0..n_features).collect();
+    informative.shuffle(&mut rng);
+    let mut coefficients = Array2::<f64>::zeros((n_features, n_targets));
+    for &feature in &informative[..n_informative] {
+        for coefficient in coefficients.row_mut(feature) {
+            *coefficient = 100. * rng.gen::<f64>();
+        }
+    }
+
+    let mut output = input.dot(&coefficients) + bias;
+    if noise > 0. {
+        output.scaled_add(noise, &Array2::<f64>::random_using((n_samples, n_targets), StandardNormal, &mut rng));
+    }
+
+    Ok((input, output, coefficients))
+}
+
+/// Standard normal features sharing a common factor, then masked to the requested density
+fn correlated_features(n_samples: usize, n_features: usize, correlation: f64, density: f64, rng: &mut StdRng) -> Array2<f64> {
+    let mut input = Array2::<f64>::random_using((n_samples, n_features), StandardNormal, rng) * (1. - correlation).sqrt();
+    if correlation > 0. {
+        let factor = Array1::<f64>::random_using(n_samples, StandardNormal, rng).insert_axis(Axis(1));
+        input.scaled_add(correlation.sqrt(), &factor);
+    }
+    if density < 1. {
+        input.mapv_inplace(|value| if rng.gen::<f64>() < density { value } else { 0. });
+    }
+    input
+}
+
+/// Function to generate the Friedman #1 problem
+///
+/// # Parameters:
+/// - `n_samples: usize` - Number of rows
+/// - `n_features: usize` - Number of U(0, 1) features, at least 5; only the first 5 enter the output
+/// - `noise: f64` - Standard deviation of the Gaussian noise on the output
+/// - `seed: Option<u64>` - Seed for reproducible data
+///
+/// `y = 10 sin(pi x0 x1) + 20 (x2 - 0.5)^2 + 10 x3 + 5 x4 + noise * N(0, 1)`
+///
+/// # Returns:
+/// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input, Output)
+pub fn make_friedman1(n_samples: usize, n_features: usize, noise: f64, seed: Option<u64>) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+    if n_features < 5 {
+        return Err(format!("Friedman #1 needs at least 5 features, got {}", n_features).into());
+    }
+    let mut rng = rng(seed);
+    let input = Array2::random_using((n_samples, n_features), Uniform::new(0., 1.), &mut rng);
+    let output = friedman_output(&input, noise, &mut rng, |x| {
+        10. * (PI * x[0] * x[1]).sin() + 20. * (x[2] - 0.5).powi(2) + 10. * x[3] + 5. * x[4]
+    });
+    Ok((input, output))
+}
+
+/// Function to generate the Friedman #2 problem, the impedance of an AC circuit
+///
+/// Features are `x0 ~ U(0, 100)`, `x1 ~ U(40 pi, 560 pi)`, `x2 ~ U(0, 1)` and `x3 ~ U(1, 11)`,
+/// and `y = sqrt(x0^2 + (x1 x2 - 1 / (x1 x3))^2) + noise * N(0, 1)`.
+///
+/// # Returns:
+/// - `(Array2<f64>, Array2<f64>)` - Tuple (Input, Output)
+pub fn make_friedman2(n_samples: usize, noise: f64, seed: Option<u64>) -> (Array2<f64>, Array2<f64>) {
+    let mut rng = rng(seed);
+    let input = friedman_circuit(n_samples, &mut rng);
+    let output = friedman_output(&input, noise, &mut rng, |x| {
+        (x[0].powi(2) + (x[1] * x[2] - 1. / (x[1] * x[3])).powi(2)).sqrt()
+    });
+    (input, output)
+}
+
+/// Function to generate the Friedman #3 problem, the phase of the Friedman #2 circuit
+///
+/// Features are drawn as for `make_friedman2`, and `y = atan((x1 x2 - 1 / (x1 x3)) / x0) + noise * N(0, 1)`.
+///
+/// # Returns:
+/// - `(Array2<f64>, Array2<f64>)` - Tuple (Input, Output)
+pub fn make_friedman3(n_samples: usize, noise: f64, seed: Option<u64>) -> (Array2<f64>, Array2<f64>) {
+    let mut rng = rng(seed);
+    let input = friedman_circuit(n_samples, &mut rng);
+    let output = friedman_output(&input, noise, &mut rng, |x| {
+        ((x[1] * x[2] - 1. / (x[1] * x[3])) / x[0]).atan()
+    });
+    (input, output)
+}
+
+fn friedman_circuit(n_samples: usize, rng: &mut StdRng) -> Array2<f64> {
+    let ranges = [(0., 100.), (40. * PI, 560. * PI), (0., 1.), (1., 11.)];
+    let mut input = Array2::<f64>::zeros((n_samples, ranges.len()));
+    for (mut column, (low, high)) in input.columns_mut().into_iter().zip(ranges) {
+        column.assign(&Array1::random_using(n_samples, Uniform::new(low, high), rng));
+    }
+    input
+}
+
+fn friedman_output(input: &Array2<f64>, noise: f64, rng: &mut StdRng, f: impl Fn(&[f64]) -> f64) -> Array2<f64> {
+    let mut output = input
+        .rows()
+        .into_iter()
+        .map(|row| f(&row.to_vec()))
+        .collect::<Array1<f64>>()
+        .insert_axis(Axis(1));
+    if noise > 0. {
+        output.scaled_add(noise, &Array2::<f64>::random_using(output.dim(), StandardNormal, rng));
+    }
+    output
+}
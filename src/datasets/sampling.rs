@@ -0,0 +1,27 @@
+use ndarray::{Array2, Axis};
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
+
+/// Splits `n_samples` as evenly as possible over `n_groups`, the first groups taking the remainder
+pub(crate) fn group_sizes(n_samples: usize, n_groups: usize) -> Vec<usize> {
+    (0..n_groups)
+        .map(|group| n_samples / n_groups + usize::from(group < n_samples % n_groups))
+        .collect()
+}
+
+/// Column of labels `0, 0, ..., 1, 1, ...` with `sizes[k]` rows of label `k`
+pub(crate) fn group_labels(sizes: &[usize]) -> Array2<f64> {
+    let labels: Vec<f64> = sizes
+        .iter()
+        .enumerate()
+        .flat_map(|(label, &size)| std::iter::repeat_n(label as f64, size))
+        .collect();
+    Array2::from_shape_vec((labels.len(), 1), labels).expect("One label per row")
+}
+
+/// Applies the same random row permutation to the input and the output
+pub(crate) fn shuffle_rows(x: Array2<f64>, y: Array2<f64>, rng: &mut StdRng) -> (Array2<f64>, Array2<f64>) {
+    let mut rows: Vec<usize> = (0..x.nrows()).collect();
+    rows.shuffle(rng);
+    (x.select(Axis(0), &rows), y.select(Axis(0), &rows))
+}
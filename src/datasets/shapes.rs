@@ -0,0 +1,67 @@
+use std::error::Error;
+use std::f64::consts::PI;
+use ndarray::Array2;
+use ndarray_rand::rand_distr::StandardNormal;
+use ndarray_rand::RandomExt;
+use crate::datasets::sampling::{group_labels, group_sizes, shuffle_rows};
+use crate::utils::rng;
+
+/// Function to generate two interleaving half circles in the plane
+///
+/// # Parameters:
+/// - `n_samples: usize` - Number of rows, split evenly over the two moons
+/// - `noise: f64` - Standard deviation of the Gaussian noise added to the points
+/// - `seed: Option<u64>` - Seed for reproducible data
+///
+/// # Returns:
+/// - `(Array2<f64>, Array2<f64>)` - Tuple (Input with 2 columns, Labels 0 for the upper moon and 1 for the lower)
+pub fn make_moons(n_samples: usize, noise: f64, seed: Option<u64>) -> (Array2<f64>, Array2<f64>) {
+    let sizes = group_sizes(n_samples, 2);
+    let labels = group_labels(&sizes);
+    let mut input = Array2::<f64>::zeros((n_samples, 2));
+    for (i, mut row) in input.rows_mut().into_iter().enumerate() {
+        let (moon, position, size) = if i < sizes[0] { (0, i, sizes[0]) } else { (1, i - sizes[0], sizes[1]) };
+        let angle = PI * position as f64 / (size.max(2) - 1) as f64;
+        if moon == 0 {
+            row.assign(&ndarray::arr1(&[angle.cos(), angle.sin()]));
+        } else {
+            row.assign(&ndarray::arr1(&[1. - angle.cos(), 0.5 - angle.sin()]));
+        }
+    }
+
+    with_noise(input, labels, noise, seed)
+}
+
+/// Function to generate a small circle inside a large one in the plane
+///
+/// # Parameters:
+/// - `n_samples: usize` - Number of rows, split evenly over the two circles
+/// - `factor: f64` - Radius of the inner circle relative to the unit outer one, in [0, 1)
+/// - `noise: f64` - Standard deviation of the Gaussian noise added to the points
+/// - `seed: Option<u64>` - Seed for reproducible data
+///
+/// # Returns:
+/// - `Result<(Array2<f64>, Array2<f64>), Box<dyn Error>>` - Tuple (Input with 2 columns, Labels 0 for the outer circle and 1 for the inner)
+pub fn make_circles(n_samples: usize, factor: f64, noise: f64, seed: Option<u64>) -> Result<(Array2<f64>, Array2<f64>), Box<dyn Error>> {
+    if !(0. ..1.).contains(&factor) {
+        return Err(format!("Factor must be in [0, 1), got {}", factor).into());
+    }
+    let sizes = group_sizes(n_samples, 2);
+    let labels = group_labels(&sizes);
+    let mut input = Array2::<f64>::zeros((n_samples, 2));
+    for (i, mut row) in input.rows_mut().into_iter().enumerate() {
+        let (radius, position, size) = if i < sizes[0] { (1., i, sizes[0]) } else { (factor, i - sizes[0], sizes[1]) };
+        let angle = 2. * PI * position as f64 / size as f64;
+        row.assign(&ndarray::arr1(&[radius * angle.cos(), radius * angle.sin()]));
+    }
+
+    Ok(with_noise(input, labels, noise, seed))
+}
+
+fn with_noise(mut input: Array2<f64>, labels: Array2<f64>, noise: f64, seed: Option<u64>) -> (Array2<f64>, Array2<f64>) {
+    let mut rng = rng(seed);
+    if noise > 0. {
+        input.scaled_add(noise, &Array2::<f64>::random_using(input.dim(), StandardNormal, &mut rng));
+    }
+    shuffle_rows(input, labels, &mut rng)
+}
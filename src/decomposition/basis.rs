@@ -0,0 +1,75 @@
+use std::error::Error;
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::matrix_operations::matrix_mul;
+
+/// Fitted components shared by the decompositions, with the statistics they report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Basis {
+    pub(crate) mean: Option<Array1<f64>>, // Subtracted before projecting, `None` for uncentered decompositions
+    pub(crate) components: Array2<f64>,   // One unit-norm component per row
+    pub(crate) singular_values: Array1<f64>,
+    pub(crate) explained_variance: Array1<f64>,
+    pub(crate) explained_variance_ratio: Array1<f64>,
+}
+
+impl Basis {
+    /// Coordinates of the rows of `x` on the components, divided by their spread when whitening
+    pub(crate) fn project(&self, name: &str, x: &Array2<f64>, whiten: bool) -> Result<Array2<f64>, Box<dyn Error>> {
+        if x.ncols() != self.components.ncols() {
+            return Err(format!("{} was fitted on {} columns but got {}", name, self.components.ncols(), x.ncols()).into());
+        }
+        let centered = match &self.mean {
+            Some(mean) => x - &mean.view().insert_axis(Axis(0)),
+            None => x.clone(),
+        };
+        let mut projected = matrix_mul(&centered, &self.components.t());
+        if whiten {
+            projected /= &self.spread().insert_axis(Axis(0));
+        }
+        Ok(projected)
+    }
+
+    /// Rows in the original space whose projection is `z`
+    pub(crate) fn reconstruct(&self, name: &str, z: &Array2<f64>, whiten: bool) -> Result<Array2<f64>, Box<dyn Error>> {
+        if z.ncols() != self.components.nrows() {
+            return Err(format!("{} has {} components but got {} columns", name, self.components.nrows(), z.ncols()).into());
+        }
+        let scaled = if whiten { z * &self.spread().insert_axis(Axis(0)) } else { z.clone() };
+        let mut reconstructed = matrix_mul(&scaled, &self.components);
+        if let Some(mean) = &self.mean {
+            reconstructed += &mean.view().insert_axis(Axis(0));
+        }
+        Ok(reconstructed)
+    }
+
+    // Standard deviation along every component; zero variance components are left unscaled
+    fn spread(&self) -> Array1<f64> {
+        self.explained_variance.mapv(|v| if v > 0.0 { v.sqrt() } else { 1.0 })
+    }
+}
+
+/// Makes the largest absolute entry of every component positive, for reproducible signs
+pub(crate) fn flip_signs(components: &mut Array2<f64>) {
+    for mut row in components.axis_iter_mut(Axis(0)) {
+        let largest = row.iter().copied().fold(0.0_f64, |acc, v| if v.abs() > acc.abs() { v } else { acc });
+        if largest < 0.0 {
+            row.mapv_inplace(|v| -v);
+        }
+    }
+}
+
+/// Sum of the population variances of the columns times `n_samples / (n_samples - 1)`
+pub(crate) fn total_variance(x: &Array2<f64>) -> f64 {
+    let n = x.nrows() as f64;
+    x.axis_iter(Axis(1))
+        .map(|column| {
+            let mean = column.sum() / n;
+            column.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        })
+        .sum()
+}
+
+pub(crate) fn component_names(prefix: &str, n_components: usize) -> Vec<String> {
+    (0..n_components).map(|i| format!("{}{}", prefix, i)).collect()
+}
@@ -0,0 +1,189 @@
+use std::error::Error;
+use ndarray::{concatenate, s, Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::decomposition::basis::{component_names, flip_signs, Basis};
+use crate::matrix_operations::svd;
+use crate::traits::{InverseTransform, Transform};
+
+/// Principal component analysis updated one batch of rows at a time
+///
+/// Every `partial_fit` takes the SVD of the current components (scaled by their singular
+/// values), the centered batch and a mean correction row (Ross et al., 2008), so memory
+/// does not grow with the number of rows seen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncrementalPca {
+    n_components: usize,
+    whiten: bool,
+    batch_size: Option<usize>, // Rows per batch used by `fit`, defaults to 5 * n_features
+    n_samples_seen: usize,
+    squared_deviations: Option<Array1<f64>>, // Running sum of squared deviations from the mean, per column
+    basis: Option<Basis>,
+}
+
+impl IncrementalPca {
+    /// Creates an unfitted incremental PCA keeping `n_components` components
+    pub fn new(n_components: usize) -> Self {
+        Self {
+            n_components,
+            whiten: false,
+            batch_size: None,
+            n_samples_seen: 0,
+            squared_deviations: None,
+            basis: None,
+        }
+    }
+
+    /// Scales the projected columns to unit variance
+    pub fn whiten(mut self, whiten: bool) -> Self {
+        self.whiten = whiten;
+        self
+    }
+
+    /// Rows per batch when `fit` is given the whole dataset
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Function to update the components with a new batch of rows
+    ///
+    /// # Parameters:
+    /// - `batch: &Array2<f64>` - New rows; the first batch needs at least `n_components` rows
+    ///
+    /// # Returns:
+    /// - `Result<(), Box<dyn Error>>`
+    pub fn partial_fit(&mut self, batch: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        let (n_batch, n_features) = batch.dim();
+        if n_batch == 0 {
+            return Ok(());
+        }
+        if self.n_components == 0 || self.n_components > n_features {
+            return Err(format!("Cannot keep {} components of {} columns", self.n_components, n_features).into());
+        }
+        let batch_mean = batch.mean_axis(Axis(0)).ok_or("IncrementalPca got an empty batch")?;
+        let centered = batch - &batch_mean.view().insert_axis(Axis(0));
+        let batch_deviations = centered.mapv(|v| v * v).sum_axis(Axis(0));
+
+        let (stacked, mean, squared_deviations) = match (&self.basis, &self.squared_deviations) {
+            (Some(basis), Some(deviations)) => {
+                if n_features != basis.components.ncols() {
+                    return Err(format!(
+                        "IncrementalPca was fitted on {} columns but got {}",
+                        basis.components.ncols(),
+                        n_features
+                    )
+                    .into());
+                }
+                let last_mean = basis.mean.as_ref().ok_or("IncrementalPca has no mean")?;
+                let (n_seen, n_new) = (self.n_samples_seen as f64, n_batch as f64);
+                let n_total = n_seen + n_new;
+                let delta = last_mean - &batch_mean;
+                // Chan et al. update of the mean and the squared deviations
+                let mean = last_mean - &(&delta * (n_new / n_total));
+                let squared_deviations = deviations + &batch_deviations + &(delta.mapv(|d| d * d) * (n_seen * n_new / n_total));
+                let weighted = &basis.components * &basis.singular_values.view().insert_axis(Axis(1));
+                let correction = (delta * (n_seen * n_new / n_total).sqrt()).insert_axis(Axis(0));
+                let stacked = concatenate(Axis(0), &[weighted.view(), centered.view(), correction.view()])?;
+                (stacked, mean, squared_deviations)
+            }
+            _ => {
+                if n_batch < self.n_components {
+                    return Err(format!(
+                        "The first batch needs at least {} rows, got {}",
+                        self.n_components, n_batch
+                    )
+                    .into());
+                }
+                (centered, batch_mean, batch_deviations)
+            }
+        };
+
+        let n_total = self.n_samples_seen + n_batch;
+        let (_, singular_values, vt) = svd(&stacked)?;
+        let k = self.n_components;
+        let mut components = vt.slice(s![..k, ..]).to_owned();
+        flip_signs(&mut components);
+        let singular_values = singular_values.slice(s![..k]).to_owned();
+        let explained_variance = singular_values.mapv(|s| s * s / (n_total.max(2) - 1) as f64);
+        let total: f64 = squared_deviations.sum();
+        let explained_variance_ratio = singular_values.mapv(|s| if total > 0.0 { s * s / total } else { 0.0 });
+
+        self.basis = Some(Basis {
+            mean: Some(mean),
+            components,
+            singular_values,
+            explained_variance,
+            explained_variance_ratio,
+        });
+        self.squared_deviations = Some(squared_deviations);
+        self.n_samples_seen = n_total;
+        Ok(())
+    }
+
+    pub fn n_samples_seen(&self) -> usize {
+        self.n_samples_seen
+    }
+
+    /// Principal axes, one per row, `None` before `fit`
+    pub fn components(&self) -> Option<&Array2<f64>> {
+        self.basis.as_ref().map(|basis| &basis.components)
+    }
+
+    /// Variance of the rows seen so far along every component
+    pub fn explained_variance(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().map(|basis| &basis.explained_variance)
+    }
+
+    /// Fraction of the total variance of the rows seen so far explained by every component
+    pub fn explained_variance_ratio(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().map(|basis| &basis.explained_variance_ratio)
+    }
+
+    /// Column means of the rows seen so far
+    pub fn mean(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().and_then(|basis| basis.mean.as_ref())
+    }
+
+    /// Output column names `pc0`, `pc1`, ...
+    pub fn feature_names(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let basis = self.basis.as_ref().ok_or("IncrementalPca has not been fitted")?;
+        Ok(component_names("pc", basis.components.nrows()))
+    }
+}
+
+impl Transform for IncrementalPca {
+    /// Forgets previous batches and fits on `x` in batches of `batch_size` rows
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        self.n_samples_seen = 0;
+        self.squared_deviations = None;
+        self.basis = None;
+        let batch_size = self.batch_size.unwrap_or(5 * x.ncols()).max(self.n_components).max(1);
+        let mut start = 0;
+        while start < x.nrows() {
+            // A short last batch is merged into the previous one
+            let end = if x.nrows() - start < 2 * batch_size { x.nrows() } else { start + batch_size };
+            self.partial_fit(&x.slice(s![start..end, ..]).to_owned())?;
+            start = end;
+        }
+        if self.basis.is_none() {
+            return Err("IncrementalPca needs at least one row".into());
+        }
+        Ok(())
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.basis
+            .as_ref()
+            .ok_or("IncrementalPca has not been fitted")?
+            .project("IncrementalPca", x, self.whiten)
+    }
+}
+
+impl InverseTransform for IncrementalPca {
+    fn inverse_transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.basis
+            .as_ref()
+            .ok_or("IncrementalPca has not been fitted")?
+            .reconstruct("IncrementalPca", x, self.whiten)
+    }
+}
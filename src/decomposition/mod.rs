@@ -0,0 +1,8 @@
+mod basis;
+mod pca;
+mod incremental_pca;
+mod truncated_svd;
+
+pub use pca::*;
+pub use incremental_pca::*;
+pub use truncated_svd::*;
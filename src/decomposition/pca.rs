@@ -0,0 +1,171 @@
+use std::error::Error;
+use ndarray::{s, Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::decomposition::basis::{component_names, flip_signs, total_variance, Basis};
+use crate::matrix_operations::{randomized_svd, svd};
+use crate::traits::{InverseTransform, Transform};
+
+/// Algorithm computing the singular value decomposition of a decomposition
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SvdSolver {
+    Full, // Exact Jacobi SVD of the whole matrix
+    Randomized {
+        n_oversamples: usize, // Extra random directions
+        n_iter: usize,        // Power iterations
+        seed: Option<u64>,
+    },
+}
+
+impl SvdSolver {
+    /// Randomized solver with 10 oversamples and 4 power iterations
+    pub fn randomized(seed: Option<u64>) -> Self {
+        SvdSolver::Randomized { n_oversamples: 10, n_iter: 4, seed }
+    }
+}
+
+/// Number of components kept by `Pca`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NComponents {
+    Count(usize),
+    VarianceRatio(f64), // Fewest components explaining at least this fraction of the variance (full solver only)
+    All,                // min(n_samples, n_features)
+}
+
+/// Principal component analysis: projects centered data on the directions of largest variance
+///
+/// Components are the right singular vectors of the centered training data. With `whiten`
+/// the projected columns are additionally scaled to unit variance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pca {
+    n_components: NComponents,
+    whiten: bool,
+    solver: SvdSolver,
+    basis: Option<Basis>,
+}
+
+impl Pca {
+    /// Creates an unfitted PCA keeping `n_components` components, using the full solver
+    pub fn new(n_components: usize) -> Self {
+        Self::with_components(NComponents::Count(n_components))
+    }
+
+    pub fn with_components(n_components: NComponents) -> Self {
+        Self {
+            n_components,
+            whiten: false,
+            solver: SvdSolver::Full,
+            basis: None,
+        }
+    }
+
+    /// Scales the projected columns to unit variance
+    pub fn whiten(mut self, whiten: bool) -> Self {
+        self.whiten = whiten;
+        self
+    }
+
+    pub fn solver(mut self, solver: SvdSolver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    /// Principal axes, one per row, `None` before `fit`
+    pub fn components(&self) -> Option<&Array2<f64>> {
+        self.basis.as_ref().map(|basis| &basis.components)
+    }
+
+    /// Variance of the training data along every component
+    pub fn explained_variance(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().map(|basis| &basis.explained_variance)
+    }
+
+    /// Fraction of the total training variance explained by every component
+    pub fn explained_variance_ratio(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().map(|basis| &basis.explained_variance_ratio)
+    }
+
+    pub fn singular_values(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().map(|basis| &basis.singular_values)
+    }
+
+    /// Column means of the training data
+    pub fn mean(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().and_then(|basis| basis.mean.as_ref())
+    }
+
+    /// Output column names `pc0`, `pc1`, ...
+    pub fn feature_names(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let basis = self.basis.as_ref().ok_or("Pca has not been fitted")?;
+        Ok(component_names("pc", basis.components.nrows()))
+    }
+}
+
+impl Transform for Pca {
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        let (n_samples, n_features) = x.dim();
+        if n_samples < 2 {
+            return Err("Pca needs at least 2 rows".into());
+        }
+        let max_components = n_samples.min(n_features);
+        let requested = match self.n_components {
+            NComponents::Count(k) if k == 0 || k > max_components => {
+                return Err(format!("Cannot keep {} components of {} rows and {} columns", k, n_samples, n_features).into());
+            }
+            NComponents::Count(k) => k,
+            NComponents::VarianceRatio(ratio) if ratio <= 0.0 || ratio > 1.0 => {
+                return Err(format!("Variance ratio should be in (0, 1], got {}", ratio).into());
+            }
+            NComponents::VarianceRatio(_) | NComponents::All => max_components,
+        };
+
+        let mean = x.mean_axis(Axis(0)).ok_or("Pca needs at least one row")?;
+        let centered = x - &mean.view().insert_axis(Axis(0));
+        let (_, singular_values, vt) = match self.solver {
+            SvdSolver::Full => svd(&centered)?,
+            SvdSolver::Randomized { .. } if matches!(self.n_components, NComponents::VarianceRatio(_)) => {
+                return Err("A variance ratio needs the full SVD solver".into());
+            }
+            SvdSolver::Randomized { n_oversamples, n_iter, seed } => {
+                randomized_svd(&centered, requested, n_oversamples, n_iter, seed)?
+            }
+        };
+
+        let explained_variance = singular_values.mapv(|s| s * s / (n_samples - 1) as f64);
+        let total = total_variance(x);
+        let explained_variance_ratio = if total > 0.0 { &explained_variance / total } else { Array1::zeros(explained_variance.len()) };
+        let k = match self.n_components {
+            NComponents::VarianceRatio(ratio) => {
+                let mut cumulative = 0.0;
+                explained_variance_ratio
+                    .iter()
+                    .position(|r| {
+                        cumulative += r;
+                        cumulative >= ratio - 1e-12
+                    })
+                    .map_or(max_components, |i| i + 1)
+            }
+            _ => requested,
+        };
+
+        let mut components = vt.slice(s![..k, ..]).to_owned();
+        flip_signs(&mut components);
+        self.basis = Some(Basis {
+            mean: Some(mean),
+            components,
+            singular_values: singular_values.slice(s![..k]).to_owned(),
+            explained_variance: explained_variance.slice(s![..k]).to_owned(),
+            explained_variance_ratio: explained_variance_ratio.slice(s![..k]).to_owned(),
+        });
+        Ok(())
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.basis.as_ref().ok_or("Pca has not been fitted")?.project("Pca", x, self.whiten)
+    }
+}
+
+impl InverseTransform for Pca {
+    fn inverse_transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.basis.as_ref().ok_or("Pca has not been fitted")?.reconstruct("Pca", x, self.whiten)
+    }
+}
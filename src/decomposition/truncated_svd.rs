@@ -0,0 +1,151 @@
+use std::error::Error;
+use ndarray::{s, Array1, Array2};
+use serde::{Deserialize, Serialize};
+use crate::decomposition::basis::{component_names, flip_signs, total_variance, Basis};
+use crate::decomposition::SvdSolver;
+use crate::matrix_operations::{randomized_svd, svd, CsrMatrix, LinearOperator, Svd, Transpose};
+use crate::traits::{InverseTransform, Transform};
+
+/// Dimensionality reduction by a truncated SVD of the uncentered data
+///
+/// Unlike `Pca` the data is not centered, so zero entries stay zero and the decomposition
+/// suits sparse, count-like inputs (e.g. bag-of-words). The randomized solver only needs
+/// matrix products with the input, so `fit_sparse` and `transform_sparse` work on a
+/// `CsrMatrix` without densifying it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TruncatedSvd {
+    n_components: usize,
+    solver: SvdSolver,
+    basis: Option<Basis>,
+}
+
+impl TruncatedSvd {
+    /// Creates an unfitted decomposition keeping `n_components`, using the randomized solver
+    pub fn new(n_components: usize) -> Self {
+        Self {
+            n_components,
+            solver: SvdSolver::randomized(None),
+            basis: None,
+        }
+    }
+
+    pub fn solver(mut self, solver: SvdSolver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    /// Right singular vectors, one per row, `None` before `fit`
+    pub fn components(&self) -> Option<&Array2<f64>> {
+        self.basis.as_ref().map(|basis| &basis.components)
+    }
+
+    pub fn singular_values(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().map(|basis| &basis.singular_values)
+    }
+
+    /// Variance of the training data projected on every component
+    pub fn explained_variance(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().map(|basis| &basis.explained_variance)
+    }
+
+    /// Fraction of the total training variance explained by every component
+    pub fn explained_variance_ratio(&self) -> Option<&Array1<f64>> {
+        self.basis.as_ref().map(|basis| &basis.explained_variance_ratio)
+    }
+
+    /// Fits on a sparse input through products only; needs the randomized solver
+    pub fn fit_sparse(&mut self, x: &CsrMatrix) -> Result<(), Box<dyn Error>> {
+        let SvdSolver::Randomized { n_oversamples, n_iter, seed } = self.solver else {
+            return Err("TruncatedSvd needs the randomized solver to fit a sparse input".into());
+        };
+        self.check_shape(x.dim())?;
+        let decomposition = randomized_svd(x, self.n_components, n_oversamples, n_iter, seed)?;
+        self.set_basis(x, decomposition, sparse_total_variance(x))
+    }
+
+    /// Projects the rows of a sparse input on the components
+    pub fn transform_sparse(&self, x: &CsrMatrix) -> Result<Array2<f64>, Box<dyn Error>> {
+        let basis = self.basis.as_ref().ok_or("TruncatedSvd has not been fitted")?;
+        if x.ncols() != basis.components.ncols() {
+            return Err(format!("TruncatedSvd was fitted on {} columns but got {}", basis.components.ncols(), x.ncols()).into());
+        }
+        Ok(x.multiply(Transpose::No, &basis.components.t())?)
+    }
+
+    /// Output column names `svd0`, `svd1`, ...
+    pub fn feature_names(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let basis = self.basis.as_ref().ok_or("TruncatedSvd has not been fitted")?;
+        Ok(component_names("svd", basis.components.nrows()))
+    }
+}
+
+impl Transform for TruncatedSvd {
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        self.check_shape(x.dim())?;
+        let decomposition = match self.solver {
+            SvdSolver::Full => svd(x)?,
+            SvdSolver::Randomized { n_oversamples, n_iter, seed } => {
+                randomized_svd(x, self.n_components, n_oversamples, n_iter, seed)?
+            }
+        };
+        self.set_basis(x, decomposition, total_variance(x))
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.basis.as_ref().ok_or("TruncatedSvd has not been fitted")?.project("TruncatedSvd", x, false)
+    }
+}
+
+impl TruncatedSvd {
+    fn check_shape(&self, (n_samples, n_features): (usize, usize)) -> Result<(), Box<dyn Error>> {
+        if n_samples < 2 {
+            return Err("TruncatedSvd needs at least 2 rows".into());
+        }
+        if self.n_components == 0 || self.n_components > n_samples.min(n_features) {
+            return Err(format!(
+                "Cannot keep {} components of {} rows and {} columns",
+                self.n_components, n_samples, n_features
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Keeps the leading `n_components` of `decomposition` of `x`, whose total variance is `total`
+    fn set_basis<A: LinearOperator>(&mut self, x: &A, decomposition: Svd, total: f64) -> Result<(), Box<dyn Error>> {
+        let (_, singular_values, vt) = decomposition;
+        let k = self.n_components;
+        let mut components = vt.slice(s![..k, ..]).to_owned();
+        flip_signs(&mut components);
+
+        let projected = x.multiply(Transpose::No, &components.t())?;
+        let explained_variance = Array1::from_iter((0..k).map(|i| total_variance(&projected.slice(s![.., i..i + 1]).to_owned())));
+        let explained_variance_ratio = if total > 0.0 { &explained_variance / total } else { Array1::zeros(k) };
+        self.basis = Some(Basis {
+            mean: None,
+            components,
+            singular_values: singular_values.slice(s![..k]).to_owned(),
+            explained_variance,
+            explained_variance_ratio,
+        });
+        Ok(())
+    }
+}
+
+/// `total_variance` from the stored entries of a sparse matrix
+fn sparse_total_variance(x: &CsrMatrix) -> f64 {
+    let n = x.nrows() as f64;
+    let mut sums = vec![0.0; x.ncols()];
+    let mut squares = 0.0;
+    for (&col, &value) in x.indices().iter().zip(x.data()) {
+        sums[col] += value;
+        squares += value * value;
+    }
+    (squares - sums.iter().map(|sum| sum * sum / n).sum::<f64>()) / (n - 1.0)
+}
+
+impl InverseTransform for TruncatedSvd {
+    fn inverse_transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.basis.as_ref().ok_or("TruncatedSvd has not been fitted")?.reconstruct("TruncatedSvd", x, false)
+    }
+}
@@ -0,0 +1,57 @@
+use std::error::Error;
+use ndarray::{Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::feature_selection::support::Support;
+use crate::stats::pearson_correlation;
+use crate::traits::Transform;
+
+/// Drops redundant columns: a column is removed when its absolute Pearson correlation
+/// with an earlier kept column is above `max_correlation`
+///
+/// Columns are visited in input order, so the first column of a correlated group survives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationFilter {
+    max_correlation: f64,
+    support: Option<Support>,
+}
+
+impl CorrelationFilter {
+    pub fn new(max_correlation: f64) -> Self {
+        Self { max_correlation, support: None }
+    }
+
+    /// Indices of the kept input columns, `None` before `fit`
+    pub fn support(&self) -> Option<&[usize]> {
+        self.support.as_ref().map(|support| support.selected())
+    }
+
+    /// Names of the kept columns among `input_names`
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        self.support.as_ref().ok_or("CorrelationFilter has not been fitted")?.feature_names(input_names)
+    }
+}
+
+impl Transform for CorrelationFilter {
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        let columns: Vec<Vec<f64>> = x.axis_iter(Axis(1)).map(|column| column.to_vec()).collect();
+        let mut kept: Vec<usize> = Vec::new();
+        for (i, column) in columns.iter().enumerate() {
+            // Constant columns have an undefined correlation and are never redundant
+            let redundant = kept
+                .iter()
+                .any(|&j| pearson_correlation(column, &columns[j]).abs() > self.max_correlation);
+            if !redundant {
+                kept.push(i);
+            }
+        }
+        self.support = Some(Support::new("CorrelationFilter", x.ncols(), kept)?);
+        Ok(())
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.support
+            .as_ref()
+            .ok_or("CorrelationFilter has not been fitted")?
+            .transform("CorrelationFilter", x)
+    }
+}
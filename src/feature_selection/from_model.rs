@@ -0,0 +1,80 @@
+use std::error::Error;
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+use crate::feature_selection::support::{coefficient_importances, Support};
+use crate::multivariate_regression::linear_regression::LinearRegression;
+use crate::multivariate_regression::model::TrainedModel;
+use crate::multivariate_regression::training::train_config::TrainConfig;
+use crate::traits::{Fit, SupervisedTransform};
+
+/// Keeps the columns to which a (typically L1 regularized) linear model gives a non-zero weight
+///
+/// Gradient descent leaves L1 weights oscillating around zero rather than exactly at it,
+/// so a column counts as zero when its summed absolute weight is at most `threshold`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectFromModel {
+    config: TrainConfig,
+    threshold: f64,
+    importances: Option<Array1<f64>>,
+    support: Option<Support>,
+}
+
+impl SelectFromModel {
+    /// Creates an unfitted selector training a model with `config`, e.g. with `Regularization::l1`
+    pub fn new(config: TrainConfig, threshold: f64) -> Self {
+        Self {
+            config,
+            threshold,
+            importances: None,
+            support: None,
+        }
+    }
+
+    /// Fitted selector reading the weights of an already trained model
+    pub fn prefit(model: &TrainedModel, threshold: f64) -> Result<Self, Box<dyn Error>> {
+        let mut selector = Self::new(TrainConfig::default(), threshold);
+        selector.select(model)?;
+        Ok(selector)
+    }
+
+    /// Summed absolute weight of every input column, `None` before `fit`
+    pub fn importances(&self) -> Option<&Array1<f64>> {
+        self.importances.as_ref()
+    }
+
+    /// Indices of the kept input columns, `None` before `fit`
+    pub fn support(&self) -> Option<&[usize]> {
+        self.support.as_ref().map(|support| support.selected())
+    }
+
+    /// Names of the kept columns among `input_names`
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        self.support.as_ref().ok_or("SelectFromModel has not been fitted")?.feature_names(input_names)
+    }
+
+    fn select(&mut self, model: &TrainedModel) -> Result<(), Box<dyn Error>> {
+        let importances = coefficient_importances(model);
+        let mask: Vec<bool> = importances.iter().map(|&w| w > self.threshold).collect();
+        self.support = Some(Support::from_mask("SelectFromModel", &mask)?);
+        self.importances = Some(importances);
+        Ok(())
+    }
+}
+
+impl Fit for SelectFromModel {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        let mut estimator = LinearRegression::new(self.config.clone());
+        estimator.fit(x, y)?;
+        self.select(estimator.model().ok_or("SelectFromModel model was not trained")?)
+    }
+}
+
+impl SupervisedTransform for SelectFromModel {
+    /// Keeps the selected columns of `x`
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.support
+            .as_ref()
+            .ok_or("SelectFromModel has not been fitted")?
+            .transform("SelectFromModel", x)
+    }
+}
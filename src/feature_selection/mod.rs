@@ -0,0 +1,12 @@
+mod support;
+mod variance;
+mod correlation;
+mod univariate;
+mod rfe;
+mod from_model;
+
+pub use variance::*;
+pub use correlation::*;
+pub use univariate::*;
+pub use rfe::*;
+pub use from_model::*;
@@ -0,0 +1,90 @@
+use std::error::Error;
+use ndarray::{Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::feature_selection::support::{coefficient_importances, top_k, Support};
+use crate::multivariate_regression::linear_regression::LinearRegression;
+use crate::multivariate_regression::training::train_config::TrainConfig;
+use crate::traits::{Fit, SupervisedTransform};
+
+/// Recursive feature elimination with a linear model
+///
+/// Repeatedly trains on the remaining columns and drops the `step` columns with the
+/// smallest absolute weights until `n_features_to_select` remain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rfe {
+    config: TrainConfig,
+    n_features_to_select: usize,
+    step: usize,
+    ranking: Option<Vec<usize>>,
+    support: Option<Support>,
+}
+
+impl Rfe {
+    /// Creates an unfitted selector
+    ///
+    /// # Parameters:
+    /// - `config: TrainConfig` - Configuration of the model trained at every round
+    /// - `n_features_to_select: usize` - Number of columns to keep
+    /// - `step: usize` - Number of columns removed per round
+    pub fn new(config: TrainConfig, n_features_to_select: usize, step: usize) -> Self {
+        Self {
+            config,
+            n_features_to_select,
+            step: step.max(1),
+            ranking: None,
+            support: None,
+        }
+    }
+
+    /// Rank of every input column: 1 for kept columns, higher for columns eliminated earlier
+    pub fn ranking(&self) -> Option<&[usize]> {
+        self.ranking.as_deref()
+    }
+
+    /// Indices of the kept input columns, `None` before `fit`
+    pub fn support(&self) -> Option<&[usize]> {
+        self.support.as_ref().map(|support| support.selected())
+    }
+
+    /// Names of the kept columns among `input_names`
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        self.support.as_ref().ok_or("Rfe has not been fitted")?.feature_names(input_names)
+    }
+}
+
+impl Fit for Rfe {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if self.n_features_to_select == 0 || self.n_features_to_select > x.ncols() {
+            return Err(format!("Cannot select {} of {} features", self.n_features_to_select, x.ncols()).into());
+        }
+
+        let mut remaining: Vec<usize> = (0..x.ncols()).collect();
+        let mut eliminated: Vec<Vec<usize>> = Vec::new(); // Columns removed in every round
+        while remaining.len() > self.n_features_to_select {
+            let mut estimator = LinearRegression::new(self.config.clone());
+            estimator.fit(&x.select(Axis(1), &remaining), y)?;
+            let importances = coefficient_importances(estimator.model().ok_or("Rfe model was not trained")?);
+
+            let n_drop = self.step.min(remaining.len() - self.n_features_to_select);
+            let keep = top_k(importances.as_slice().unwrap(), remaining.len() - n_drop);
+            let (kept, dropped): (Vec<usize>, Vec<usize>) = (0..remaining.len()).partition(|i| keep.contains(i));
+            eliminated.push(dropped.iter().map(|&i| remaining[i]).collect());
+            remaining = kept.iter().map(|&i| remaining[i]).collect();
+        }
+
+        let mut ranking = vec![1; x.ncols()];
+        for (round, columns) in eliminated.iter().enumerate() {
+            columns.iter().for_each(|&c| ranking[c] = eliminated.len() - round + 1);
+        }
+        self.ranking = Some(ranking);
+        self.support = Some(Support::new("Rfe", x.ncols(), remaining)?);
+        Ok(())
+    }
+}
+
+impl SupervisedTransform for Rfe {
+    /// Keeps the selected columns of `x`
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.support.as_ref().ok_or("Rfe has not been fitted")?.transform("Rfe", x)
+    }
+}
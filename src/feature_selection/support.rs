@@ -0,0 +1,62 @@
+use std::error::Error;
+use ndarray::{s, Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::multivariate_regression::model::TrainedModel;
+
+/// Input columns kept by a fitted selector, in input order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Support {
+    n_features: usize,
+    selected: Vec<usize>,
+}
+
+impl Support {
+    pub(crate) fn new(selector: &str, n_features: usize, selected: Vec<usize>) -> Result<Self, Box<dyn Error>> {
+        if selected.is_empty() {
+            return Err(format!("{} selected none of the {} features", selector, n_features).into());
+        }
+        Ok(Self { n_features, selected })
+    }
+
+    /// Support of the columns whose mask entry is set
+    pub(crate) fn from_mask(selector: &str, mask: &[bool]) -> Result<Self, Box<dyn Error>> {
+        let selected = (0..mask.len()).filter(|&i| mask[i]).collect();
+        Self::new(selector, mask.len(), selected)
+    }
+
+    pub(crate) fn selected(&self) -> &[usize] {
+        &self.selected
+    }
+
+    pub(crate) fn transform(&self, selector: &str, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        if x.ncols() != self.n_features {
+            return Err(format!("{} was fitted on {} columns but got {}", selector, self.n_features, x.ncols()).into());
+        }
+        Ok(x.select(Axis(1), &self.selected))
+    }
+
+    pub(crate) fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        if input_names.len() != self.n_features {
+            return Err(format!("Got {} names for {} input columns", input_names.len(), self.n_features).into());
+        }
+        Ok(self.selected.iter().map(|&i| input_names[i].clone()).collect())
+    }
+}
+
+/// Indices of the `k` largest scores, returned in input order; ties keep the earlier column
+pub(crate) fn top_k(scores: &[f64], k: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+    order.truncate(k);
+    order.sort_unstable();
+    order
+}
+
+/// Importance of every input column of a linear model: the sum over outputs of its
+/// absolute weights, which are comparable because the model normalizes its inputs
+pub(crate) fn coefficient_importances(model: &TrainedModel) -> Array1<f64> {
+    model
+        .weights()
+        .slice(s![1.., ..])
+        .map_axis(Axis(1), |row| row.iter().map(|w| w.abs()).sum())
+}
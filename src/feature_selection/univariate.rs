@@ -0,0 +1,176 @@
+use std::error::Error;
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use serde::{Deserialize, Serialize};
+use crate::feature_selection::support::{top_k, Support};
+use crate::stats::{digamma, f_sf, pearson_correlation};
+use crate::traits::{Fit, SupervisedTransform};
+
+/// Statistic scoring the dependence between one input column and the targets
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ScoreFunction {
+    FRegression,              // F statistic of the univariate linear regression, with p-values
+    MutualInformation(usize), // k-nearest-neighbour (Kraskov) estimate using this many neighbours
+    Correlation,              // Absolute Pearson correlation
+}
+
+/// Which columns a `UnivariateSelector` keeps given their scores
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SelectionMode {
+    KBest(usize),    // The k highest scores
+    Percentile(f64), // The given percentage (0-100] of columns with the highest scores
+    Threshold(f64),  // Every column scoring at least this value
+}
+
+/// Selects input columns by scoring each one separately against the targets
+///
+/// With several target columns the score of a column is its mean score over the targets.
+/// Columns with an undefined score (e.g. constant columns) score 0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnivariateSelector {
+    score_function: ScoreFunction,
+    mode: SelectionMode,
+    scores: Option<Array1<f64>>,
+    p_values: Option<Array1<f64>>,
+    support: Option<Support>,
+}
+
+impl UnivariateSelector {
+    pub fn new(score_function: ScoreFunction, mode: SelectionMode) -> Self {
+        Self {
+            score_function,
+            mode,
+            scores: None,
+            p_values: None,
+            support: None,
+        }
+    }
+
+    /// Keeps the `k` columns with the highest scores
+    pub fn k_best(score_function: ScoreFunction, k: usize) -> Self {
+        Self::new(score_function, SelectionMode::KBest(k))
+    }
+
+    /// Keeps the columns whose absolute correlation with the targets is at least `threshold`
+    pub fn correlation_threshold(threshold: f64) -> Self {
+        Self::new(ScoreFunction::Correlation, SelectionMode::Threshold(threshold))
+    }
+
+    /// Score of every input column, `None` before `fit`
+    pub fn scores(&self) -> Option<&Array1<f64>> {
+        self.scores.as_ref()
+    }
+
+    /// p-value of every input column, only for `ScoreFunction::FRegression`
+    pub fn p_values(&self) -> Option<&Array1<f64>> {
+        self.p_values.as_ref()
+    }
+
+    /// Indices of the kept input columns, `None` before `fit`
+    pub fn support(&self) -> Option<&[usize]> {
+        self.support.as_ref().map(|support| support.selected())
+    }
+
+    /// Names of the kept columns among `input_names`
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        self.support.as_ref().ok_or("UnivariateSelector has not been fitted")?.feature_names(input_names)
+    }
+}
+
+impl Fit for UnivariateSelector {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if x.nrows() != y.nrows() {
+            return Err(format!("x has {} rows but y has {}", x.nrows(), y.nrows()).into());
+        }
+        if x.nrows() < 3 {
+            return Err("UnivariateSelector needs at least 3 rows".into());
+        }
+        if y.ncols() == 0 {
+            return Err("UnivariateSelector needs at least one target column".into());
+        }
+
+        let n = x.nrows() as f64;
+        let mut scores = Array1::<f64>::zeros(x.ncols());
+        let mut p_values = Array1::<f64>::zeros(x.ncols());
+        for (i, column) in x.axis_iter(Axis(1)).enumerate() {
+            for target in y.axis_iter(Axis(1)) {
+                let (score, p_value) = match self.score_function {
+                    ScoreFunction::FRegression => {
+                        let r = pearson_correlation(&column.to_vec(), &target.to_vec());
+                        if r.is_nan() {
+                            (0.0, 1.0)
+                        } else {
+                            let f = r * r / (1.0 - r * r) * (n - 2.0);
+                            (f, f_sf(f, 1.0, n - 2.0))
+                        }
+                    }
+                    ScoreFunction::MutualInformation(neighbours) => {
+                        (mutual_information(column, target, neighbours)?, f64::NAN)
+                    }
+                    ScoreFunction::Correlation => {
+                        let r = pearson_correlation(&column.to_vec(), &target.to_vec());
+                        (if r.is_nan() { 0.0 } else { r.abs() }, f64::NAN)
+                    }
+                };
+                scores[i] += score / y.ncols() as f64;
+                p_values[i] += p_value / y.ncols() as f64;
+            }
+        }
+
+        let selected = match self.mode {
+            SelectionMode::KBest(k) => top_k(scores.as_slice().unwrap(), k.min(x.ncols())),
+            SelectionMode::Percentile(percent) => {
+                if percent <= 0.0 || percent > 100.0 {
+                    return Err(format!("Percentile should be in (0, 100], got {}", percent).into());
+                }
+                let k = (x.ncols() as f64 * percent / 100.0).ceil() as usize;
+                top_k(scores.as_slice().unwrap(), k)
+            }
+            SelectionMode::Threshold(threshold) => (0..x.ncols()).filter(|&i| scores[i] >= threshold).collect(),
+        };
+        self.support = Some(Support::new("UnivariateSelector", x.ncols(), selected)?);
+        self.p_values = (self.score_function == ScoreFunction::FRegression).then_some(p_values);
+        self.scores = Some(scores);
+        Ok(())
+    }
+}
+
+impl SupervisedTransform for UnivariateSelector {
+    /// Keeps the selected columns of `x`
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.support
+            .as_ref()
+            .ok_or("UnivariateSelector has not been fitted")?
+            .transform("UnivariateSelector", x)
+    }
+}
+
+/// Kraskov-Stögbauer-Grassberger estimate of the mutual information (in nats) of two
+/// continuous samples, computed on standardized values with the max-norm
+fn mutual_information(x: ArrayView1<f64>, y: ArrayView1<f64>, neighbours: usize) -> Result<f64, Box<dyn Error>> {
+    let n = x.len();
+    if neighbours == 0 || neighbours >= n {
+        return Err(format!("Mutual information needs 1 to {} neighbours, got {}", n - 1, neighbours).into());
+    }
+    let standardize = |v: ArrayView1<f64>| -> Option<Vec<f64>> {
+        let mean = v.sum() / n as f64;
+        let std = (v.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / n as f64).sqrt();
+        (std > 0.0).then(|| v.iter().map(|a| (a - mean) / std).collect())
+    };
+    // A constant sample carries no information
+    let (Some(x), Some(y)) = (standardize(x), standardize(y)) else {
+        return Ok(0.0);
+    };
+
+    let mut sum = 0.0;
+    let mut distances = Vec::with_capacity(n - 1);
+    for i in 0..n {
+        distances.clear();
+        distances.extend((0..n).filter(|&j| j != i).map(|j| (x[i] - x[j]).abs().max((y[i] - y[j]).abs())));
+        distances.select_nth_unstable_by(neighbours - 1, |a, b| a.total_cmp(b));
+        let radius = distances[neighbours - 1];
+        let n_x = (0..n).filter(|&j| j != i && (x[i] - x[j]).abs() < radius).count();
+        let n_y = (0..n).filter(|&j| j != i && (y[i] - y[j]).abs() < radius).count();
+        sum += digamma(n_x as f64 + 1.0) + digamma(n_y as f64 + 1.0);
+    }
+    Ok((digamma(n as f64) + digamma(neighbours as f64) - sum / n as f64).max(0.0))
+}
@@ -0,0 +1,64 @@
+use std::error::Error;
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::feature_selection::support::Support;
+use crate::traits::Transform;
+
+/// Drops the columns whose variance is not above a threshold
+///
+/// The default threshold of 0 removes constant columns. Missing (`NaN`) values are
+/// ignored when computing the variance.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VarianceThreshold {
+    threshold: f64,
+    variances: Option<Array1<f64>>,
+    support: Option<Support>,
+}
+
+impl VarianceThreshold {
+    pub fn new(threshold: f64) -> Self {
+        Self { threshold, variances: None, support: None }
+    }
+
+    /// Population variance of every input column, `None` before `fit`
+    pub fn variances(&self) -> Option<&Array1<f64>> {
+        self.variances.as_ref()
+    }
+
+    /// Indices of the kept input columns, `None` before `fit`
+    pub fn support(&self) -> Option<&[usize]> {
+        self.support.as_ref().map(|support| support.selected())
+    }
+
+    /// Names of the kept columns among `input_names`
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        self.support.as_ref().ok_or("VarianceThreshold has not been fitted")?.feature_names(input_names)
+    }
+}
+
+impl Transform for VarianceThreshold {
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        let variances: Array1<f64> = x
+            .axis_iter(Axis(1))
+            .map(|column| {
+                let observed: Vec<f64> = column.iter().copied().filter(|v| !v.is_nan()).collect();
+                if observed.is_empty() {
+                    return 0.0;
+                }
+                let mean = observed.iter().sum::<f64>() / observed.len() as f64;
+                observed.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / observed.len() as f64
+            })
+            .collect();
+        let mask: Vec<bool> = variances.iter().map(|&v| v > self.threshold).collect();
+        self.support = Some(Support::from_mask("VarianceThreshold", &mask)?);
+        self.variances = Some(variances);
+        Ok(())
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.support
+            .as_ref()
+            .ok_or("VarianceThreshold has not been fitted")?
+            .transform("VarianceThreshold", x)
+    }
+}
@@ -3,10 +3,28 @@
 //! A Rust-based machine learning library for machine learning and fast inference.
 //!
 //! ## Features
-//! - CSV Input/Output
-//! - Multivariate Regression
-//! - Matrix Operations
-//! - Training with Different Optimizers
+//! - CSV Input/Output with typed, header-aware datasets
+//! - NumPy `.npy`/`.npz`, JSON-lines and LIBSVM (read as sparse) Input/Output
+//! - Apache Parquet and Arrow IPC Input/Output (`parquet` feature, enabled by default)
+//! - Missing-value detection and imputation
+//! - Feature Selection: Variance and Correlation Filters, F-Test, Mutual Information, RFE and L1 Models
+//! - Multivariate Regression on dense or sparse (CSR) inputs, with per-row sample weights, JSON model persistence and optional target scaling
+//! - Standard, Min-Max, Max-Abs, Robust, Quantile and Power Scalers, Row Normalization
+//! - Polynomial and Interaction Feature Expansion
+//! - B-Spline and Natural Cubic Spline Bases, K-Bins Discretization
+//! - Serializable Pipelines of Transformers and a Model, with Per-Column Transformers
+//! - Common `Fit`, `Predict`, `Transform`, `SupervisedTransform`, `Score` and `Persist` Traits, with a `LinearRegression` Estimator
+//! - Regression and Classification Metrics, Optionally Weighted per Row
+//! - Stratified, Grouped, Chronological and Three-Way Train/Test Splits
+//! - K-fold, Stratified, Grouped and Time-Series Cross-Validation
+//! - Grid, Random and Successive-Halving Hyperparameter Search
+//! - Matrix Operations: GEMM/GEMV on strided views, CSR/CSC Sparse Matrices and Sparse x Dense Products, Inverse, Triangular Solves, Cholesky, LU, QR and (Weighted) Least Squares, Symmetric Eigendecomposition, SVD and Randomized SVD
+//! - PCA (Full, Randomized and Incremental, with Whitening) and Truncated SVD
+//! - Quantile Regression, OLS Prediction Intervals and Split-Conformal Intervals
+//! - Coefficient Inference: Standard Errors, t-Tests, Confidence Intervals and Robust Covariance
+//! - Seeded Synthetic Datasets: Regression with Known Coefficients, Correlated or Sparse Features, Classification, Blobs, Moons, Circles and Friedman Problems
+//! - Squared, Absolute, Huber and Quantile Losses, or Your Own through the `Loss` Trait
+//! - Training with Different Optimizers, in an Allocation-Free Loop over Reused Buffers (see `benches/training.rs`)
 //!
 //! ## Example Usage
 // ```rust
@@ -15,43 +33,1403 @@
 
 // Re-export public modules
 pub mod data_utils;
+pub mod datasets;
+pub mod decomposition;
+pub mod feature_selection;
 pub mod matrix_operations;
+pub mod metrics;
+pub mod model_selection;
 pub mod multivariate_regression;
-
-use data_utils::{csv_read_input_output, train_test_split};
+pub mod pipeline;
+pub mod preprocessing;
+pub mod stats;
+pub mod traits;
+mod utils;
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use crate::multivariate_regression::cost_fn::cost_fn::CostFn;
+    use ndarray::array;
+    use crate::data_utils::{jsonl_read, jsonl_read_input_output, jsonl_write, libsvm_read, libsvm_write, npy_read, npy_write, npz_read_input_output, npz_write};
+    use crate::data_utils::{ColumnType, CsvConfigBuilder, Dataset, csv_read_input_output, csv_read_input_output_with_na, csv_write, missing_report, train_test_split};
+    use crate::data_utils::{SplitStrategy, TestSize, split_indices, train_test_split_with, train_val_test_split};
+    use crate::metrics::*;
+    use crate::model_selection::*;
     use crate::multivariate_regression::gradient::Gradient;
+    use crate::multivariate_regression::loss::{AbsoluteError, HuberLoss, Loss, LossFn, QuantileLoss, SquaredError};
+    use crate::multivariate_regression::intervals::split_conformal;
+    use crate::multivariate_regression::inference::{CovarianceType, SummaryConfig, SummaryConfigBuilder};
     use crate::multivariate_regression::regularization::regularization::Regularization;
+    use crate::multivariate_regression::training::quantile::train_quantiles;
     use crate::multivariate_regression::training::train::train;
     use crate::multivariate_regression::training::train_config::TrainConfigBuilder;
-    use crate::multivariate_regression::update_weight::UpdatationMethod;
+    use crate::multivariate_regression::update_weight::{update_weight, MiniBatchSize, TrainingWorkspace, UpdatationMethod};
+    use crate::preprocessing::{ImputeStrategy, KnnImputer, PolynomialFeatures, SimpleImputer};
+    use crate::multivariate_regression::normalization::normalization::{denormalize_data, normalize_data, NormalizationParameterType};
+    use crate::preprocessing::{MaxAbsScaler, MinMaxScaler, Norm, Normalizer, PowerMethod, PowerTransformer, QuantileOutput, QuantileTransformer, RobustScaler, Scaler, StandardScaler};
+    use crate::preprocessing::{BinEncoding, BinStrategy, KBinsDiscretizer, KnotStrategy, SplineBasis, SplineTransformer};
+    use crate::pipeline::{ColumnTransformer, Pipeline, Remainder, Step};
+    use crate::decomposition::{IncrementalPca, NComponents, Pca, SvdSolver, TruncatedSvd};
+    use crate::matrix_operations::{matrix_mul, qr_decomposition, randomized_svd, svd, symmetric_eigen};
+    use crate::matrix_operations::{cholesky, cholesky_solve, gemm, gemv, least_squares, lu_decomposition, matrix_inverse, solve_triangular, try_matrix_mul, weighted_least_squares, MatrixError, Transpose};
+    use crate::matrix_operations::{CscMatrix, CsrMatrix, LinearOperator};
+    use crate::multivariate_regression::training::train::{train_sparse, train_sparse_weighted, train_weighted, try_train_weighted};
+    use crate::feature_selection::{CorrelationFilter, Rfe, ScoreFunction, SelectFromModel, UnivariateSelector, VarianceThreshold};
+    use crate::multivariate_regression::linear_regression::LinearRegression;
+    use crate::traits::{Fit, FitWeighted, InverseTransform, Persist, Predict, Score, SupervisedTransform, Transform};
+    use crate::datasets::*;
 
     #[test]
     fn train_test() {
-        let filepath = "Student_Performance.csv".to_string();
-        let output_cols = vec!["Performance Index".to_string()];
-        let input_exclude_cols: Vec<String> = Vec::new();
-
-        let (input, output) = csv_read_input_output(filepath, output_cols, input_exclude_cols)
-            .expect("Failed to read input and output from CSV");
-        let (x_train, y_train, x_test, y_test) = train_test_split(input, output, 0.7)
+        let config = RegressionConfigBuilder::new().n_samples(500).n_features(5).noise(5.).seed(7).build();
+        let (input, output, _) = make_regression(&config).expect("Failed to generate dataset");
+        let (x_train, y_train, x_test, y_test) = train_test_split_with(input, output, TestSize::Fraction(0.3), &SplitStrategy::Shuffle, Some(7))
             .expect("Failed to split dataset");
 
         let config = TrainConfigBuilder::new()
             .epochs(100)
             .print_log(true)
-            .cost_fn(CostFn::mean_absolute_error())
-            .delta(0.9)
+            .loss(LossFn::AbsoluteError)
             .regularization(Regularization::elastic_net(0.06,0.05))
             .learning_rate(0.0001)
             .optimizer(UpdatationMethod::BGD)
-            .gradient_fn(Gradient::mean_absolute_error(Regularization::elastic_net(0.6,0.05)))
             .build();
 
-        train(x_train, y_train, config);
+        let model = train(x_train, y_train, config);
+        let y_pred = model.predict(&x_test);
+        assert_eq!(y_pred.dim(), y_test.dim());
+        assert!(r2_score(&y_test, &y_pred, MultiOutput::UniformAverage).is_finite());
+        assert!(root_mean_squared_error(&y_test, &y_pred, MultiOutput::UniformAverage).is_finite());
+    }
+
+    #[test]
+    fn missing_values_test() {
+        let filepath = std::env::temp_dir().join("ferrite_missing_values_test.csv");
+        std::fs::write(&filepath, "a,b,c,y\n1,,3,1\n2,5,NA,0\n3,7,9,?\n,9,-,1\n").unwrap();
+        let filepath = filepath.to_string_lossy().to_string();
+
+        let (input, output) = csv_read_input_output_with_na(
+            filepath.clone(),
+            vec!["y".to_string()],
+            Vec::new(),
+            vec!["NA".to_string(), "?".to_string(), "-".to_string()],
+        )
+        .expect("Failed to read CSV with missing values");
+        assert_eq!(missing_report(&input).missing_counts, vec![1, 1, 2]);
+        assert_eq!(missing_report(&output).missing_counts, vec![1]);
+        assert!(csv_read_input_output(filepath, vec!["y".to_string()], Vec::new()).is_err());
+
+        let mut imputer = SimpleImputer::new(ImputeStrategy::Median, true);
+        let filled = imputer.fit_transform(&input).unwrap();
+        assert_eq!(filled.ncols(), 6);
+        assert_eq!(filled.row(0).to_vec(), vec![1., 7., 3., 0., 1., 0.]);
+        assert_eq!(filled.row(3).to_vec(), vec![2., 9., 6., 1., 0., 1.]);
+
+        let mut imputer = SimpleImputer::most_frequent();
+        let filled = imputer.fit_transform(&array![[1., 2.], [1., f64::NAN], [3., 2.], [f64::NAN, 4.]]).unwrap();
+        assert_eq!(filled.column(0).to_vec(), vec![1., 1., 3., 1.]);
+        assert_eq!(filled.column(1).to_vec(), vec![2., 2., 2., 4.]);
+
+        let mut imputer = KnnImputer::new(1, false);
+        imputer.fit(&array![[0., 0.], [1., 10.], [10., 100.]]).unwrap();
+        let filled = imputer.transform(&array![[9., f64::NAN], [f64::NAN, 11.]]).unwrap();
+        assert_eq!(filled, array![[9., 100.], [1., 11.]]);
+    }
+
+    #[test]
+    fn dataset_test() {
+        let dir = std::env::temp_dir();
+        let filepath = dir.join("ferrite_dataset_test.csv").to_string_lossy().to_string();
+        std::fs::write(&filepath, "id;x1;x2;y\n\"a;1\";1;0.5;2.5\nb;2;;3.5\nc;3;1.5;NA\n").unwrap();
+
+        let config = CsvConfigBuilder::new().delimiter(b';').build();
+        let dataset = Dataset::read_csv(filepath.clone(), &config).expect("Failed to read dataset");
+        assert_eq!(dataset.column_names(), ["id", "x1", "x2", "y"]);
+        assert_eq!(
+            dataset.column_types(),
+            [ColumnType::Text, ColumnType::Integer, ColumnType::Float, ColumnType::Float]
+        );
+        assert_eq!(dataset.text_column(&"id".into()).unwrap(), ["a;1", "b", "c"]);
+
+        let (input, output) = dataset.input_output(&["y".into()], &[]).unwrap();
+        assert_eq!(input.ncols(), 2);
+        assert_eq!(output.column(0).to_vec()[..2], [2.5, 3.5]);
+        assert_eq!(dataset.select(&[2.into(), "x1".into()]).unwrap().row(0).to_vec(), vec![0.5, 1.]);
+        assert!(dataset.select(&["id".into()]).is_err());
+
+        let written = dir.join("ferrite_dataset_roundtrip.csv").to_string_lossy().to_string();
+        dataset.write_csv(written.clone(), &config).unwrap();
+        let reread = Dataset::read_csv(written.clone(), &config).unwrap();
+        assert_eq!(reread.column_names(), dataset.column_names());
+        assert_eq!(reread.column_types(), dataset.column_types());
+        assert_eq!(reread.text_column(&0.into()).unwrap(), dataset.text_column(&0.into()).unwrap());
+        assert_eq!(reread.data().slice(ndarray::s![.., 1..2]), dataset.data().slice(ndarray::s![.., 1..2]));
+        assert_eq!(
+            std::fs::read_to_string(&written).unwrap(),
+            "id;x1;x2;y\n\"a;1\";1;0.5;2.5\nb;2;;3.5\nc;3;1.5;\n"
+        );
+
+        // Forcing a fractional column to `Integer` reads fine but cannot be written back
+        let forced = CsvConfigBuilder::new().delimiter(b';').column_type("x2", ColumnType::Integer).build();
+        assert!(Dataset::read_csv(filepath.clone(), &forced).unwrap().write_csv(written.clone(), &forced).is_err());
+
+        let predictions = Dataset::new(vec!["y".to_string()], array![[2.4], [3.6], [4.1]]).unwrap();
+        let ids = dataset.select_dataset(&["id".into()]).unwrap();
+        let output_path = dir.join("ferrite_dataset_predictions.csv").to_string_lossy().to_string();
+        ids.hstack(&predictions).unwrap().write_csv(output_path.clone(), &CsvConfigBuilder::new().build()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(output_path).unwrap(),
+            "id,y\na;1,2.4\nb,3.6\nc,4.1\n"
+        );
+
+        let plain = dir.join("ferrite_csv_write_test.csv").to_string_lossy().to_string();
+        csv_write(plain.clone(), vec!["x".to_string(), "y".to_string()], &array![[1., 2.], [3., 4.5]]).unwrap();
+        assert_eq!(std::fs::read_to_string(&plain).unwrap(), "x,y\n1,2\n3,4.5\n");
+        csv_write(plain.clone(), Vec::new(), &array![[1., 2.]]).unwrap();
+        assert_eq!(std::fs::read_to_string(plain).unwrap(), "1,2\n");
+    }
+
+    #[test]
+    fn dataset_formats_test() {
+        let dir = std::env::temp_dir();
+        let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+        let x = array![[1., 0., 2.5], [0., 0., -1.], [3., 4., 0.]];
+        let y = array![[1.], [0.], [2.]];
+
+        npy_write(path("ferrite_x.npy"), &x).unwrap();
+        assert_eq!(npy_read(path("ferrite_x.npy")).unwrap(), x);
+
+        // Fortran-ordered int32 array of shape (2, 3), as saved by NumPy
+        let mut npy = b"\x93NUMPY\x01\x00".to_vec();
+        let header = "{'descr': '<i4', 'fortran_order': True, 'shape': (2, 3), }          \n";
+        npy.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        npy.extend_from_slice(header.as_bytes());
+        for value in [1i32, 4, 2, 5, 3, 6] {
+            npy.extend_from_slice(&value.to_le_bytes());
+        }
+        std::fs::write(path("ferrite_fortran.npy"), npy).unwrap();
+        assert_eq!(npy_read(path("ferrite_fortran.npy")).unwrap(), array![[1., 2., 3.], [4., 5., 6.]]);
+
+        // A header claiming a huge shape is rejected before any allocation
+        let mut npy = b"\x93NUMPY\x01\x00".to_vec();
+        let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (4294967296, 4294967296), }\n";
+        npy.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        npy.extend_from_slice(header.as_bytes());
+        std::fs::write(path("ferrite_huge.npy"), &npy).unwrap();
+        assert!(npy_read(path("ferrite_huge.npy")).is_err());
+        let mut npy = b"\x93NUMPY\x01\x00".to_vec();
+        let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (1000, 1000), }\n";
+        npy.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        npy.extend_from_slice(header.as_bytes());
+        npy.extend_from_slice(&1f64.to_le_bytes());
+        std::fs::write(path("ferrite_truncated.npy"), npy).unwrap();
+        assert!(npy_read(path("ferrite_truncated.npy")).is_err());
+
+        npz_write(path("ferrite_xy.npz"), &[("x", &x), ("y", &y)]).unwrap();
+        assert_eq!(npz_read_input_output(path("ferrite_xy.npz"), "x", "y").unwrap(), (x.clone(), y.clone()));
+
+        let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        jsonl_write(path("ferrite_x.jsonl"), names.clone(), &x).unwrap();
+        assert_eq!(jsonl_read(path("ferrite_x.jsonl"), names).unwrap(), x);
+        std::fs::write(
+            path("ferrite_records.jsonl"),
+            "{\"id\": \"r1\", \"a\": 1, \"b\": true, \"meta\": {\"w\": 2}, \"y\": 3.5}\n\n{\"id\": \"r2\", \"a\": null, \"b\": false, \"meta\": {}, \"y\": \"4\"}\n",
+        )
+        .unwrap();
+        let (input, output) = jsonl_read_input_output(path("ferrite_records.jsonl"), vec!["y".to_string()], Vec::new()).unwrap();
+        assert_eq!(input.row(0).to_vec(), vec![1., 1.]);
+        assert!(input[(1, 0)].is_nan());
+        assert_eq!(output, array![[3.5], [4.]]);
+        let nested = jsonl_read(path("ferrite_records.jsonl"), vec!["meta.w".to_string()]).unwrap();
+        assert_eq!(nested[(0, 0)], 2.);
+        assert!(nested[(1, 0)].is_nan());
+
+        libsvm_write(path("ferrite_xy.svm"), &x, &y).unwrap();
+        assert_eq!(std::fs::read_to_string(path("ferrite_xy.svm")).unwrap().lines().next(), Some("1 1:1 3:2.5"));
+        let (sparse, labels) = libsvm_read(path("ferrite_xy.svm"), None).unwrap();
+        assert_eq!((sparse.to_dense(), labels), (x.clone(), y.clone()));
+        let (wide, _) = libsvm_read(path("ferrite_xy.svm"), Some(5)).unwrap();
+        assert_eq!((wide.nrows(), wide.ncols()), (3, 5));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_test() {
+        use crate::data_utils::{arrow_ipc_read_input_output, arrow_ipc_write, parquet_batches, parquet_read, parquet_read_input_output, parquet_write};
+
+        let dir = std::env::temp_dir();
+        let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+        let headers = vec!["a".to_string(), "b".to_string(), "c".to_string(), "y".to_string()];
+        let data = ndarray::Array2::from_shape_fn((10, 4), |(i, j)| (i * 4 + j) as f64);
+
+        parquet_write(path("ferrite_data.parquet"), headers.clone(), &data, Some(4)).unwrap();
+        let batches: Vec<_> = parquet_batches(path("ferrite_data.parquet"), vec!["y".to_string()], vec!["b".to_string()])
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(batches.iter().map(|(x, _)| x.nrows()).collect::<Vec<_>>(), vec![4, 4, 2]);
+        assert_eq!(batches[1].0.row(0).to_vec(), vec![16., 18.]);
+        assert_eq!(batches[2].1.column(0).to_vec(), vec![35., 39.]);
+
+        let (input, output) = parquet_read_input_output(path("ferrite_data.parquet"), vec!["y".to_string()], Vec::new()).unwrap();
+        assert_eq!(input, data.slice(ndarray::s![.., ..3]));
+        assert_eq!(output, data.slice(ndarray::s![.., 3..]));
+        assert_eq!(parquet_read(path("ferrite_data.parquet"), vec!["c".to_string(), "a".to_string()]).unwrap().row(1).to_vec(), vec![6., 4.]);
+
+        parquet_write(path("ferrite_empty.parquet"), headers.clone(), &ndarray::Array2::zeros((0, 4)), None).unwrap();
+        let (input, output) = parquet_read_input_output(path("ferrite_empty.parquet"), vec!["y".to_string()], Vec::new()).unwrap();
+        assert_eq!((input.dim(), output.dim()), ((0, 3), (0, 1)));
+
+        arrow_ipc_write(path("ferrite_data.arrow"), headers, &data).unwrap();
+        let (input, output) = arrow_ipc_read_input_output(path("ferrite_data.arrow"), vec!["y".to_string()], vec!["a".to_string()]).unwrap();
+        assert_eq!(input, data.slice(ndarray::s![.., 1..3]));
+        assert_eq!(output, data.slice(ndarray::s![.., 3..]));
+    }
+
+    #[test]
+    fn metrics_test() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        let y_true = array![[3.], [-0.5], [2.], [7.]];
+        let y_pred = array![[2.5], [0.], [2.], [8.]];
+        let avg = MultiOutput::UniformAverage;
+        assert!(close(r2_score(&y_true, &y_pred, avg), 0.9486081370449679));
+        assert!(close(adjusted_r2_score(&y_true, &y_pred, 1, avg), 1. - (1. - 0.9486081370449679) * 3. / 2.));
+        assert!(close(root_mean_squared_error(&y_true, &y_pred, avg), 0.375f64.sqrt()));
+        assert!(close(mean_absolute_error(&y_true, &y_pred, avg), 0.5));
+        assert!(close(median_absolute_error(&y_true, &y_pred, avg), 0.5));
+        assert!(close(explained_variance_score(&y_true, &y_pred, avg), 0.9571734475374732));
+        assert!(close(max_error(&y_true, &y_pred, avg), 1.));
+        assert!(close(mean_absolute_percentage_error(&array![[2.], [4.]], &array![[1.], [5.]], avg), 0.375));
+
+        let y_true = array![[0.5, 1.], [-1., 1.], [7., -6.]];
+        let y_pred = array![[0., 2.], [-1., 2.], [8., -5.]];
+        assert!(close(r2_score(&y_true, &y_pred, avg), 0.9368005266622779));
+        assert!(close(r2_score(&y_true, &y_pred, MultiOutput::VarianceWeighted), 0.9382566585956417));
+        assert!(close(r2_score(&y_true, &y_pred, MultiOutput::Output(0)), 0.9654377880184332));
+
+        let y_true = array![[0.], [1.], [2.], [0.], [1.], [2.]];
+        let y_pred = array![[0.], [2.], [1.], [0.], [0.], [1.]];
+        assert!(close(accuracy_score(&y_true, &y_pred, avg), 1. / 3.));
+        assert!(close(precision_score(&y_true, &y_pred, Average::Macro, avg), 2. / 9.));
+        assert!(close(precision_score(&y_true, &y_pred, Average::Micro, avg), 1. / 3.));
+        assert!(close(recall_score(&y_true, &y_pred, Average::Weighted, avg), 1. / 3.));
+        assert!(close(f1_score(&y_true, &y_pred, Average::Macro, avg), 4. / 15.));
+        assert!(close(f1_score(&y_true, &y_pred, Average::Binary(0.), avg), 0.8));
+        let confusion = confusion_matrix(&y_true, &y_pred, 0);
+        assert_eq!(confusion.labels, vec![0., 1., 2.]);
+        assert_eq!(confusion.matrix, array![[2, 0, 0], [1, 0, 1], [0, 2, 0]]);
+        // A NaN label matches no class, so it is refused rather than counted as class 0
+        let unlabeled = array![[0.], [f64::NAN], [2.], [0.], [1.], [2.]];
+        assert!(std::panic::catch_unwind(|| confusion_matrix(&unlabeled, &y_pred, 0)).is_err());
+        assert!(std::panic::catch_unwind(|| precision_score(&y_pred, &unlabeled, Average::Macro, avg)).is_err());
+
+        let labels = array![[0.], [0.], [1.], [1.]];
+        let scores = array![[0.1], [0.4], [0.35], [0.8]];
+        assert!(close(roc_auc_score(&labels, &scores, avg), 0.75));
+        assert!(close(pr_auc_score(&labels, &scores, avg), 5. / 6.));
+        assert!(close(log_loss(&array![[1.], [0.]], &array![[0.9], [0.1]], avg), -(0.9f64.ln())));
+
+        // Too few rows give NaN rather than a panic
+        let (empty, single) = (ndarray::Array2::<f64>::zeros((0, 1)), array![[1.]]);
+        assert!(adjusted_r2_score(&single, &single, 1, avg).is_nan());
+        assert!(adjusted_r2_score(&y_true, &y_pred, 5, avg).is_nan());
+        assert!(median_absolute_error(&empty, &empty, avg).is_nan());
+        assert!(root_mean_squared_error(&empty, &empty, avg).is_nan());
+        assert!(explained_variance_score(&empty, &empty, avg).is_nan());
+        assert!(roc_auc_score(&empty, &empty, avg).is_nan());
+        assert!(pr_auc_score(&empty, &empty, avg).is_nan());
+        assert!(confusion_matrix(&empty, &empty, 0).labels.is_empty());
+        assert!(close(median_absolute_error(&single, &array![[3.]], avg), 2.));
+    }
+
+    fn linear_data(rows: usize) -> (ndarray::Array2<f64>, ndarray::Array2<f64>) {
+        let x = ndarray::Array2::from_shape_fn((rows, 2), |(i, j)| ((i * (j + 3)) % 17) as f64);
+        let y = x.map_axis(ndarray::Axis(1), |row| 2. * row[0] - row[1] + 3.).insert_axis(ndarray::Axis(1));
+        (x, y)
+    }
+
+    #[test]
+    fn cross_validation_test() {
+        let (x, y) = linear_data(40);
+        let labels = ndarray::Array2::from_shape_fn((40, 1), |(i, _)| if i < 10 { 1. } else { 0. });
+
+        let folds = KFold::shuffled(4, Some(7)).split(&x, &y).unwrap();
+        assert_eq!(folds, KFold::shuffled(4, Some(7)).split(&x, &y).unwrap());
+        let mut tested: Vec<usize> = folds.iter().flat_map(|fold| fold.test.clone()).collect();
+        tested.sort_unstable();
+        assert_eq!(tested, (0..40).collect::<Vec<_>>());
+        assert_eq!(RepeatedKFold::new(4, 3, Some(1)).split(&x, &y).unwrap().len(), 12);
+        assert_eq!(LeaveOneOut.split(&x, &y).unwrap().len(), 40);
+
+        for fold in StratifiedKFold::shuffled(5, 0, Some(3)).split(&x, &labels).unwrap() {
+            assert_eq!(fold.test.iter().filter(|&&i| labels[(i, 0)] == 1.).count(), 2);
+        }
+
+        let groups: Vec<i64> = (0..40).map(|i| (i / 4) as i64).collect();
+        for fold in GroupKFold::new(3, groups.clone()).split(&x, &y).unwrap() {
+            assert!(fold.test.iter().all(|i| fold.train.iter().all(|j| groups[*i] != groups[*j])));
+        }
+
+        let mut splitter = TimeSeriesSplit::new(3);
+        splitter.gap = 2;
+        let folds = splitter.split(&x, &y).unwrap();
+        assert_eq!(folds[0].test, (10..20).collect::<Vec<_>>());
+        assert_eq!(folds[2].train, (0..28).collect::<Vec<_>>());
+
+        let factory = || {
+            LinearRegression::new(
+                TrainConfigBuilder::new()
+                    .epochs(500)
+                    .learning_rate(0.5)
+                    .regularization(Regularization::l2(0.))
+                    .build(),
+            )
+        };
+        let result = cross_validate(factory, &x, &y, &KFold::shuffled(4, Some(0)), &[Scorer::R2, Scorer::MeanAbsoluteError]).unwrap();
+        assert_eq!(result.folds.len(), 4);
+        assert_eq!(result.scorer_names, vec!["r2", "mae"]);
+        assert!(result.mean_score(0) > 0.99, "mean r2 {}", result.mean_score(0));
+    }
+
+    #[test]
+    fn split_variants_test() {
+        let (x, y) = linear_data(40);
+        // The unseeded split only has deterministic shapes
+        let (x_train, y_train, x_test, y_test) = train_test_split(x.clone(), y.clone(), 0.7).unwrap();
+        assert_eq!((x_train.nrows(), y_train.nrows(), x_test.nrows(), y_test.nrows()), (28, 28, 12, 12));
+        let labels = ndarray::Array2::from_shape_fn((40, 1), |(i, _)| if i < 10 { 1. } else { 0. });
+
+        let (_, y_train, _, y_test) = train_test_split_with(x.clone(), labels.clone(), TestSize::Fraction(0.2), &SplitStrategy::Stratified(0), Some(1)).unwrap();
+        assert_eq!((y_train.nrows(), y_test.nrows()), (32, 8));
+        assert_eq!(y_test.iter().filter(|&&v| v == 1.).count(), 2);
+
+        let (train, test) = split_indices(&y, TestSize::Count(10), &SplitStrategy::Chronological, None).unwrap();
+        assert_eq!(test, (30..40).collect::<Vec<_>>());
+        assert_eq!(train, (0..30).collect::<Vec<_>>());
+
+        let strategy = SplitStrategy::Grouped((0..40).map(|i| (i / 3) as i64).collect());
+        let (train, test) = split_indices(&y, TestSize::Fraction(0.25), &strategy, Some(4)).unwrap();
+        assert!(test.len() >= 10);
+        assert!(train.iter().all(|i| test.iter().all(|j| i / 3 != j / 3)));
+        assert_eq!(split_indices(&y, TestSize::Fraction(0.25), &strategy, Some(4)).unwrap().1, test);
+
+        let (x_train, _, x_val, _, x_test, y_test) = train_val_test_split(x, y, TestSize::Count(8), TestSize::Fraction(0.1), &SplitStrategy::Shuffle, Some(2)).unwrap();
+        assert_eq!((x_train.nrows(), x_val.nrows(), x_test.nrows(), y_test.nrows()), (28, 8, 4, 4));
+        assert!(TestSize::Count(40).resolve(40).is_err());
+    }
+
+    #[test]
+    fn inference_test() {
+        assert!((crate::stats::students_t_ppf(0.975, 10.) - 2.228_138_852).abs() < 1e-6);
+        assert!((crate::stats::students_t_cdf(1., 1.) - 0.75).abs() < 1e-10);
+        assert!((crate::stats::f_sf(3., 2., 20.) - 1.3_f64.powi(-10)).abs() < 1e-10);
+
+        let (x, mut y) = linear_data(50);
+        y.iter_mut().enumerate().for_each(|(i, v)| *v += 0.3 * (i as f64 * 12.9898).sin());
+        let config = TrainConfigBuilder::new().epochs(3000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+        let model = train(x.clone(), y.clone(), config);
+        let summary_config = SummaryConfigBuilder::new().feature_names(vec!["a".to_string(), "b".to_string()]).build();
+        let summary = &model.summary(&x, &y, &summary_config).unwrap()[0];
+
+        // Closed-form OLS on the raw design
+        let mut design = ndarray::Array2::<f64>::ones((50, 3));
+        design.slice_mut(ndarray::s![.., 1..]).assign(&x);
+        let xtx_inv = crate::matrix_operations::matrix_inverse(&design.t().dot(&design)).unwrap();
+        let beta = xtx_inv.dot(&design.t().dot(&y));
+        let residuals = &y - &design.dot(&beta);
+        let sigma2 = residuals.iter().map(|e| e * e).sum::<f64>() / 47.;
+        for (i, c) in summary.coefficients.iter().enumerate() {
+            assert!((c.estimate - beta[(i, 0)]).abs() < 1e-6, "{} {}", c.estimate, beta[(i, 0)]);
+            assert!((c.std_error - (sigma2 * xtx_inv[(i, i)]).sqrt()).abs() < 1e-6);
+            assert!(c.ci_lower < c.estimate && c.estimate < c.ci_upper);
+        }
+        assert_eq!(summary.coefficients[1].name, "a");
+        assert!((summary.df_resid - 47.).abs() < 1e-9);
+        assert!(summary.r2 > 0.99 && summary.f_p_value < 1e-10);
+        assert!(summary.coefficients[1].p_value < 1e-10);
+
+        let robust = SummaryConfigBuilder::new().covariance_type(CovarianceType::HC3).build();
+        assert!(model.summary(&x, &y, &robust).unwrap()[0].coefficients.iter().all(|c| c.std_error.is_finite()));
+        let ridge = train(x.clone(), y.clone(), TrainConfigBuilder::new().epochs(500).learning_rate(0.5).regularization(Regularization::l2(0.01)).build());
+        assert!(ridge.summary(&x, &y, &SummaryConfig::default()).unwrap()[0].df_model < 2.);
+        let lasso = train(x.clone(), y.clone(), TrainConfigBuilder::new().epochs(10).regularization(Regularization::l1(0.1)).build());
+        assert!(lasso.summary(&x, &y, &SummaryConfig::default()).is_err());
+
+        // Estimates come from the normal equations, not from how far training got
+        let short = train(x.clone(), y.clone(), TrainConfigBuilder::new().epochs(2).regularization(Regularization::l2(0.)).build());
+        for (c, exact) in short.summary(&x, &y, &SummaryConfig::default()).unwrap()[0].coefficients.iter().zip(beta.column(0)) {
+            assert!((c.estimate - exact).abs() < 1e-6, "{} {}", c.estimate, exact);
+        }
+        let default_config = train(x.clone(), y.clone(), TrainConfigBuilder::new().epochs(2).build());
+        assert!(default_config.summary(&x, &y, &SummaryConfig::default()).unwrap_err().to_string().contains("ElasticNet(1, 1)"));
+
+        // Other losses and weighted fits minimize something else, so their summaries would be the OLS ones
+        let huber = train(x.clone(), y.clone(), TrainConfigBuilder::new().epochs(2).regularization(Regularization::l2(0.)).loss(LossFn::huber(1.)).build());
+        assert!(matches!(huber.get_loss(), Some(LossFn::Huber(HuberLoss { delta })) if *delta == 1.));
+        assert!(huber.summary(&x, &y, &SummaryConfig::default()).is_err());
+        assert!(huber.prediction_interval(&x, &y, &x, 0.9).is_err());
+        let weighted = train_weighted(x.clone(), y.clone(), ndarray::Array1::ones(50), TrainConfigBuilder::new().epochs(2).regularization(Regularization::l2(0.)).build());
+        assert!(weighted.is_weighted());
+        assert!(weighted.summary(&x, &y, &SummaryConfig::default()).is_err());
+        assert!(weighted.prediction_interval(&x, &y, &x, 0.9).is_err());
+    }
+
+    #[test]
+    fn intervals_test() {
+        let (x, mut y) = linear_data(200);
+        y.iter_mut().enumerate().for_each(|(i, v)| *v += 2. * (i as f64 * 12.9898).sin());
+        let ols = || TrainConfigBuilder::new().epochs(2000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+
+        let quantile_config = TrainConfigBuilder::new().epochs(3000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+        let quantile_model = train_quantiles(x.clone(), y.clone(), &[0.9, 0.1, 0.5], quantile_config).unwrap();
+        assert_eq!(quantile_model.quantiles(), &[0.1, 0.5, 0.9]);
+        let predictions = quantile_model.predict(&x);
+        for (q, prediction) in quantile_model.quantiles().iter().zip(predictions.iter()) {
+            let below = y.iter().zip(prediction.iter()).filter(|(t, p)| t <= p).count() as f64 / 200.;
+            assert!((below - q).abs() < 0.08, "quantile {} covers {}", q, below);
+        }
+        assert!(predictions[0].iter().zip(predictions[2].iter()).all(|(low, high)| low <= high));
+        let box_cox = TrainConfigBuilder::new().epochs(1).normalization(NormalizationParameterType::PowerParameter(PowerMethod::BoxCox)).build();
+        assert!(train_quantiles(x.clone(), y.clone(), &[0.5], box_cox).is_err()); // `x` contains zeros
+
+        let model = train(x.clone(), y.clone(), ols());
+        let interval = model.prediction_interval(&x, &y, &x, 0.9).unwrap();
+        // Bounded sine noise has lighter tails than the Gaussian the interval assumes
+        assert!(interval.coverage(&y) >= 0.85, "coverage {}", interval.coverage(&y));
+        assert!(interval.width().iter().all(|w| *w > 0.));
+        let ridge = train(x.clone(), y.clone(), TrainConfigBuilder::new().epochs(10).regularization(Regularization::l2(0.1)).build());
+        assert!(ridge.prediction_interval(&x, &y, &x, 0.9).is_err());
+
+        let conformal = split_conformal(
+            |x, y| {
+                let model = train(x, y, ols());
+                move |input: &ndarray::Array2<f64>| model.predict(input)
+            },
+            &x,
+            &y,
+            TestSize::Fraction(0.5),
+            0.8,
+            Some(3),
+        )
+        .unwrap();
+        assert!(conformal.half_width()[0].is_finite());
+        assert!(conformal.predict_interval(&x).coverage(&y) >= 0.7);
+    }
+
+    #[test]
+    fn polynomial_features_test() {
+        let mut poly = PolynomialFeatures::new(2, false, true);
+        assert_eq!(poly.fit_transform(&array![[2., 3.]]).unwrap(), array![[1., 2., 3., 4., 6., 9.]]);
+        let names = poly.feature_names(&["age".to_string(), "income".to_string()]).unwrap();
+        assert_eq!(names, ["1", "age", "income", "age^2", "age income", "income^2"]);
+        assert!(poly.transform(&array![[1., 2., 3.]]).is_err());
+
+        let mut interactions = PolynomialFeatures::new(3, true, false);
+        let expanded = interactions.fit_transform(&array![[1., 2., 3.]]).unwrap();
+        assert_eq!(expanded, array![[1., 2., 3., 2., 3., 6., 6.]]);
+        assert_eq!(interactions.n_output_features(), 7);
+
+        // A quadratic target becomes linear in the expanded features
+        let (x, _) = linear_data(60);
+        let y = x.map_axis(ndarray::Axis(1), |row| row[0] * row[0] - 2. * row[0] * row[1] + 1.).insert_axis(ndarray::Axis(1));
+        let mut poly = PolynomialFeatures::new(2, false, false);
+        let expanded = poly.fit_transform(&x).unwrap();
+        let config = TrainConfigBuilder::new().epochs(3000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+        let model = train(expanded.clone(), y.clone(), config);
+        assert!(r2_score(&y, &model.predict(&expanded), MultiOutput::UniformAverage) > 0.99);
+    }
+
+    #[test]
+    fn spline_and_binning_test() {
+        let x = ndarray::Array2::from_shape_fn((60, 1), |(i, _)| i as f64 / 10.);
+        let y = x.mapv(f64::sin);
+
+        let mut spline = SplineTransformer::cubic(6);
+        let basis = spline.fit_transform(&x).unwrap();
+        assert_eq!(basis.ncols(), 8);
+        assert!(basis.rows().into_iter().all(|row| (row.sum() - 1.).abs() < 1e-9));
+        let config = || TrainConfigBuilder::new().epochs(4000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+        let model = train(basis.clone(), y.clone(), config());
+        assert!(r2_score(&y, &model.predict(&basis), MultiOutput::UniformAverage) > 0.95);
+        assert_eq!(spline.transform(&array![[-5.]]).unwrap(), spline.transform(&array![[0.]]).unwrap());
+
+        let mut natural = SplineTransformer::new(4, SplineBasis::NaturalCubic, KnotStrategy::Quantile);
+        natural.fit(&x).unwrap();
+        assert_eq!(natural.feature_names(&["t".to_string()]).unwrap(), ["t_sp0", "t_sp1", "t_sp2"]);
+        // Linear beyond the boundary knots: equal steps give equal differences
+        let beyond = natural.transform(&array![[7.], [8.], [9.]]).unwrap();
+        let (d1, d2) = (&beyond.row(1) - &beyond.row(0), &beyond.row(2) - &beyond.row(1));
+        assert!(d1.iter().zip(d2.iter()).all(|(a, b)| (a - b).abs() < 1e-9));
+
+        let mut ordinal = KBinsDiscretizer::new(4, BinStrategy::Uniform, BinEncoding::Ordinal);
+        let bins = ordinal.fit_transform(&array![[0.], [1.], [2.], [3.9], [4.]]).unwrap();
+        assert_eq!(bins.column(0).to_vec(), vec![0., 1., 2., 3., 3.]);
+        assert_eq!(ordinal.transform(&array![[-1.], [9.]]).unwrap().column(0).to_vec(), vec![0., 3.]);
+
+        let clustered = array![[0., 1.], [0.1, 2.], [0.2, 3.], [10., 4.], [10.1, 5.], [20., 6.]];
+        let mut onehot = KBinsDiscretizer::new(3, BinStrategy::KMeans, BinEncoding::OneHot);
+        let encoded = onehot.fit_transform(&clustered).unwrap();
+        assert_eq!(encoded.ncols(), 6);
+        assert!(encoded.rows().into_iter().all(|row| row.sum() == 2.));
+        assert_eq!(encoded.column(0).to_vec(), vec![1., 1., 1., 0., 0., 0.]);
+        assert_eq!(encoded.column(2).to_vec(), vec![0., 0., 0., 0., 0., 1.]);
+
+        let mut quantile_bins = KBinsDiscretizer::new(2, BinStrategy::Quantile, BinEncoding::Ordinal);
+        let halves = quantile_bins.fit_transform(&x).unwrap();
+        assert_eq!(halves.iter().filter(|&&b| b == 1.).count(), 30);
+
+        // Knots and edges need rows and finite values, otherwise they would be NaN
+        let empty = ndarray::Array2::<f64>::zeros((0, 2));
+        let infinite = array![[0.], [1.], [f64::INFINITY]];
+        assert!(SplineTransformer::cubic(4).fit(&empty).is_err());
+        assert!(SplineTransformer::cubic(3).fit(&infinite).is_err());
+        assert!(KBinsDiscretizer::new(2, BinStrategy::Uniform, BinEncoding::Ordinal).fit(&empty).is_err());
+        assert!(KBinsDiscretizer::new(2, BinStrategy::Uniform, BinEncoding::Ordinal).fit(&infinite).is_err());
+    }
+
+    #[test]
+    fn scalers_test() {
+        let x = ndarray::Array2::from_shape_fn((50, 2), |(i, j)| ((i + 1) as f64 * 0.37 + j as f64).exp().powf(0.3));
+        let close = |a: &ndarray::Array2<f64>, b: &ndarray::Array2<f64>| a.iter().zip(b.iter()).all(|(u, v)| (u - v).abs() < 1e-6 * v.abs().max(1.));
+
+        let mut scalers: Vec<Box<dyn Scaler>> = vec![
+            Box::new(StandardScaler::new()),
+            Box::new(MinMaxScaler::new()),
+            Box::new(MaxAbsScaler::new()),
+            Box::new(RobustScaler::new()),
+            Box::new(QuantileTransformer::new(100, QuantileOutput::Uniform)),
+            Box::new(QuantileTransformer::new(100, QuantileOutput::Normal)),
+            Box::new(PowerTransformer::box_cox()),
+            Box::new(PowerTransformer::yeo_johnson()),
+        ];
+        for scaler in scalers.iter_mut() {
+            let transformed = scaler.fit_transform(&x).unwrap();
+            assert!(close(&scaler.inverse_transform(&transformed).unwrap(), &x));
+            assert!(scaler.transform(&array![[1.]]).is_err());
+        }
+
+        let standardized = scalers[0].transform(&x).unwrap();
+        assert!(standardized.mean_axis(ndarray::Axis(0)).unwrap().iter().all(|m| m.abs() < 1e-9));
+        let uniform = scalers[4].transform(&x).unwrap();
+        assert!((uniform[(0, 0)]).abs() < 1e-12 && (uniform[(49, 1)] - 1.).abs() < 1e-12);
+        let robust = scalers[3].transform(&x).unwrap();
+        assert!(crate::stats::quantile(&robust.column(0).to_vec(), 0.5).abs() < 1e-12);
+        // Box-Cox lambda brings exp(0.111 i) close to linear in i, i.e. lambda near 0
+        let mut box_cox = PowerTransformer::new(PowerMethod::BoxCox, false);
+        box_cox.fit(&x.column(0).to_owned().insert_axis(ndarray::Axis(1))).unwrap();
+        assert!(box_cox.lambdas().unwrap()[0].abs() < 0.2, "lambda {}", box_cox.lambdas().unwrap()[0]);
+        assert!(PowerTransformer::box_cox().fit(&array![[1.], [-1.]]).is_err());
+
+        let mut normalizer = Normalizer::new(Norm::L2);
+        let unit = normalizer.fit_transform(&array![[3., 4.], [0., 0.]]).unwrap();
+        assert_eq!(unit, array![[0.6, 0.8], [0., 0.]]);
+        assert!(normalizer.inverse_transform(&unit).is_err());
+
+        let (x, y) = linear_data(40);
+        let config = TrainConfigBuilder::new()
+            .epochs(500)
+            .learning_rate(0.5)
+            .regularization(Regularization::l2(0.))
+            .normalization(NormalizationParameterType::RobustParameter)
+            .build();
+        let model = train(x.clone(), y.clone(), config);
+        assert!(r2_score(&y, &model.predict(&x), MultiOutput::UniformAverage) > 0.99);
+        let filepath = std::env::temp_dir().join("ferrite_model.json").to_string_lossy().to_string();
+        model.save(filepath.clone()).unwrap();
+        let loaded = crate::multivariate_regression::model::TrainedModel::load(filepath).unwrap();
+        assert_eq!(loaded.predict(&x), model.predict(&x));
+
+        let mut normalized = x.clone();
+        let normalization = normalize_data(&NormalizationParameterType::PowerParameter(PowerMethod::YeoJohnson), &mut normalized).unwrap();
+        denormalize_data(&mut normalized, &normalization).unwrap();
+        assert!(close(&normalized, &x));
+
+        // Zero columns fit and transform to zero columns
+        let mut no_columns = QuantileTransformer::new(10, QuantileOutput::Normal);
+        let empty = ndarray::Array2::<f64>::zeros((5, 0));
+        assert_eq!(no_columns.fit_transform(&empty).unwrap().dim(), (5, 0));
+        assert_eq!(no_columns.inverse_transform(&empty).unwrap().dim(), (5, 0));
+    }
+
+    #[test]
+    fn target_normalization_test() {
+        let data = RegressionConfigBuilder::new().n_samples(500).n_features(5).bias(40.).noise(5.).seed(8).build();
+        let (input, output, _) = make_regression(&data).unwrap();
+        let (x_train, y_train, x_test, y_test) = train_test_split_with(input, output, TestSize::Fraction(0.3), &SplitStrategy::Shuffle, Some(8)).unwrap();
+        let config = TrainConfigBuilder::new()
+            .epochs(300)
+            .learning_rate(0.1)
+            .regularization(Regularization::l2(0.))
+            .normalization(NormalizationParameterType::ZParameter)
+            .target_normalization(NormalizationParameterType::ZParameter)
+            .build();
+        let model = train(x_train, y_train, config);
+        assert!(model.get_target_normalization().is_some());
+        let y_pred = model.predict(&x_test);
+        assert!(r2_score(&y_test, &y_pred, MultiOutput::UniformAverage) > 0.95);
+
+        // Coefficients in original units do not depend on how the target was scaled
+        let (x, y) = linear_data(40);
+        let config = |target: Option<NormalizationParameterType>| {
+            let builder = TrainConfigBuilder::new().epochs(2000).learning_rate(0.5).regularization(Regularization::l2(0.));
+            match target {
+                Some(target) => builder.target_normalization(target).build(),
+                None => builder.build(),
+            }
+        };
+        let plain = train(x.clone(), y.clone(), config(None)).summary(&x, &y, &SummaryConfig::default()).unwrap();
+        let scaled_model = train(x.clone(), y.clone(), config(Some(NormalizationParameterType::MinMaxParameter)));
+        let scaled = scaled_model.summary(&x, &y, &SummaryConfig::default()).unwrap();
+        for (a, b) in plain[0].coefficients.iter().zip(scaled[0].coefficients.iter()) {
+            assert!((a.estimate - b.estimate).abs() < 1e-4, "{} {}", a.estimate, b.estimate);
+        }
+        assert!((scaled[0].coefficients[1].estimate - 2.).abs() < 1e-4);
+        let quantile_target = train(x.clone(), y.clone(), config(Some(NormalizationParameterType::QuantileParameter(QuantileOutput::Normal))));
+        assert!(quantile_target.predict(&x).iter().all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn search_test() {
+        let (x, y) = linear_data(40);
+        let splitter = KFold::shuffled(3, Some(0));
+        let grid = ParamGrid::new()
+            .base(TrainParams { epochs: 300, ..TrainParams::default() })
+            .learning_rate(vec![0.001, 0.5])
+            .regularization(vec![Regularization::l2(0.), Regularization::l2(1.)]);
+        assert_eq!(grid.candidates().len(), 4);
+
+        let result = SearchCV::new(&splitter, Scorer::R2).n_jobs(2).grid(&grid, &x, &y).unwrap();
+        assert_eq!(result.entries.len(), 4);
+        assert_eq!(result.best().rank, 1);
+        assert_eq!(result.best_params().lr, 0.5);
+        assert!(result.entries.windows(2).all(|w| w[0].mean_score >= w[1].mean_score));
+        let model = result.best_model.as_ref().unwrap();
+        assert!(r2_score(&y, &model.predict(&x), MultiOutput::UniformAverage) > 0.99);
+
+        let distributions = ParamDistributions::new()
+            .epochs(IntDistribution::Choice(vec![400]))
+            .learning_rate(FloatDistribution::LogUniform(1e-3, 1.))
+            .loss(vec![LossFn::SquaredError, LossFn::huber(1.)]);
+        let candidates = distributions.sample(6, Some(5)).unwrap();
+        assert!(candidates.iter().all(|p| p.lr >= 1e-3 && p.lr < 1.));
+        assert_eq!(distributions.sample(6, Some(5)).unwrap()[3].lr, candidates[3].lr);
+
+        let result = SearchCV::new(&splitter, Scorer::MeanAbsoluteError)
+            .halving(2, 50)
+            .refit(false)
+            .random(&distributions, 6, Some(5), &x, &y)
+            .unwrap();
+        assert_eq!(result.entries.len(), 6);
+        assert!(result.best_model.is_none());
+        assert_eq!(result.best().epochs_used, 400);
+        assert!(result.entries.iter().filter(|e| e.epochs_used == 400).count() < 6);
+    }
+
+    #[test]
+    fn pipeline_test() {
+        // y = 2 a - b + 3 with missing values in `a`; `c` is an unused noise column
+        let (x, y) = linear_data(60);
+        let noise = ndarray::Array2::from_shape_fn((60, 1), |(i, _)| (i % 5) as f64);
+        let mut raw = ndarray::concatenate(ndarray::Axis(1), &[x.view(), noise.view()]).unwrap();
+        raw[(3, 0)] = f64::NAN;
+        raw[(10, 0)] = f64::NAN;
+        let names: Vec<String> = ["a", "b", "c"].iter().map(|n| n.to_string()).collect();
+
+        let columns = ColumnTransformer::new(&names, Remainder::Drop)
+            .add("impute", SimpleImputer::new(ImputeStrategy::Mean, true), ["a"])
+            .add("scale", StandardScaler::new(), ["b"]);
+        let config = TrainConfigBuilder::new().epochs(2000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+        let mut pipeline = Pipeline::new(LinearRegression::new(config))
+            .step("columns", columns)
+            .step("poly", PolynomialFeatures::new(1, false, false));
+        assert!(pipeline.predict(&raw).is_err());
+        pipeline.fit(&raw, &y).unwrap();
+        assert_eq!(pipeline.feature_names(&names).unwrap(), vec!["a", "missing_a", "b"]);
+        assert_eq!(pipeline.transform(&raw).unwrap().ncols(), 3);
+        match pipeline.get_step("columns") {
+            Some(Step::Columns(columns)) => assert_eq!(columns.output_names().unwrap(), vec!["a", "missing_a", "b"]),
+            other => panic!("unexpected step {:?}", other),
+        }
+
+        let complete: Vec<usize> = (0..60).filter(|&i| i != 3 && i != 10).collect();
+        let x_complete = raw.select(ndarray::Axis(0), &complete);
+        let y_complete = y.select(ndarray::Axis(0), &complete);
+        let y_pred = pipeline.predict(&x_complete).unwrap();
+        assert!(r2_score(&y_complete, &y_pred, MultiOutput::UniformAverage) > 0.99);
+        assert!(pipeline.predict(&x).is_err());
+
+        // Steps and model round-trip through one file, including the imputer's fitted state
+        let filepath = std::env::temp_dir().join("ferrite_pipeline.json").to_string_lossy().to_string();
+        pipeline.save(filepath.clone()).unwrap();
+        let loaded: Pipeline = Pipeline::load(filepath.clone()).unwrap();
+        std::fs::remove_file(filepath).unwrap();
+        let difference = loaded.predict(&raw).unwrap() - pipeline.predict(&raw).unwrap();
+        assert!(difference.iter().all(|d| d.abs() < 1e-9));
+
+        // Remainder columns are passed through after the transformed ones
+        let mut passthrough = ColumnTransformer::new(&names, Remainder::Passthrough).add("knn", KnnImputer::new(2, false), [0usize]);
+        let transformed = passthrough.fit_transform(&raw).unwrap();
+        assert_eq!(passthrough.output_names().unwrap(), vec!["a", "b", "c"]);
+        assert!(transformed.iter().all(|v| v.is_finite()));
+        let knn_pipeline = Pipeline::new(LinearRegression::default()).step("columns", passthrough);
+        let json = serde_json::to_string(&knn_pipeline).unwrap();
+        let restored: Pipeline = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.transform(&raw).unwrap(), transformed);
+    }
+
+    #[test]
+    fn traits_test() {
+        let (x, y) = linear_data(40);
+        let config = || TrainConfigBuilder::new().epochs(2000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+
+        let mut estimator = LinearRegression::new(config());
+        assert!(Predict::predict(&estimator, &x).is_err());
+        estimator.fit(&x, &y).unwrap();
+        assert!(estimator.score(&x, &y).unwrap() > 0.999);
+        assert!(estimator.score(&x, &y.select(ndarray::Axis(0), &[0, 1])).is_err());
+        assert!(Predict::predict(&estimator, &x.slice(ndarray::s![.., ..1]).to_owned()).is_err());
+        let model = estimator.model().unwrap();
+        assert_eq!(Predict::predict(model, &x).unwrap(), model.predict(&x));
+
+        // Normalizations the data cannot support are errors, also inside cross-validation
+        let box_cox = || LinearRegression::new(TrainConfigBuilder::new().epochs(1).normalization(NormalizationParameterType::PowerParameter(PowerMethod::BoxCox)).build());
+        assert!(box_cox().fit(&x, &y).is_err()); // `x` contains zeros
+        let unit_target = TrainConfigBuilder::new().epochs(1).target_normalization(NormalizationParameterType::UnitNormParameter(Norm::L2)).build();
+        assert!(LinearRegression::new(unit_target).fit(&x, &y).is_err());
+        assert!(cross_validate(box_cox, &x, &y, &KFold::new(3), &[Scorer::R2]).is_err());
+        // Row-wise normalizations accept any width, so the model checks it before multiplying
+        let unit_input = TrainConfigBuilder::new().epochs(1).normalization(NormalizationParameterType::UnitNormParameter(Norm::L2)).build();
+        let mut unit_estimator = LinearRegression::new(unit_input);
+        unit_estimator.fit(&x, &y).unwrap();
+        assert!(Predict::predict(&unit_estimator, &ndarray::Array2::ones((3, 5))).is_err());
+
+        // Generic code only sees the traits
+        fn fit_score<E: Fit + Score>(mut estimator: E, x: &ndarray::Array2<f64>, y: &ndarray::Array2<f64>) -> f64 {
+            estimator.fit(x, y).unwrap();
+            estimator.score(x, y).unwrap()
+        }
+        let pipeline = Pipeline::new(LinearRegression::new(config())).step("scale", StandardScaler::new());
+        assert!(fit_score(pipeline, &x, &y) > 0.999);
+
+        let mut transformers: Vec<Box<dyn Transform>> = vec![
+            Box::new(SimpleImputer::mean()),
+            Box::new(PolynomialFeatures::new(2, false, false)),
+            Box::new(KBinsDiscretizer::new(3, BinStrategy::Uniform, BinEncoding::Ordinal)),
+            Box::new(MinMaxScaler::new()),
+        ];
+        let widths: Vec<usize> = transformers.iter_mut().map(|t| t.fit_transform(&x).unwrap().ncols()).collect();
+        assert_eq!(widths, vec![2, 5, 2, 2]);
+        let mut scaler = RobustScaler::new();
+        let scaled = scaler.fit_transform(&x).unwrap();
+        assert!((scaler.inverse_transform(&scaled).unwrap() - &x).iter().all(|d| d.abs() < 1e-9));
+
+        let filepath = std::env::temp_dir().join("ferrite_estimator.json").to_string_lossy().to_string();
+        estimator.save(filepath.clone()).unwrap();
+        let loaded = LinearRegression::load(filepath.clone()).unwrap();
+        std::fs::remove_file(filepath).unwrap();
+        let difference = Predict::predict(&loaded, &x).unwrap() - Predict::predict(&estimator, &x).unwrap();
+        assert!(difference.iter().all(|d| d.abs() < 1e-9));
+        assert_eq!(loaded.config().epochs, 2000);
+    }
+
+    #[test]
+    fn feature_selection_test() {
+        // y = 2 a - b + 3; `constant` carries nothing, `double_a` repeats `a`, `noise` is unrelated
+        let (x, y) = linear_data(80);
+        let extra = ndarray::Array2::from_shape_fn((80, 3), |(i, j)| match j {
+            0 => 1.,
+            1 => 2. * x[(i, 0)] + 1.,
+            _ => ((i * 7919) % 23) as f64,
+        });
+        let data = ndarray::concatenate(ndarray::Axis(1), &[x.view(), extra.view()]).unwrap();
+        let dataset = Dataset::new(["a", "b", "constant", "double_a", "noise"].iter().map(|n| n.to_string()).collect(), data).unwrap();
+        let (names, data) = (dataset.column_names(), dataset.data());
+
+        let mut variance = VarianceThreshold::default();
+        assert_eq!(variance.fit_transform(data).unwrap().ncols(), 4);
+        assert_eq!(variance.feature_names(names).unwrap(), vec!["a", "b", "double_a", "noise"]);
+        assert_eq!(variance.variances().unwrap()[2], 0.);
+        assert!(VarianceThreshold::new(1e6).fit(data).is_err());
+
+        let mut correlation = CorrelationFilter::new(0.95);
+        correlation.fit(data).unwrap();
+        assert_eq!(correlation.feature_names(names).unwrap(), vec!["a", "b", "constant", "noise"]);
+
+        let candidates = data.select(ndarray::Axis(1), &[0, 1, 4]);
+        let candidate_names: Vec<String> = ["a", "b", "noise"].iter().map(|n| n.to_string()).collect();
+        let mut f_test = UnivariateSelector::k_best(ScoreFunction::FRegression, 2);
+        assert_eq!(f_test.fit_transform(&candidates, &y).unwrap().ncols(), 2);
+        assert_eq!(f_test.feature_names(&candidate_names).unwrap(), vec!["a", "b"]);
+        let p_values = f_test.p_values().unwrap();
+        assert!(p_values[0] < 1e-6 && p_values[2] > 1e-3, "{:?}", p_values);
+        let mut mutual_information = UnivariateSelector::k_best(ScoreFunction::MutualInformation(3), 2);
+        mutual_information.fit(&candidates, &y).unwrap();
+        assert_eq!(mutual_information.support().unwrap(), &[0, 1]);
+        assert!(mutual_information.p_values().is_none());
+        let mut threshold = UnivariateSelector::correlation_threshold(0.5);
+        threshold.fit(&candidates, &y).unwrap();
+        assert_eq!(threshold.support().unwrap(), &[0]);
+
+        let config = || TrainConfigBuilder::new().epochs(1000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+        let mut rfe = Rfe::new(config(), 2, 1);
+        rfe.fit(&candidates, &y).unwrap();
+        assert_eq!(rfe.feature_names(&candidate_names).unwrap(), vec!["a", "b"]);
+        assert_eq!(rfe.ranking().unwrap(), &[1, 1, 2]);
+        // Every supervised selector is usable through the same trait
+        fn select_two(selector: &mut dyn SupervisedTransform, x: &ndarray::Array2<f64>, y: &ndarray::Array2<f64>) -> ndarray::Array2<f64> {
+            selector.fit_transform(x, y).unwrap()
+        }
+        assert_eq!(select_two(&mut rfe, &candidates, &y), candidates.select(ndarray::Axis(1), &[0, 1]));
+        assert!(SupervisedTransform::transform(&Rfe::new(config(), 2, 1), &candidates).is_err());
+
+        let l1_config = TrainConfigBuilder::new().epochs(2000).learning_rate(0.1).regularization(Regularization::l1(0.5)).build();
+        let mut lasso = SelectFromModel::new(l1_config, 0.2);
+        lasso.fit(&candidates, &y).unwrap();
+        let importances = lasso.importances().unwrap();
+        assert!(importances[2] < 0.2 && importances[0] > 1., "{:?}", importances);
+        assert_eq!(lasso.support().unwrap(), &[0, 1]);
+        // An exact least-squares fit gives the unrelated column a weight of about zero
+        let model = train(candidates.clone(), y.clone(), config());
+        assert_eq!(SelectFromModel::prefit(&model, 1e-3).unwrap().support().unwrap(), &[0, 1]);
+
+        // Supervised selectors see the targets inside a pipeline, names stay traceable
+        let mut pipeline = Pipeline::new(LinearRegression::new(config()))
+            .step("variance", VarianceThreshold::default())
+            .step("select", UnivariateSelector::k_best(ScoreFunction::FRegression, 2));
+        pipeline.fit(data, &y).unwrap();
+        assert_eq!(pipeline.feature_names(names).unwrap(), vec!["a", "double_a"]);
+        assert!(pipeline.score(data, &y).unwrap() > 0.5);
+        assert!(Step::from(UnivariateSelector::k_best(ScoreFunction::Correlation, 1)).fit(data).is_err());
+    }
+
+    fn max_abs_difference(a: &ndarray::Array2<f64>, b: &ndarray::Array2<f64>) -> f64 {
+        (a - b).iter().fold(0., |acc: f64, d| acc.max(d.abs()))
+    }
+
+    #[test]
+    fn decomposition_test() {
+        use ndarray_rand::rand_distr::StandardNormal;
+        use ndarray_rand::RandomExt;
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+
+        // Kernels
+        let a = ndarray::Array2::<f64>::random_using((7, 4), StandardNormal, &mut rng);
+        let (q, r) = qr_decomposition(&a).unwrap();
+        assert!(max_abs_difference(&matrix_mul(&q, &r), &a) < 1e-10);
+        assert!(max_abs_difference(&matrix_mul(&q.t().to_owned(), &q), &ndarray::Array2::eye(4)) < 1e-10);
+        assert!((1..4).all(|i| (0..i).all(|j| r[(i, j)] == 0.)));
+        for m in [a.clone(), a.t().to_owned()] {
+            let (u, singular_values, vt) = svd(&m).unwrap();
+            let reconstructed = matrix_mul(&(&u * &singular_values.view().insert_axis(ndarray::Axis(0))), &vt);
+            assert!(max_abs_difference(&reconstructed, &m) < 1e-10);
+            assert!(singular_values.windows(2).into_iter().all(|w| w[0] >= w[1]));
+        }
+        let gram = matrix_mul(&a.t().to_owned(), &a);
+        let (eigenvalues, eigenvectors) = symmetric_eigen(&gram).unwrap();
+        let singular_values = svd(&a).unwrap().1;
+        assert!(eigenvalues.iter().zip(singular_values.iter()).all(|(l, s)| (l - s * s).abs() < 1e-9));
+        let diagonal = ndarray::Array2::from_diag(&eigenvalues);
+        let rebuilt = matrix_mul(&matrix_mul(&eigenvectors, &diagonal), &eigenvectors.t().to_owned());
+        assert!(max_abs_difference(&rebuilt, &gram) < 1e-9);
+        assert!(symmetric_eigen(&a).is_err());
+        let (_, approximate, _) = randomized_svd(&a, 2, 2, 4, Some(1)).unwrap();
+        assert!((approximate[0] - singular_values[0]).abs() < 1e-6);
+        let mut broken = a.clone();
+        broken[(1, 2)] = f64::NAN;
+        assert_eq!(svd(&broken).unwrap_err(), MatrixError::NonFinite);
+        assert_eq!(qr_decomposition(&broken).unwrap_err(), MatrixError::NonFinite);
+        assert!(symmetric_eigen(&ndarray::Array2::from_elem((2, 2), f64::INFINITY)).is_err());
+
+        // Three correlated columns spanning essentially two directions
+        let t = ndarray::Array2::<f64>::random_using((200, 2), StandardNormal, &mut rng);
+        let noise = ndarray::Array2::<f64>::random_using((200, 1), StandardNormal, &mut rng) * 0.01;
+        let x = ndarray::Array2::from_shape_fn((200, 3), |(i, j)| match j {
+            0 => 3. * t[(i, 0)] + 5.,
+            1 => 2. * t[(i, 0)] - t[(i, 1)],
+            _ => t[(i, 1)] + noise[(i, 0)],
+        });
+
+        let mut full = Pca::with_components(NComponents::All);
+        let projected = full.fit_transform(&x).unwrap();
+        let ratio = full.explained_variance_ratio().unwrap();
+        assert!((ratio.sum() - 1.).abs() < 1e-9);
+        assert!(ratio[2] < 1e-4);
+        assert!(max_abs_difference(&full.inverse_transform(&projected).unwrap(), &x) < 1e-9);
+
+        let mut two = Pca::with_components(NComponents::VarianceRatio(0.99));
+        two.fit(&x).unwrap();
+        assert_eq!(two.feature_names().unwrap(), vec!["pc0", "pc1"]);
+        assert!(max_abs_difference(&two.inverse_transform(&two.transform(&x).unwrap()).unwrap(), &x) < 0.05);
+
+        let mut whitened = Pca::new(2).whiten(true);
+        let white = whitened.fit_transform(&x).unwrap();
+        let variances = white.var_axis(ndarray::Axis(0), 1.);
+        assert!(variances.iter().all(|v| (v - 1.).abs() < 1e-9), "{:?}", variances);
+        assert!(max_abs_difference(&whitened.inverse_transform(&white).unwrap(), &x) < 0.05);
+
+        let mut randomized = Pca::new(2).solver(SvdSolver::randomized(Some(3)));
+        randomized.fit(&x).unwrap();
+        let difference = randomized.singular_values().unwrap() - &two.singular_values().unwrap().view();
+        assert!(difference.iter().all(|d| d.abs() < 1e-6));
+        assert!(max_abs_difference(randomized.components().unwrap(), two.components().unwrap()) < 1e-6);
+        assert!(Pca::with_components(NComponents::VarianceRatio(0.9)).solver(SvdSolver::randomized(None)).fit(&x).is_err());
+
+        // Batches give the same components as one pass
+        let mut incremental = IncrementalPca::new(2).batch_size(30);
+        incremental.fit(&x).unwrap();
+        assert_eq!(incremental.n_samples_seen(), 200);
+        assert!(max_abs_difference(incremental.components().unwrap(), two.components().unwrap()) < 1e-6);
+        let difference = incremental.explained_variance_ratio().unwrap() - &two.explained_variance_ratio().unwrap().view();
+        assert!(difference.iter().all(|d| d.abs() < 1e-9));
+        assert!(max_abs_difference(&incremental.transform(&x).unwrap(), &two.transform(&x).unwrap()) < 1e-6);
+
+        // Uncentered decomposition of count-like data
+        let counts = ndarray::Array2::from_shape_fn((40, 6), |(i, j)| if (i + j) % 3 == 0 { ((i * j) % 5) as f64 } else { 0. });
+        let mut exact = TruncatedSvd::new(3).solver(SvdSolver::Full);
+        let mut fast = TruncatedSvd::new(3).solver(SvdSolver::randomized(Some(0)));
+        let reduced = exact.fit_transform(&counts).unwrap();
+        fast.fit(&counts).unwrap();
+        assert_eq!(reduced.dim(), (40, 3));
+        let difference = fast.singular_values().unwrap() - &exact.singular_values().unwrap().view();
+        assert!(difference.iter().all(|d| d.abs() < 1e-6));
+        assert!(exact.explained_variance_ratio().unwrap().sum() <= 1. + 1e-9);
+        assert_eq!(exact.inverse_transform(&reduced).unwrap().dim(), (40, 6));
+        let sparse_counts = CsrMatrix::from_dense(&counts);
+        let mut sparse = TruncatedSvd::new(3).solver(SvdSolver::randomized(Some(0)));
+        sparse.fit_sparse(&sparse_counts).unwrap();
+        assert!(max_abs_difference(sparse.components().unwrap(), fast.components().unwrap()) < 1e-9);
+        assert!(max_abs_difference(&sparse.transform_sparse(&sparse_counts).unwrap(), &fast.transform(&counts).unwrap()) < 1e-9);
+        let difference = sparse.explained_variance_ratio().unwrap() - &fast.explained_variance_ratio().unwrap().view();
+        assert!(difference.iter().all(|d| d.abs() < 1e-9));
+        assert!(exact.fit_sparse(&sparse_counts).is_err());
+
+        // In front of a regression
+        let y = x.column(0).to_owned().insert_axis(ndarray::Axis(1));
+        let config = TrainConfigBuilder::new().epochs(1000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+        let mut pipeline = Pipeline::new(LinearRegression::new(config)).step("pca", Pca::new(2));
+        pipeline.fit(&x, &y).unwrap();
+        assert_eq!(pipeline.feature_names(&["a".into(), "b".into(), "c".into()]).unwrap(), vec!["pc0", "pc1"]);
+        assert!(pipeline.score(&x, &y).unwrap() > 0.99);
+    }
+
+    #[test]
+    fn linear_algebra_test() {
+        let a = array![[2.0, 1.0, 0.5], [1.0, 3.0, 0.0], [0.0, -1.0, 4.0], [1.5, 0.0, 1.0]];
+        let b = array![[1.0, 0.0], [2.0, 1.0], [0.0, 3.0], [1.0, 1.0]];
+
+        // GEMM reads transposes through strides and accumulates into the output
+        let mut c = ndarray::Array2::<f64>::ones((3, 2));
+        gemm(2.0, &a, Transpose::Yes, &b, Transpose::No, 0.5, &mut c).unwrap();
+        let expected = 2.0 * matrix_mul(&a.t().to_owned(), &b) + 0.5;
+        assert!(max_abs_difference(&c, &expected) < 1e-12);
+        let mut y = ndarray::Array1::<f64>::zeros(4);
+        gemv(1.0, &a, Transpose::No, &array![1.0, -1.0, 2.0], 0.0, &mut y).unwrap();
+        assert_eq!(y, array![2.0, -2.0, 9.0, 3.5]);
+        assert!(matches!(try_matrix_mul(&a, &a), Err(MatrixError::IncompatibleShapes { .. })));
+
+        // Cholesky of the Gram matrix
+        let gram = matrix_mul(&a.t(), &a);
+        let l = cholesky(&gram).unwrap();
+        assert!(max_abs_difference(&matrix_mul(&l, &l.t()), &gram) < 1e-10);
+        let rhs = array![[1.0], [2.0], [3.0]];
+        let solved = cholesky_solve(&l, &rhs).unwrap();
+        assert!(max_abs_difference(&matrix_mul(&gram, &solved), &rhs) < 1e-10);
+        assert!(matches!(cholesky(&array![[1.0, 2.0], [2.0, 1.0]]), Err(MatrixError::NotPositiveDefinite)));
+
+        // LU with pivoting
+        let square = array![[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]];
+        let lu = lu_decomposition(&square).unwrap();
+        let permuted = square.select(ndarray::Axis(0), lu.pivots());
+        assert!(max_abs_difference(&matrix_mul(&lu.l(), &lu.u()), &permuted) < 1e-12);
+        assert!((lu.determinant() - (-5.0)).abs() < 1e-12);
+        assert!(max_abs_difference(&lu.inverse(), &matrix_inverse(&square).unwrap()) < 1e-12);
+        assert!(matches!(lu_decomposition(&array![[1.0, 2.0], [2.0, 4.0]]), Err(MatrixError::Singular)));
+        assert!(matches!(matrix_inverse(&a), Err(MatrixError::NotSquare((4, 3)))));
+
+        // Triangular solve and least squares agree with the normal equations
+        let upper = array![[2.0, 1.0], [0.0, 4.0]];
+        assert_eq!(solve_triangular(&upper, &array![[4.0], [8.0]], false).unwrap(), array![[1.0], [2.0]]);
+        let coefficients = least_squares(&a, &b).unwrap();
+        let normal = cholesky_solve(&l, &matrix_mul(&a.t(), &b)).unwrap();
+        assert!(max_abs_difference(&coefficients, &normal) < 1e-10);
+    }
+
+    #[test]
+    fn sparse_test() {
+        let dense = array![[0.0, 2.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [3.0, 0.0, -1.0]];
+        let csr = CsrMatrix::from_dense(&dense);
+        assert_eq!((csr.nnz(), csr.indptr(), csr.indices()), (4, &[0, 1, 2, 2, 4][..], &[1, 0, 0, 2][..]));
+        assert_eq!(csr.to_dense(), dense);
+        let csc = csr.to_csc();
+        assert_eq!(csc, CscMatrix::from_dense(&dense));
+        assert_eq!((csc.to_dense(), csc.column(0)), (dense.clone(), (&[1, 3][..], &[1.0, 3.0][..])));
+        assert_eq!(csr.clone().transpose().to_dense(), dense.t());
+        assert_eq!(CsrMatrix::from_triplets((4, 3), vec![(3, 2, -1.0), (0, 1, 1.5), (3, 0, 3.0), (0, 1, 0.5), (1, 0, 1.0)]).unwrap(), csr);
+        assert!(matches!(CsrMatrix::new((2, 2), vec![0, 1], vec![0], vec![1.0]), Err(MatrixError::InvalidSparse(_))));
+        assert!(matches!(CsrMatrix::new((1, 2), vec![0, 2], vec![1, 0], vec![1.0, 1.0]), Err(MatrixError::InvalidSparse(_))));
+
+        // Products in both directions agree with the dense kernels
+        let b = array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        let tall = array![[1.0], [2.0], [3.0], [4.0]];
+        assert_eq!(csr.multiply(Transpose::No, &b.view()).unwrap(), matrix_mul(&dense, &b));
+        assert_eq!(csr.multiply(Transpose::Yes, &tall.view()).unwrap(), matrix_mul(&dense.t(), &tall));
+        assert_eq!(csc.multiply(Transpose::No, &b.view()).unwrap(), matrix_mul(&dense, &b));
+        assert_eq!(csc.multiply(Transpose::Yes, &tall.view()).unwrap(), matrix_mul(&dense.t(), &tall));
+        let mut c = ndarray::Array2::<f64>::ones((4, 2));
+        csr.mul_into(2.0, Transpose::No, &b.view(), 1.0, &mut c.view_mut()).unwrap();
+        assert_eq!(c, 2.0 * matrix_mul(&dense, &b) + 1.0);
+        assert!(matches!(csr.multiply(Transpose::Yes, &b.view()), Err(MatrixError::IncompatibleShapes { .. })));
+        assert!(matches!(CsrMatrix::new((2, 2), vec![0, 5, 2], vec![0, 1], vec![1., 1.]), Err(MatrixError::InvalidSparse(_))));
+        assert_eq!(csr.slice_rows(1..4).to_dense(), dense.slice(ndarray::s![1..4, ..]));
+        assert_eq!(csc.slice_rows(3..4).to_dense(), dense.slice(ndarray::s![3..4, ..]));
+        assert_eq!(csr.prepend_ones().to_dense().column(0).to_vec(), vec![1.0; 4]);
+
+        // Training on sparse rows matches training on the same rows densified
+        let (x, y) = linear_data(40);
+        let sparse_x = CsrMatrix::from_dense(&x);
+        for optimizer in [UpdatationMethod::BGD, UpdatationMethod::MiniBatchGD, UpdatationMethod::SGD] {
+            let config = TrainConfigBuilder::new().epochs(1500).learning_rate(0.1).optimizer(optimizer).regularization(Regularization::l2(0.)).build();
+            let model = train_sparse(sparse_x.clone(), y.clone(), config).unwrap();
+            let predicted = model.predict_sparse(&sparse_x).unwrap();
+            assert!(max_abs_difference(&predicted, &model.predict(&x)) < 1e-9);
+            assert!(r2_score(&y, &predicted, MultiOutput::UniformAverage) > 0.99, "{:?}", optimizer);
+        }
+        let config = TrainConfigBuilder::new().epochs(10).normalization(NormalizationParameterType::MaxAbsParameter).build();
+        let mut estimator = LinearRegression::new(config);
+        estimator.fit_sparse(&sparse_x, &y).unwrap();
+        assert_eq!(estimator.predict_sparse(&sparse_x).unwrap().dim(), (40, 1));
+        let mut minmax = LinearRegression::new(TrainConfigBuilder::new().epochs(10).build());
+        minmax.fit(&(&x + 1.0), &y).unwrap(); // Columns no longer start at 0, so MinMax centers them
+        assert!(minmax.predict_sparse(&sparse_x).is_err());
+        // Unsupported settings are errors, not panics
+        let unit_target = TrainConfigBuilder::new().epochs(1).target_normalization(NormalizationParameterType::UnitNormParameter(Norm::L2)).build();
+        assert!(train_sparse(sparse_x.clone(), y.clone(), unit_target).is_err());
+        let minmax_input = TrainConfigBuilder::new().epochs(1).normalization(NormalizationParameterType::MinMaxParameter).build();
+        assert!(LinearRegression::new(minmax_input).fit_sparse(&sparse_x, &y).is_err());
+        assert!(train_sparse(sparse_x.clone(), y.slice(ndarray::s![..39, ..]).to_owned(), TrainConfigBuilder::new().build()).is_err());
+    }
+
+    #[test]
+    fn training_workspace_test() {
+        // Dense operators multiply in place without `dgemm` at every width; `multiply` uses it when wide
+        let a = ndarray::Array2::from_shape_fn((7, 5), |(i, j)| ((i * 5 + j * 3) % 11) as f64 - 5.);
+        for width in [1, 3, 4, 6, 9] {
+            let b = ndarray::Array2::from_shape_fn((5, width), |(i, j)| (i + 2 * j) as f64 * 0.5);
+            let c = ndarray::Array2::from_shape_fn((7, width), |(i, j)| (i * j) as f64);
+            assert!(max_abs_difference(&a.multiply(Transpose::No, &b.view()).unwrap(), &matrix_mul(&a, &b)) < 1e-12);
+            assert!(max_abs_difference(&a.multiply(Transpose::Yes, &c.view()).unwrap(), &matrix_mul(&a.t(), &c)) < 1e-12);
+            let mut in_place = ndarray::Array2::<f64>::ones((7, width));
+            a.mul_into(2., Transpose::No, &b.view(), 1., &mut in_place.view_mut()).unwrap();
+            assert!(max_abs_difference(&in_place, &(2. * matrix_mul(&a, &b) + 1.)) < 1e-12);
+            let mut in_place = ndarray::Array2::<f64>::zeros((5, width));
+            a.mul_into(1., Transpose::Yes, &c.view(), 0., &mut in_place.view_mut()).unwrap();
+            assert!(max_abs_difference(&in_place, &matrix_mul(&a.t(), &c)) < 1e-12);
+            let strided = c.t().to_owned();
+            a.mul_into(1., Transpose::Yes, &strided.t(), 0., &mut in_place.view_mut()).unwrap();
+            assert!(max_abs_difference(&in_place, &matrix_mul(&a.t(), &c)) < 1e-12);
+        }
+        let mut rows = ndarray::Array2::<f64>::zeros((3, 2));
+        let b = ndarray::Array2::from_shape_fn((5, 2), |(i, j)| (i + j) as f64);
+        a.mul_rows_into(2..5, 1., Transpose::No, &b.view(), 0., &mut rows.view_mut()).unwrap();
+        assert_eq!(rows, matrix_mul(&a.slice(ndarray::s![2..5, ..]), &b));
+
+        // One epoch with the workspace equals the allocating gradient on a design with an explicit bias column
+        let (x, y) = linear_data(42);
+        let design = PolynomialFeatures::prepend_bias(&x);
+        let grad = Gradient::new(LossFn::huber(1.), Regularization::elastic_net(0.01, 0.02));
+        let initial = ndarray::Array2::from_shape_fn((3, 1), |(i, _)| 0.1 * i as f64);
+        for (optimizer, batch_size) in [(UpdatationMethod::BGD, 42), (UpdatationMethod::MiniBatchGD, 16), (UpdatationMethod::SGD, 1)] {
+            let mut expected = initial.clone();
+            for start in (0..42).step_by(batch_size) {
+                let end = (start + batch_size).min(42);
+                let batch_ip = design.slice(ndarray::s![start..end, ..]).to_owned();
+                let batch_op = y.slice(ndarray::s![start..end, ..]).to_owned();
+                let pred = matrix_mul(&batch_ip, &expected);
+                expected = &expected - 0.01 * grad.calculate_gradient(&batch_ip, &pred, &batch_op, &expected, None);
+            }
+
+            let mut workspace = TrainingWorkspace::new();
+            let (mut dense, mut sparse) = (initial.clone(), initial.clone());
+            update_weight(&x, &y, None, &mut dense, &optimizer, &Some(MiniBatchSize::Medium), &grad, 0.01, false, &mut workspace);
+            update_weight(&CsrMatrix::from_dense(&x), &y, None, &mut sparse, &optimizer, &Some(MiniBatchSize::Medium), &grad, 0.01, false, &mut workspace);
+            assert!(max_abs_difference(&dense, &expected) < 1e-12, "{:?}", optimizer);
+            assert!(max_abs_difference(&sparse, &expected) < 1e-12, "{:?}", optimizer);
+        }
+
+        // Six outputs go through the wide kernel and still match
+        let y = ndarray::Array2::from_shape_fn((42, 6), |(i, j)| y[(i, 0)] * (j + 1) as f64);
+        let mut expected = ndarray::Array2::from_shape_fn((3, 6), |(i, j)| 0.1 * (i + j) as f64);
+        let mut weight = expected.clone();
+        for start in (0..42).step_by(16) {
+            let end = (start + 16).min(42);
+            let batch_ip = design.slice(ndarray::s![start..end, ..]).to_owned();
+            let batch_op = y.slice(ndarray::s![start..end, ..]).to_owned();
+            let pred = matrix_mul(&batch_ip, &expected);
+            expected = &expected - 0.01 * grad.calculate_gradient(&batch_ip, &pred, &batch_op, &expected, None);
+        }
+        let mut workspace = TrainingWorkspace::new();
+        update_weight(&x, &y, None, &mut weight, &UpdatationMethod::MiniBatchGD, &Some(MiniBatchSize::Medium), &grad, 0.01, false, &mut workspace);
+        assert!(max_abs_difference(&weight, &expected) < 1e-12);
+    }
+
+    #[test]
+    fn datasets_test() {
+        // Training recovers the coefficients and bias used to generate the data
+        let config = RegressionConfigBuilder::new().n_samples(200).n_features(4).n_informative(3).bias(5.).noise(0.1).seed(11).build();
+        let (x, y, coefficients) = make_regression(&config).unwrap();
+        assert_eq!(make_regression(&config).unwrap().0, x);
+        assert_eq!(coefficients.iter().filter(|&&c| c == 0.).count(), 1);
+        let model = train(x.clone(), y.clone(), TrainConfigBuilder::new().epochs(3000).learning_rate(0.5).regularization(Regularization::l2(0.)).build());
+        let summary = &model.summary(&x, &y, &SummaryConfig::default()).unwrap()[0];
+        assert!((summary.coefficients[0].estimate - 5.).abs() < 0.05, "bias {}", summary.coefficients[0].estimate);
+        for (coefficient, truth) in summary.coefficients[1..].iter().zip(coefficients.column(0)) {
+            assert!((coefficient.estimate - truth).abs() < 0.05, "{} {}", coefficient.estimate, truth);
+        }
+
+        let config = RegressionConfigBuilder::new().n_samples(4000).n_features(3).correlation(0.8).seed(2).build();
+        let (x, _, _) = make_regression(&config).unwrap();
+        let correlation = (&x.column(0) * &x.column(1)).mean().unwrap() / (x.column(0).std(0.) * x.column(1).std(0.));
+        assert!((correlation - 0.8).abs() < 0.05, "correlation {}", correlation);
+        let config = RegressionConfigBuilder::new().n_samples(500).n_features(20).density(0.1).seed(3).build();
+        let (x, _, _) = make_regression(&config).unwrap();
+        let density = CsrMatrix::from_dense(&x).nnz() as f64 / 10_000.;
+        assert!((density - 0.1).abs() < 0.02, "density {}", density);
+        assert!(make_regression(&RegressionConfigBuilder::new().n_features(2).n_informative(3).build()).is_err());
+
+        let config = ClassificationConfigBuilder::new().n_samples(90).n_features(6).n_informative(2).n_redundant(1).n_classes(3).flip_y(0.).seed(5).build();
+        let (x, labels) = make_classification(&config).unwrap();
+        assert_eq!(x.dim(), (90, 6));
+        assert_eq!((0..3).map(|k| labels.iter().filter(|&&l| l == k as f64).count()).collect::<Vec<_>>(), vec![30, 30, 30]);
+        let (informative, redundant) = (x.slice(ndarray::s![.., ..2]).to_owned(), x.slice(ndarray::s![.., 2..3]).to_owned());
+        let mixing = least_squares(&informative, &redundant).unwrap();
+        assert!(max_abs_difference(&informative.dot(&mixing), &redundant) < 1e-9, "redundant column is not a combination of the informative ones");
+        assert!(make_classification(&ClassificationConfigBuilder::new().n_informative(1).n_redundant(0).n_classes(3).build()).is_err());
+
+        let (x, labels, centers) = make_blobs(60, 2, Centers::Fixed(array![[0., 0.], [10., 10.]]), 0.5, Some(1)).unwrap();
+        assert_eq!(centers.nrows(), 2);
+        for (row, label) in x.rows().into_iter().zip(labels.iter()) {
+            assert!((&row - &centers.row(*label as usize)).mapv(f64::abs).iter().all(|&d| d < 3.));
+        }
+        assert_eq!(make_blobs(10, 3, Centers::Count(4), 1., Some(1)).unwrap().2.dim(), (4, 3));
+
+        let (x, labels) = make_moons(50, 0., Some(4));
+        for (row, label) in x.rows().into_iter().zip(labels.iter()) {
+            let center = if *label == 0. { (0., 0.) } else { (1., 0.5) };
+            assert!((((row[0] - center.0).powi(2) + (row[1] - center.1).powi(2)).sqrt() - 1.).abs() < 1e-12);
+        }
+        let (x, labels) = make_circles(40, 0.3, 0., Some(4)).unwrap();
+        for (row, label) in x.rows().into_iter().zip(labels.iter()) {
+            assert!((row[0].hypot(row[1]) - if *label == 0. { 1. } else { 0.3 }).abs() < 1e-12);
+        }
+
+        let (x, y) = make_friedman1(20, 7, 0., Some(6)).unwrap();
+        let expected = 10. * (std::f64::consts::PI * x[(0, 0)] * x[(0, 1)]).sin() + 20. * (x[(0, 2)] - 0.5).powi(2) + 10. * x[(0, 3)] + 5. * x[(0, 4)];
+        assert!((y[(0, 0)] - expected).abs() < 1e-12);
+        assert!(make_friedman1(20, 4, 0., None).is_err());
+        assert_eq!(make_friedman2(20, 0.1, Some(6)).0.ncols(), 4);
+        assert!(make_friedman3(20, 0., Some(6)).1.iter().all(|v| v.abs() < std::f64::consts::FRAC_PI_2));
+    }
+
+    #[test]
+    fn sample_weight_test() {
+        // A row of weight 2 counts like the same row included twice
+        let (x, mut y) = linear_data(30);
+        y.iter_mut().enumerate().for_each(|(i, v)| *v += (i as f64 * 1.7).sin());
+        let sample_weight = ndarray::Array1::from_shape_fn(30, |i| if i % 3 == 0 { 2. } else { 1. });
+        let repeated: Vec<usize> = (0..30).chain((0..30).step_by(3)).collect();
+        let (x_repeated, y_repeated) = (x.select(ndarray::Axis(0), &repeated), y.select(ndarray::Axis(0), &repeated));
+
+        let loss = LossFn::huber(0.5);
+        let regularization = Regularization::elastic_net(0.01, 0.02);
+        let weight = array![[0.5], [1.], [-0.5]];
+        let design = PolynomialFeatures::prepend_bias(&x);
+        let pred = design.dot(&weight);
+        let design_repeated = PolynomialFeatures::prepend_bias(&x_repeated);
+        let pred_repeated = design_repeated.dot(&weight);
+        let cost = loss.calculate_cost(&y, &pred, &regularization, &weight, Some(&sample_weight));
+        assert!((cost - loss.calculate_cost(&y_repeated, &pred_repeated, &regularization, &weight, None)).abs() < 1e-12);
+        let grad = Gradient::new(loss, regularization);
+        let gradient = grad.calculate_gradient(&design, &pred, &y, &weight, Some(&sample_weight));
+        assert!(max_abs_difference(&gradient, &grad.calculate_gradient(&design_repeated, &pred_repeated, &y_repeated, &weight, None)) < 1e-12);
+
+        // Weights start at random, so compare the converged fits
+        let config = || TrainConfigBuilder::new().epochs(3000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+        let weighted = train_weighted(x.clone(), y.clone(), sample_weight.clone(), config());
+        let repeated_model = train(x_repeated.clone(), y_repeated.clone(), config());
+        assert!(max_abs_difference(&weighted.predict(&x), &repeated_model.predict(&x)) < 1e-6);
+
+        // Minibatches weight their own rows and skip batches of zero weight
+        let mut batch_weight = sample_weight.clone();
+        batch_weight.slice_mut(ndarray::s![16..]).fill(0.);
+        let mut expected = weight.clone();
+        let batch_ip = design.slice(ndarray::s![..16, ..]).to_owned();
+        let batch_pred = batch_ip.dot(&expected);
+        let batch_sample_weight = batch_weight.slice(ndarray::s![..16]).to_owned();
+        expected = &expected - 0.01 * grad.calculate_gradient(&batch_ip, &batch_pred, &y.slice(ndarray::s![..16, ..]).to_owned(), &expected, Some(&batch_sample_weight));
+        let mut updated = weight.clone();
+        update_weight(&x, &y, Some(&batch_weight), &mut updated, &UpdatationMethod::MiniBatchGD, &Some(MiniBatchSize::Medium), &grad, 0.01, false, &mut TrainingWorkspace::new());
+        assert!(max_abs_difference(&updated, &expected) < 1e-12);
+
+        // Zero weights ignore corrupted rows, in training and in the closed-form solution
+        let (x, mut y) = linear_data(40);
+        y.slice_mut(ndarray::s![..10, ..]).mapv_inplace(|v| v + 50.);
+        let clean = ndarray::Array1::from_shape_fn(40, |i| if i < 10 { 0. } else { 1. });
+        let mut model = LinearRegression::new(TrainConfigBuilder::new().epochs(3000).learning_rate(0.5).regularization(Regularization::l2(0.)).build());
+        model.fit_weighted(&x, &y, &clean).unwrap();
+        let (_, y_clean) = linear_data(40);
+        assert!(max_abs_difference(&model.predict(&x).unwrap(), &y_clean) < 1e-3);
+        assert!(model.fit_weighted(&x, &y, &ndarray::Array1::zeros(40)).is_err());
+        assert!(try_train_weighted(x.clone(), y.clone(), ndarray::Array1::from_elem(40, -1.), TrainConfigBuilder::new().build()).is_err());
+        let beta = weighted_least_squares(&PolynomialFeatures::prepend_bias(&x), &y, &clean).unwrap();
+        assert!(max_abs_difference(&beta, &array![[3.], [2.], [-1.]]) < 1e-9);
+
+        // Weighted metrics equal the unweighted ones on repeated rows
+        let y_true = array![[1., 0.], [2., 1.], [4., 1.]];
+        let y_pred = array![[1.5, 0.], [2., 0.], [3., 1.]];
+        let weights = ndarray::array![2., 1., 1.];
+        let rows = [0, 0, 1, 2];
+        let (true_repeated, pred_repeated) = (y_true.select(ndarray::Axis(0), &rows), y_pred.select(ndarray::Axis(0), &rows));
+        let avg = MultiOutput::UniformAverage;
+        assert!((r2_score_weighted(&y_true, &y_pred, &weights, avg) - r2_score(&true_repeated, &pred_repeated, avg)).abs() < 1e-12);
+        assert!((root_mean_squared_error_weighted(&y_true, &y_pred, &weights, avg) - root_mean_squared_error(&true_repeated, &pred_repeated, avg)).abs() < 1e-12);
+        assert!((mean_absolute_error_weighted(&y_true, &y_pred, &weights, avg) - mean_absolute_error(&true_repeated, &pred_repeated, avg)).abs() < 1e-12);
+        assert!((mean_absolute_percentage_error_weighted(&y_true, &y_pred, &weights, MultiOutput::Output(0)) - mean_absolute_percentage_error(&true_repeated, &pred_repeated, MultiOutput::Output(0))).abs() < 1e-12);
+        assert!((accuracy_score_weighted(&y_true, &y_pred, &weights, MultiOutput::Output(1)) - 0.75).abs() < 1e-12);
+        let probabilities = array![[0.9], [0.2], [0.6]];
+        let labels = array![[1.], [0.], [0.]];
+        assert!((log_loss_weighted(&labels, &probabilities, &weights, avg) - log_loss(&labels.select(ndarray::Axis(0), &rows), &probabilities.select(ndarray::Axis(0), &rows), avg)).abs() < 1e-12);
+        assert!((median_absolute_error_weighted(&y_true, &y_pred, &weights, avg) - median_absolute_error(&true_repeated, &pred_repeated, avg)).abs() < 1e-12);
+        assert!((explained_variance_score_weighted(&y_true, &y_pred, &weights, MultiOutput::Output(0)) - explained_variance_score(&true_repeated, &pred_repeated, MultiOutput::Output(0))).abs() < 1e-12);
+        assert_eq!(max_error_weighted(&y_true, &y_pred, &array![0., 1., 1.], MultiOutput::Output(0)), 1.);
+
+        let classes = array![[0.], [1.], [2.], [1.], [0.], [2.]];
+        let predicted = array![[0.], [2.], [2.], [1.], [1.], [2.]];
+        let weights = ndarray::array![2., 1., 1., 3., 1., 1.];
+        let rows: Vec<usize> = weights.iter().enumerate().flat_map(|(i, &w)| std::iter::repeat_n(i, w as usize)).collect();
+        let (classes_repeated, predicted_repeated) = (classes.select(ndarray::Axis(0), &rows), predicted.select(ndarray::Axis(0), &rows));
+        for average in [Average::Binary(1.), Average::Micro, Average::Macro, Average::Weighted] {
+            assert!((precision_score_weighted(&classes, &predicted, &weights, average, avg) - precision_score(&classes_repeated, &predicted_repeated, average, avg)).abs() < 1e-12);
+            assert!((recall_score_weighted(&classes, &predicted, &weights, average, avg) - recall_score(&classes_repeated, &predicted_repeated, average, avg)).abs() < 1e-12);
+            assert!((f1_score_weighted(&classes, &predicted, &weights, average, avg) - f1_score(&classes_repeated, &predicted_repeated, average, avg)).abs() < 1e-12);
+        }
+        let counts = confusion_matrix(&classes_repeated, &predicted_repeated, 0);
+        let weighted = confusion_matrix_weighted(&classes, &predicted, &weights, 0);
+        assert_eq!((weighted.labels, weighted.matrix), (counts.labels, counts.matrix.mapv(|c| c as f64)));
+        let binary = array![[1.], [0.], [1.], [0.], [1.], [0.]];
+        let scores = array![[0.8], [0.8], [0.3], [0.1], [0.5], [0.6]];
+        let (binary_repeated, scores_repeated) = (binary.select(ndarray::Axis(0), &rows), scores.select(ndarray::Axis(0), &rows));
+        assert!((roc_auc_score_weighted(&binary, &scores, &weights, avg) - roc_auc_score(&binary_repeated, &scores_repeated, avg)).abs() < 1e-12);
+        assert!((pr_auc_score_weighted(&binary, &scores, &weights, avg) - pr_auc_score(&binary_repeated, &scores_repeated, avg)).abs() < 1e-12);
+
+        // Every scorer but `Custom` weights rows, also inside cross-validation
+        assert!((Scorer::MedianAbsoluteError.score_weighted(&y_true, &y_pred, &ndarray::array![2., 1., 1.]).unwrap() - median_absolute_error(&true_repeated, &pred_repeated, avg)).abs() < 1e-12);
+        let custom = Scorer::Custom { name: "mse", metric: |t, p| (t - p).mapv(|v| v * v).mean().unwrap(), greater_is_better: false };
+        assert!(custom.score_weighted(&y_true, &y_pred, &ndarray::array![2., 1., 1.]).is_none());
+        let factory = || LinearRegression::new(TrainConfigBuilder::new().epochs(3000).learning_rate(0.5).regularization(Regularization::l2(0.)).build());
+        let splitter = KFold::shuffled(4, Some(0));
+        let result = cross_validate_weighted(factory, &x, &y, &clean, &splitter, &[Scorer::R2, Scorer::MaxError]).unwrap();
+        assert!(result.mean_score(0) > 0.999, "weighted r2 {}", result.mean_score(0));
+        assert!(result.mean_score(1) < 1e-2, "weighted max error {}", result.mean_score(1));
+        assert!(cross_validate_weighted(factory, &x, &y, &clean, &splitter, &[custom]).is_err());
+
+        // Sparse training takes the same weights
+        let config = TrainConfigBuilder::new().epochs(3000).learning_rate(0.5).regularization(Regularization::l2(0.)).build();
+        let sparse_model = train_sparse_weighted(CsrMatrix::from_dense(&x), y.clone(), clean.clone(), config.clone()).unwrap();
+        assert!(max_abs_difference(&sparse_model.predict(&x), &y_clean) < 1e-3);
+        assert!(train_sparse_weighted(CsrMatrix::from_dense(&x), y.clone(), ndarray::Array1::zeros(40), config).is_err());
+    }
+
+    /// Under-prediction costs three times as much as over-prediction
+    #[derive(Debug)]
+    struct AsymmetricSquaredError;
+
+    impl Loss for AsymmetricSquaredError {
+        fn value(&self, y_true: f64, y_pred: f64, _: usize) -> f64 {
+            let scale = if y_pred < y_true { 3. } else { 1. };
+            0.5 * scale * (y_true - y_pred).powi(2)
+        }
+
+        fn derivative(&self, y_true: f64, y_pred: f64, _: usize) -> f64 {
+            let scale = if y_pred < y_true { 3. } else { 1. };
+            scale * (y_pred - y_true)
+        }
+    }
+
+    #[test]
+    fn loss_test() {
+        // Every built-in derivative matches a central difference of its value, away from the kinks
+        let losses: Vec<Box<dyn Loss>> = vec![
+            Box::new(SquaredError),
+            Box::new(AbsoluteError),
+            Box::new(HuberLoss { delta: 0.7 }),
+            Box::new(QuantileLoss::new(vec![0.2, 0.8]).unwrap()),
+            Box::new(LossFn::huber(0.7)),
+            Box::new(LossFn::custom(AsymmetricSquaredError)),
+        ];
+        for loss in &losses {
+            for (y_true, y_pred, column) in [(1., 0.2, 0), (1., 0.9, 1), (-2., 1.5, 1), (0.3, -0.1, 0)] {
+                let h = 1e-6;
+                let numeric = (loss.value(y_true, y_pred + h, column) - loss.value(y_true, y_pred - h, column)) / (2. * h);
+                assert!((numeric - loss.derivative(y_true, y_pred, column)).abs() < 1e-6, "{:?} at ({}, {})", loss, y_true, y_pred);
+            }
+        }
+        assert_eq!(QuantileLoss::new(vec![0.2, 0.8]).unwrap().value(1., 0., 1), 0.8);
+
+        // Quantiles are checked when built and when read back, so the loss never divides by zero
+        for quantiles in [vec![], vec![0.], vec![1.], vec![0.5, 1.5], vec![f64::NAN]] {
+            assert!(LossFn::quantile(quantiles.clone()).is_err(), "{:?}", quantiles);
+        }
+        assert!(train_quantiles(linear_data(10).0, linear_data(10).1, &[0.5, f64::NAN], TrainConfigBuilder::new().build()).is_err());
+        let json = serde_json::to_string(&LossFn::quantile(vec![0.5]).unwrap()).unwrap();
+        assert!(serde_json::from_str::<LossFn>(&json).is_ok());
+        assert!(serde_json::from_str::<LossFn>(&json.replace("0.5", "2.0")).is_err());
+        assert!(serde_json::from_str::<LossFn>(r#"{"Quantile":{"quantiles":[]}}"#).is_err());
+
+        // The logged cost is the mean of the loss values plus the penalty
+        let y_true = array![[1., 2.], [3., -1.]];
+        let y_pred = array![[1.5, 0.], [3., 1.]];
+        let weight = array![[0.], [1.]];
+        let gradient = Gradient::new(LossFn::huber(1.), Regularization::l2(0.5));
+        let values = [0.5 * 0.25, 2. - 0.5, 0., 2. - 0.5];
+        assert!((gradient.calculate_cost(&y_true, &y_pred, &weight, None) - (values.iter().sum::<f64>() / 4. + 0.5)).abs() < 1e-12);
+
+        // The gradient is the derivative of the logged cost, penalty included
+        let (x, y) = linear_data(12);
+        let design = PolynomialFeatures::prepend_bias(&x);
+        let y = ndarray::concatenate![ndarray::Axis(1), y, y.mapv(|v| 0.5 * v - 1.)];
+        let weight = array![[0.3, -0.2], [1.1, 0.4], [-0.6, 0.7]];
+        let sample_weight = ndarray::Array1::from_shape_fn(12, |i| 1. + (i % 3) as f64);
+        for loss in [LossFn::SquaredError, LossFn::huber(0.8), LossFn::quantile(vec![0.3, 0.7]).unwrap()] {
+            let grad = Gradient::new(loss, Regularization::elastic_net(0.05, 0.1));
+            for rows_weight in [None, Some(&sample_weight)] {
+                let cost = |w: &ndarray::Array2<f64>| grad.calculate_cost(&y, &design.dot(w), w, rows_weight);
+                let analytic = grad.calculate_gradient(&design, &design.dot(&weight), &y, &weight, rows_weight);
+                for ((i, j), &g) in analytic.indexed_iter() {
+                    let h = 1e-6;
+                    let (mut plus, mut minus) = (weight.clone(), weight.clone());
+                    plus[(i, j)] += h;
+                    minus[(i, j)] -= h;
+                    let numeric = (cost(&plus) - cost(&minus)) / (2. * h);
+                    assert!((numeric - g).abs() < 1e-5, "{:?} at ({}, {}): {} vs {}", grad.loss(), i, j, numeric, g);
+                }
+            }
+        }
+
+        // A custom loss trains through the same loop; the asymmetric one shifts predictions upwards
+        let (x, mut y) = linear_data(60);
+        y.iter_mut().enumerate().for_each(|(i, v)| *v += if i % 2 == 0 { 1. } else { -1. });
+        let config = |loss: LossFn| TrainConfigBuilder::new().epochs(2000).learning_rate(0.3).regularization(Regularization::l2(0.)).loss(loss).build();
+        let symmetric = train(x.clone(), y.clone(), config(LossFn::SquaredError));
+        let asymmetric = train(x.clone(), y.clone(), config(LossFn::custom(AsymmetricSquaredError)));
+        let bias = |model: &crate::multivariate_regression::model::TrainedModel| (&model.predict(&x) - &y).mean().unwrap();
+        assert!(bias(&symmetric).abs() < 0.05, "{}", bias(&symmetric));
+        assert!(bias(&asymmetric) > 0.4, "{}", bias(&asymmetric));
+
+        // Built-in losses persist with the configuration, custom ones cannot be saved
+        let huber = config(LossFn::huber(0.25));
+        let restored: crate::multivariate_regression::training::train_config::TrainConfig = serde_json::from_str(&serde_json::to_string(&huber).unwrap()).unwrap();
+        assert!(matches!(restored.loss, Some(LossFn::Huber(HuberLoss { delta })) if delta == 0.25));
+        assert!(serde_json::to_string(&config(LossFn::custom(AsymmetricSquaredError))).is_err());
     }
-}
\ No newline at end of file
+}
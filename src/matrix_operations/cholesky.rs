@@ -0,0 +1,39 @@
+use ndarray::Array2;
+use crate::matrix_operations::{solve_triangular, MatrixError};
+
+/// Cholesky factorization `A = L Lᵀ` of a symmetric positive definite matrix.
+///
+/// Returns the lower triangular `L`, or `NotPositiveDefinite` when a pivot is not positive.
+pub fn cholesky(a: &Array2<f64>) -> Result<Array2<f64>, MatrixError> {
+    let n = a.nrows();
+    if a.ncols() != n {
+        return Err(MatrixError::NotSquare(a.dim()));
+    }
+
+    let mut l = Array2::<f64>::zeros((n, n));
+    for j in 0..n {
+        let mut diagonal = a[(j, j)];
+        for k in 0..j {
+            diagonal -= l[(j, k)] * l[(j, k)];
+        }
+        if diagonal <= 0.0 || diagonal.is_nan() {
+            return Err(MatrixError::NotPositiveDefinite);
+        }
+        let pivot = diagonal.sqrt();
+        l[(j, j)] = pivot;
+        for i in j + 1..n {
+            let mut value = a[(i, j)];
+            for k in 0..j {
+                value -= l[(i, k)] * l[(j, k)];
+            }
+            l[(i, j)] = value / pivot;
+        }
+    }
+    Ok(l)
+}
+
+/// Solves `A X = B` given the Cholesky factor `L` of `A`
+pub fn cholesky_solve(l: &Array2<f64>, b: &Array2<f64>) -> Result<Array2<f64>, MatrixError> {
+    let y = solve_triangular(l, b, true)?;
+    solve_triangular(&l.t().to_owned(), &y, false)
+}
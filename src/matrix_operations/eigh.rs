@@ -0,0 +1,78 @@
+use ndarray::{Array1, Array2, Axis};
+use crate::matrix_operations::MatrixError;
+
+/// Jacobi sweeps after which `symmetric_eigen` gives up
+const MAX_SWEEPS: usize = 100;
+
+/// Eigendecomposition of a symmetric matrix by the cyclic Jacobi method.
+///
+/// Returns `(eigenvalues, eigenvectors)` with the eigenvalues in descending order and the
+/// matching unit eigenvectors as columns, so that `A = V diag(w) Vᵀ`. Only the upper
+/// triangle is trusted to be symmetric up to rounding; an error is returned otherwise, as
+/// well as for NaN or infinite entries and when the off-diagonal mass is still above
+/// tolerance after the last sweep.
+pub fn symmetric_eigen(a: &Array2<f64>) -> Result<(Array1<f64>, Array2<f64>), MatrixError> {
+    let (n, m) = a.dim();
+    if n != m {
+        return Err(MatrixError::NotSquare((n, m)));
+    }
+    if a.iter().any(|x| !x.is_finite()) {
+        return Err(MatrixError::NonFinite);
+    }
+    let scale = a.iter().fold(0.0_f64, |acc, &x| acc.max(x.abs()));
+    for i in 0..n {
+        for j in i + 1..n {
+            if (a[(i, j)] - a[(j, i)]).abs() > 1e-8 * scale.max(1.0) {
+                return Err(MatrixError::NotSymmetric);
+            }
+        }
+    }
+
+    let mut d = a.clone();
+    let mut v = Array2::<f64>::eye(n);
+    let converged = |d: &Array2<f64>| {
+        let off_diagonal: f64 = (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))).map(|(i, j)| d[(i, j)].powi(2)).sum();
+        off_diagonal.sqrt() <= f64::EPSILON * scale * n as f64
+    };
+    for _sweep in 0..MAX_SWEEPS {
+        if converged(&d) {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if d[(p, q)].abs() <= f64::MIN_POSITIVE {
+                    continue;
+                }
+                // Rotation angle zeroing d[p, q]
+                let theta = (d[(q, q)] - d[(p, p)]) / (2.0 * d[(p, q)]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (dkp, dkq) = (d[(k, p)], d[(k, q)]);
+                    d[(k, p)] = c * dkp - s * dkq;
+                    d[(k, q)] = s * dkp + c * dkq;
+                }
+                for k in 0..n {
+                    let (dpk, dqk) = (d[(p, k)], d[(q, k)]);
+                    d[(p, k)] = c * dpk - s * dqk;
+                    d[(q, k)] = s * dpk + c * dqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                    v[(k, p)] = c * vkp - s * vkq;
+                    v[(k, q)] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    if !converged(&d) {
+        return Err(MatrixError::NotConverged { operation: "Symmetric eigendecomposition", sweeps: MAX_SWEEPS });
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| d[(j, j)].total_cmp(&d[(i, i)]));
+    let eigenvalues = order.iter().map(|&i| d[(i, i)]).collect();
+    Ok((eigenvalues, v.select(Axis(1), &order)))
+}
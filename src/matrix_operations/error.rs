@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::fmt;
+
+/// Failure of a matrix kernel or factorization
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatrixError {
+    IncompatibleShapes {
+        operation: &'static str,
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    NotSquare((usize, usize)),
+    NotSymmetric,
+    Singular,              // Zero pivot or rank deficiency
+    NotPositiveDefinite,   // Non-positive pivot in a Cholesky factorization
+    InvalidSparse(String), // Inconsistent compressed sparse arrays
+    NonFinite,             // NaN or infinite entry in the input
+    NotConverged {
+        operation: &'static str,
+        sweeps: usize,
+    },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::IncompatibleShapes { operation, left, right } => write!(
+                f,
+                "Incompatible shapes for {}: {}x{} and {}x{}",
+                operation, left.0, left.1, right.0, right.1
+            ),
+            MatrixError::NotSquare((rows, cols)) => write!(f, "Expected a square matrix, got {}x{}", rows, cols),
+            MatrixError::NotSymmetric => write!(f, "Matrix is not symmetric"),
+            MatrixError::Singular => write!(f, "Matrix is singular"),
+            MatrixError::NotPositiveDefinite => write!(f, "Matrix is not positive definite"),
+            MatrixError::InvalidSparse(reason) => write!(f, "Invalid sparse matrix: {}", reason),
+            MatrixError::NonFinite => write!(f, "Matrix contains NaN or infinite values"),
+            MatrixError::NotConverged { operation, sweeps } => {
+                write!(f, "{} did not converge after {} sweeps", operation, sweeps)
+            }
+        }
+    }
+}
+
+impl Error for MatrixError {}
@@ -0,0 +1,50 @@
+use ndarray::Array2;
+use crate::matrix_operations::MatrixError;
+
+/// Inverts a square matrix by Gauss-Jordan elimination with partial pivoting.
+///
+/// Returns an error when the matrix is not square or is numerically singular.
+pub fn matrix_inverse(a: &Array2<f64>) -> Result<Array2<f64>, MatrixError> {
+    let (n, m) = a.dim();
+    if n != m {
+        return Err(MatrixError::NotSquare((n, m)));
+    }
+
+    let mut lhs = a.clone();
+    let mut inverse = Array2::<f64>::eye(n);
+    let tolerance = f64::EPSILON * n as f64 * a.iter().fold(0.0_f64, |acc, &x| acc.max(x.abs()));
+
+    for col in 0..n {
+        // Row with the largest pivot candidate in this column
+        let pivot = (col..n)
+            .max_by(|&i, &j| lhs[(i, col)].abs().total_cmp(&lhs[(j, col)].abs()))
+            .unwrap_or(col);
+        if lhs[(pivot, col)].abs() <= tolerance {
+            return Err(MatrixError::Singular);
+        }
+        if pivot != col {
+            for k in 0..n {
+                lhs.swap((pivot, k), (col, k));
+                inverse.swap((pivot, k), (col, k));
+            }
+        }
+
+        let scale = lhs[(col, col)];
+        for k in 0..n {
+            lhs[(col, k)] /= scale;
+            inverse[(col, k)] /= scale;
+        }
+        for row in 0..n {
+            let factor = lhs[(row, col)];
+            if row == col || factor == 0.0 {
+                continue;
+            }
+            for k in 0..n {
+                lhs[(row, k)] -= factor * lhs[(col, k)];
+                inverse[(row, k)] -= factor * inverse[(col, k)];
+            }
+        }
+    }
+
+    Ok(inverse)
+}
@@ -0,0 +1,108 @@
+use ndarray::{s, Array2};
+use crate::matrix_operations::MatrixError;
+
+/// LU factorization with partial pivoting, `P A = L U`
+///
+/// `L` (unit diagonal, below the diagonal) and `U` (on and above it) share one matrix.
+#[derive(Debug, Clone)]
+pub struct LuDecomposition {
+    lu: Array2<f64>,
+    pivots: Vec<usize>, // Row of `A` moved to position i
+    sign: f64,          // Sign of the row permutation
+}
+
+/// Factorizes a square matrix; returns `Singular` when a pivot is numerically zero.
+pub fn lu_decomposition(a: &Array2<f64>) -> Result<LuDecomposition, MatrixError> {
+    let n = a.nrows();
+    if a.ncols() != n {
+        return Err(MatrixError::NotSquare(a.dim()));
+    }
+    let tolerance = f64::EPSILON * n as f64 * a.iter().fold(0.0_f64, |acc, &x| acc.max(x.abs()));
+
+    let mut lu = a.clone();
+    let mut pivots: Vec<usize> = (0..n).collect();
+    let mut sign = 1.0;
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| lu[(i, col)].abs().total_cmp(&lu[(j, col)].abs()))
+            .unwrap_or(col);
+        if lu[(pivot, col)].abs() <= tolerance {
+            return Err(MatrixError::Singular);
+        }
+        if pivot != col {
+            for k in 0..n {
+                lu.swap((pivot, k), (col, k));
+            }
+            pivots.swap(pivot, col);
+            sign = -sign;
+        }
+        for row in col + 1..n {
+            let factor = lu[(row, col)] / lu[(col, col)];
+            lu[(row, col)] = factor;
+            if factor != 0.0 {
+                let (mut target, source) = lu.multi_slice_mut((s![row, col + 1..], s![col, col + 1..]));
+                target.scaled_add(-factor, &source);
+            }
+        }
+    }
+    Ok(LuDecomposition { lu, pivots, sign })
+}
+
+impl LuDecomposition {
+    /// Solves `A X = B`
+    pub fn solve(&self, b: &Array2<f64>) -> Result<Array2<f64>, MatrixError> {
+        let n = self.lu.nrows();
+        if b.nrows() != n {
+            return Err(MatrixError::IncompatibleShapes { operation: "LU solve", left: self.lu.dim(), right: b.dim() });
+        }
+        let mut x = b.select(ndarray::Axis(0), &self.pivots);
+        // Forward substitution with the unit lower triangle, then back substitution with U
+        for i in 0..n {
+            for j in 0..i {
+                let factor = self.lu[(i, j)];
+                let (mut row_i, row_j) = x.multi_slice_mut((s![i, ..], s![j, ..]));
+                row_i.scaled_add(-factor, &row_j);
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let factor = self.lu[(i, j)];
+                let (mut row_i, row_j) = x.multi_slice_mut((s![i, ..], s![j, ..]));
+                row_i.scaled_add(-factor, &row_j);
+            }
+            let diagonal = self.lu[(i, i)];
+            x.row_mut(i).mapv_inplace(|v| v / diagonal);
+        }
+        Ok(x)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.sign * self.lu.diag().product()
+    }
+
+    pub fn inverse(&self) -> Array2<f64> {
+        let n = self.lu.nrows();
+        self.solve(&Array2::eye(n)).expect("Identity has the factorized shape")
+    }
+
+    /// Unit lower triangular factor
+    pub fn l(&self) -> Array2<f64> {
+        let n = self.lu.nrows();
+        Array2::from_shape_fn((n, n), |(i, j)| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => self.lu[(i, j)],
+            std::cmp::Ordering::Equal => 1.0,
+            std::cmp::Ordering::Less => 0.0,
+        })
+    }
+
+    /// Upper triangular factor
+    pub fn u(&self) -> Array2<f64> {
+        let n = self.lu.nrows();
+        Array2::from_shape_fn((n, n), |(i, j)| if i <= j { self.lu[(i, j)] } else { 0.0 })
+    }
+
+    /// Row of `A` at every position of `P A`
+    pub fn pivots(&self) -> &[usize] {
+        &self.pivots
+    }
+}
@@ -1,39 +1,131 @@
-use matrixmultiply::dgemm;
-use ndarray::{Array2, ArrayViewMut2}; // Double-precision (f64) General Matrix Multiply
+use matrixmultiply::dgemm; // Double-precision (f64) General Matrix Multiply
+use ndarray::{Array2, ArrayBase, Data, DataMut, Ix1, Ix2};
+use crate::matrix_operations::MatrixError;
 
-/// Multiplies two 2D matrices using `matrixmultiply` for high performance.
-pub fn matrix_mul(a: &Array2<f64>, b: &Array2<f64>) -> Array2<f64> {
-    let (m, k) = a.dim();
-    let (k_b, n) = b.dim();
+/// Whether an operand of `gemm`/`gemv` is used as stored or transposed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transpose {
+    No,
+    Yes,
+}
 
-    assert_eq!(k, k_b, "Matrix dimensions do not match for multiplication!");
+/// Shape and strides of `op(a)`; transposing only swaps the strides, nothing is copied
+fn operand<S: Data<Elem = f64>>(a: &ArrayBase<S, Ix2>, trans: Transpose) -> (usize, usize, isize, isize) {
+    let (rows, cols) = a.dim();
+    let (rs, cs) = (a.strides()[0], a.strides()[1]);
+    match trans {
+        Transpose::No => (rows, cols, rs, cs),
+        Transpose::Yes => (cols, rows, cs, rs),
+    }
+}
 
-    // Ensure A and B are in standard layout (row-major)
-    let a_view = a.as_standard_layout(); // Ensures contiguous row-major layout
-    let b_view = b.as_standard_layout(); // Ensures contiguous row-major layout
+/// Function to compute `C = alpha * op(A) * op(B) + beta * C` in place
+///
+/// Any memory layout is accepted (views, slices, `.t()`), the strides are passed to
+/// `dgemm` directly. With `beta = 0` the previous content of `C` is ignored.
+///
+/// # Parameters:
+/// - `alpha: f64` - Scale of the product
+/// - `a: &ArrayBase<_, Ix2>`, `trans_a: Transpose` - Left operand
+/// - `b: &ArrayBase<_, Ix2>`, `trans_b: Transpose` - Right operand
+/// - `beta: f64` - Scale of the existing `C`
+/// - `c: &mut ArrayBase<_, Ix2>` - Output buffer of shape `rows(op(A)) x cols(op(B))`
+///
+/// # Returns:
+/// - `Result<(), MatrixError>` - `IncompatibleShapes` when the shapes do not line up
+pub fn gemm<S1, S2, S3>(
+    alpha: f64,
+    a: &ArrayBase<S1, Ix2>,
+    trans_a: Transpose,
+    b: &ArrayBase<S2, Ix2>,
+    trans_b: Transpose,
+    beta: f64,
+    c: &mut ArrayBase<S3, Ix2>,
+) -> Result<(), MatrixError>
+where
+    S1: Data<Elem = f64>,
+    S2: Data<Elem = f64>,
+    S3: DataMut<Elem = f64>,
+{
+    let (m, k, rsa, csa) = operand(a, trans_a);
+    let (k_b, n, rsb, csb) = operand(b, trans_b);
+    if k != k_b {
+        return Err(MatrixError::IncompatibleShapes { operation: "gemm", left: (m, k), right: (k_b, n) });
+    }
+    if c.dim() != (m, n) {
+        return Err(MatrixError::IncompatibleShapes { operation: "gemm output", left: (m, n), right: c.dim() });
+    }
+    if m == 0 || n == 0 {
+        return Ok(());
+    }
+    let (rsc, csc) = (c.strides()[0], c.strides()[1]);
 
-    let mut c = Array2::<f64>::zeros((m, n)); // Output matrix
+    // Safety: the shapes were checked above and every pointer/stride pair comes from a live array
+    unsafe {
+        dgemm(m, k, n, alpha, a.as_ptr(), rsa, csa, b.as_ptr(), rsb, csb, beta, c.as_mut_ptr(), rsc, csc);
+    }
+    Ok(())
+}
 
-    let mut c_view: ArrayViewMut2<f64> = c.view_mut();
+/// Function to compute `y = alpha * op(A) * x + beta * y` in place
+///
+/// # Parameters:
+/// - `alpha: f64` - Scale of the product
+/// - `a: &ArrayBase<_, Ix2>`, `trans_a: Transpose` - Matrix operand
+/// - `x: &ArrayBase<_, Ix1>` - Vector of length `cols(op(A))`
+/// - `beta: f64` - Scale of the existing `y`
+/// - `y: &mut ArrayBase<_, Ix1>` - Output vector of length `rows(op(A))`
+///
+/// # Returns:
+/// - `Result<(), MatrixError>` - `IncompatibleShapes` when the lengths do not line up
+pub fn gemv<S1, S2, S3>(
+    alpha: f64,
+    a: &ArrayBase<S1, Ix2>,
+    trans_a: Transpose,
+    x: &ArrayBase<S2, Ix1>,
+    beta: f64,
+    y: &mut ArrayBase<S3, Ix1>,
+) -> Result<(), MatrixError>
+where
+    S1: Data<Elem = f64>,
+    S2: Data<Elem = f64>,
+    S3: DataMut<Elem = f64>,
+{
+    let (m, k, rsa, csa) = operand(a, trans_a);
+    if x.len() != k || y.len() != m {
+        return Err(MatrixError::IncompatibleShapes { operation: "gemv", left: (m, k), right: (x.len(), y.len()) });
+    }
+    if m == 0 {
+        return Ok(());
+    }
+    let (rsx, rsy) = (x.strides()[0], y.strides()[0]);
 
+    // Safety: as in `gemm`, with the vectors seen as single-column matrices
     unsafe {
-        dgemm(
-            m,
-            k,
-            n,
-            1.0, // α (scaling factor for A * B)
-            a_view.as_ptr(),
-            a_view.strides()[0], // Row stride of A (rsA)
-            a_view.strides()[1], // Column stride of A (csA)
-            b_view.as_ptr(),
-            b_view.strides()[0], // Row stride of B (rsB)
-            b_view.strides()[1], // Column stride of B (csB)
-            0.0,                 // β (scaling factor for C; 0 means initialize)
-            c_view.as_mut_ptr(),
-            c_view.strides()[0], // Row stride of C (rsC)
-            c_view.strides()[1], // Column stride of C (csC)
-        );
-    }
-
-    c
+        dgemm(m, k, 1, alpha, a.as_ptr(), rsa, csa, x.as_ptr(), rsx, 1, beta, y.as_mut_ptr(), rsy, 1);
+    }
+    Ok(())
+}
+
+/// Multiplies two 2D matrices, returning `IncompatibleShapes` instead of panicking
+pub fn try_matrix_mul<S1, S2>(a: &ArrayBase<S1, Ix2>, b: &ArrayBase<S2, Ix2>) -> Result<Array2<f64>, MatrixError>
+where
+    S1: Data<Elem = f64>,
+    S2: Data<Elem = f64>,
+{
+    let mut c = Array2::<f64>::zeros((a.nrows(), b.ncols())); // Output matrix
+    gemm(1.0, a, Transpose::No, b, Transpose::No, 0.0, &mut c)?;
+    Ok(c)
+}
+
+/// Multiplies two 2D matrices using `matrixmultiply` for high performance.
+///
+/// Accepts arrays and views of any layout, so `matrix_mul(&x.t(), &y)` does not copy `x`.
+/// Panics when the inner dimensions differ, see `try_matrix_mul`.
+pub fn matrix_mul<S1, S2>(a: &ArrayBase<S1, Ix2>, b: &ArrayBase<S2, Ix2>) -> Array2<f64>
+where
+    S1: Data<Elem = f64>,
+    S2: Data<Elem = f64>,
+{
+    try_matrix_mul(a, b).unwrap_or_else(|e| panic!("Matrix dimensions do not match for multiplication! {}", e))
 }
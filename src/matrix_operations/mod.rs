@@ -1,3 +1,21 @@
+mod error;
 mod mat_mul;
+mod inverse;
+mod triangular;
+mod cholesky;
+mod lu;
+mod qr;
+mod eigh;
+mod svd;
+mod sparse;
 
+pub use error::*;
 pub use mat_mul::*;
+pub use inverse::*;
+pub use triangular::*;
+pub use cholesky::*;
+pub use lu::*;
+pub use qr::*;
+pub use eigh::*;
+pub use svd::*;
+pub use sparse::*;
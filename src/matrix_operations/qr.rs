@@ -0,0 +1,80 @@
+use ndarray::{s, Array1, Array2, Axis};
+use crate::matrix_operations::{matrix_mul, solve_triangular, MatrixError};
+
+/// Thin QR decomposition by Householder reflections.
+///
+/// For an `m x n` matrix with `k = min(m, n)` returns `(Q, R)` where `Q` is `m x k` with
+/// orthonormal columns, `R` is `k x n` upper triangular and `A = Q R`. Returns `NonFinite`
+/// for NaN or infinite entries, which would otherwise spread through every reflection.
+pub fn qr_decomposition(a: &Array2<f64>) -> Result<(Array2<f64>, Array2<f64>), MatrixError> {
+    if a.iter().any(|x| !x.is_finite()) {
+        return Err(MatrixError::NonFinite);
+    }
+    let (m, n) = a.dim();
+    let k = m.min(n);
+    let mut r = a.clone();
+    let mut reflectors: Vec<Array1<f64>> = Vec::with_capacity(k);
+
+    for col in 0..k {
+        // Householder vector v mapping r[col.., col] onto a multiple of e_1
+        let mut v = r.slice(s![col.., col]).to_owned();
+        let norm = v.dot(&v).sqrt();
+        if norm > 0.0 {
+            v[0] += if v[0] >= 0.0 { norm } else { -norm };
+            let v_norm = v.dot(&v).sqrt();
+            v /= v_norm;
+            let mut block = r.slice_mut(s![col.., col..]);
+            let projection = v.dot(&block);
+            for (i, &vi) in v.iter().enumerate() {
+                block.row_mut(i).scaled_add(-2.0 * vi, &projection);
+            }
+        }
+        reflectors.push(v);
+    }
+
+    // Q = H_0 H_1 ... H_{k-1} applied to the first k columns of the identity
+    let mut q = Array2::<f64>::eye(m).slice(s![.., ..k]).to_owned();
+    for (col, v) in reflectors.iter().enumerate().rev() {
+        if v.iter().all(|&x| x == 0.0) {
+            continue;
+        }
+        let mut block = q.slice_mut(s![col.., ..]);
+        let projection = v.dot(&block);
+        for (i, &vi) in v.iter().enumerate() {
+            block.row_mut(i).scaled_add(-2.0 * vi, &projection);
+        }
+    }
+
+    let mut r = r.slice(s![..k, ..]).to_owned();
+    for i in 1..k {
+        r.slice_mut(s![i, ..i]).fill(0.0);
+    }
+    Ok((q, r))
+}
+
+/// Least-squares solution of `A X = B` for a tall `A` of full column rank, through `A = Q R`.
+///
+/// Better conditioned than solving the normal equations; returns `Singular` when `A` is rank deficient.
+pub fn least_squares(a: &Array2<f64>, b: &Array2<f64>) -> Result<Array2<f64>, MatrixError> {
+    let (m, n) = a.dim();
+    if b.nrows() != m || m < n {
+        return Err(MatrixError::IncompatibleShapes { operation: "least squares", left: a.dim(), right: b.dim() });
+    }
+    let (q, r) = qr_decomposition(a)?;
+    let scale = r.diag().iter().fold(0.0_f64, |acc, &x| acc.max(x.abs()));
+    if r.diag().iter().any(|d| d.abs() <= f64::EPSILON * m as f64 * scale) {
+        return Err(MatrixError::Singular);
+    }
+    solve_triangular(&r, &matrix_mul(&q.t(), b), false)
+}
+
+/// Weighted least-squares solution of `A X = B`, minimizing `sum_i w_i |A_i X - B_i|²` over the rows.
+///
+/// Solves the ordinary problem on rows scaled by `sqrt(w_i)`; weights must be non-negative.
+pub fn weighted_least_squares(a: &Array2<f64>, b: &Array2<f64>, sample_weight: &Array1<f64>) -> Result<Array2<f64>, MatrixError> {
+    if sample_weight.len() != a.nrows() || b.nrows() != a.nrows() {
+        return Err(MatrixError::IncompatibleShapes { operation: "weighted least squares", left: a.dim(), right: b.dim() });
+    }
+    let scale = sample_weight.mapv(|w| w.max(0.0).sqrt()).insert_axis(Axis(1));
+    least_squares(&(a * &scale), &(b * &scale))
+}
@@ -0,0 +1,579 @@
+use std::ops::Range;
+use ndarray::{s, Array2, ArrayBase, ArrayView2, ArrayViewMut2, Data, Ix2};
+use serde::{Deserialize, Serialize};
+use crate::matrix_operations::{gemm, MatrixError, Transpose};
+
+/// Matrix that can multiply dense matrices: a dense `Array2`, a `CsrMatrix` or a `CscMatrix`
+///
+/// The linear model training loop and gradients are written against this trait, so sparse
+/// inputs are never densified.
+pub trait LinearOperator {
+    fn dim(&self) -> (usize, usize);
+
+    /// `C = alpha * op(self[rows, ..]) * B + beta * C` in place, reading the rows where they are stored
+    ///
+    /// Lets training loops multiply a batch of rows without copying it out first.
+    fn mul_rows_into(
+        &self,
+        rows: Range<usize>,
+        alpha: f64,
+        trans: Transpose,
+        b: &ArrayView2<f64>,
+        beta: f64,
+        c: &mut ArrayViewMut2<f64>,
+    ) -> Result<(), MatrixError>;
+
+    /// Copy of the rows in `rows`, in the same storage format
+    fn slice_rows(&self, rows: Range<usize>) -> Self
+    where
+        Self: Sized;
+
+    /// `C = alpha * op(self) * B + beta * C` in place, like `gemm` with a dense right operand
+    fn mul_into(
+        &self,
+        alpha: f64,
+        trans: Transpose,
+        b: &ArrayView2<f64>,
+        beta: f64,
+        c: &mut ArrayViewMut2<f64>,
+    ) -> Result<(), MatrixError> {
+        self.mul_rows_into(0..self.dim().0, alpha, trans, b, beta, c)
+    }
+
+    /// `op(self) * B` in a new matrix
+    fn multiply(&self, trans: Transpose, b: &ArrayView2<f64>) -> Result<Array2<f64>, MatrixError> {
+        let (rows, cols) = self.dim();
+        let m = if trans == Transpose::No { rows } else { cols };
+        let mut c = Array2::<f64>::zeros((m, b.ncols()));
+        self.mul_into(1.0, trans, b, 0.0, &mut c.view_mut())?;
+        Ok(c)
+    }
+}
+
+/// Widest right-hand side with a kernel specialised at compile time
+const SKINNY_COLUMNS: usize = 4;
+
+/// `C += alpha * op(A) * B` without allocating, shapes already checked
+///
+/// `dgemm` packs its operands into freshly allocated buffers on every call, which the training
+/// loop would pay on every step. Up to `SKINNY_COLUMNS` right-hand columns the rows of `B` and `C`
+/// stay in registers; wider right-hand sides are accumulated one row of `C` at a time.
+fn dense_mul(alpha: f64, a: &ArrayView2<f64>, trans: Transpose, b: &ArrayView2<f64>, c: &mut ArrayViewMut2<f64>) {
+    if let (Some(a_data), Some(b_data), Some(c_data)) = (a.as_slice(), b.as_slice(), c.as_slice_mut()) {
+        let width = a.ncols();
+        match b.ncols() {
+            0 => {}
+            1 => skinny_kernel::<1>(alpha, a_data, width, trans, b_data, c_data),
+            2 => skinny_kernel::<2>(alpha, a_data, width, trans, b_data, c_data),
+            3 => skinny_kernel::<3>(alpha, a_data, width, trans, b_data, c_data),
+            4 => skinny_kernel::<4>(alpha, a_data, width, trans, b_data, c_data),
+            n => wide_kernel(alpha, a_data, width, trans, b_data, n, c_data),
+        }
+        return;
+    }
+    for (r, row) in a.rows().into_iter().enumerate() {
+        for j in 0..b.ncols() {
+            match trans {
+                Transpose::No => c[(r, j)] += alpha * row.dot(&b.column(j)),
+                Transpose::Yes => c.column_mut(j).scaled_add(alpha * b[(r, j)], &row),
+            }
+        }
+    }
+}
+
+/// Contiguous `dense_mul` with `N` right-hand columns known at compile time, so rows of `B` and `C` stay in registers
+fn skinny_kernel<const N: usize>(alpha: f64, a: &[f64], width: usize, trans: Transpose, b: &[f64], c: &mut [f64]) {
+    if width == 0 {
+        return;
+    }
+    let as_array = |row: &[f64]| <[f64; N]>::try_from(row).expect("Chunks have N values");
+    match trans {
+        Transpose::No => {
+            for (a_row, c_row) in a.chunks_exact(width).zip(c.chunks_exact_mut(N)) {
+                let mut sum = [0.0; N];
+                for (&x, b_row) in a_row.iter().zip(b.chunks_exact(N)) {
+                    sum.iter_mut().zip(as_array(b_row)).for_each(|(s, b)| *s += x * b);
+                }
+                c_row.iter_mut().zip(sum).for_each(|(c, s)| *c += alpha * s);
+            }
+        }
+        Transpose::Yes => {
+            for (a_row, b_row) in a.chunks_exact(width).zip(b.chunks_exact(N)) {
+                let scaled = as_array(b_row).map(|v| alpha * v);
+                for (&x, c_row) in a_row.iter().zip(c.chunks_exact_mut(N)) {
+                    c_row.iter_mut().zip(scaled).for_each(|(c, s)| *c += x * s);
+                }
+            }
+        }
+    }
+}
+
+/// Contiguous `dense_mul` for any number `n` of right-hand columns, adding whole rows of `B` into rows of `C`
+fn wide_kernel(alpha: f64, a: &[f64], width: usize, trans: Transpose, b: &[f64], n: usize, c: &mut [f64]) {
+    if width == 0 {
+        return;
+    }
+    match trans {
+        Transpose::No => {
+            for (a_row, c_row) in a.chunks_exact(width).zip(c.chunks_exact_mut(n)) {
+                for (&x, b_row) in a_row.iter().zip(b.chunks_exact(n)) {
+                    let x = alpha * x;
+                    c_row.iter_mut().zip(b_row).for_each(|(c, b)| *c += x * b);
+                }
+            }
+        }
+        Transpose::Yes => {
+            for (a_row, b_row) in a.chunks_exact(width).zip(b.chunks_exact(n)) {
+                for (&x, c_row) in a_row.iter().zip(c.chunks_exact_mut(n)) {
+                    let x = alpha * x;
+                    c_row.iter_mut().zip(b_row).for_each(|(c, b)| *c += x * b);
+                }
+            }
+        }
+    }
+}
+
+/// Panics unless `rows` lies within `0..n_rows`, like slicing a dense array out of bounds
+fn check_rows(rows: &Range<usize>, n_rows: usize) {
+    assert!(rows.start <= rows.end && rows.end <= n_rows, "Row range {:?} out of bounds for {} rows", rows, n_rows);
+}
+
+impl LinearOperator for Array2<f64> {
+    fn dim(&self) -> (usize, usize) {
+        ArrayBase::dim(self)
+    }
+
+    fn mul_rows_into(
+        &self,
+        rows: Range<usize>,
+        alpha: f64,
+        trans: Transpose,
+        b: &ArrayView2<f64>,
+        beta: f64,
+        c: &mut ArrayViewMut2<f64>,
+    ) -> Result<(), MatrixError> {
+        let a = self.slice(s![rows, ..]);
+        let (m, k) = match trans {
+            Transpose::No => a.dim(),
+            Transpose::Yes => (a.ncols(), a.nrows()),
+        };
+        if b.nrows() != k || c.dim() != (m, b.ncols()) {
+            return Err(MatrixError::IncompatibleShapes { operation: "dense product", left: (m, k), right: b.dim() });
+        }
+        if beta == 0.0 {
+            c.fill(0.0);
+        } else if beta != 1.0 {
+            c.mapv_inplace(|v| beta * v);
+        }
+        dense_mul(alpha, &a, trans, b, c);
+        Ok(())
+    }
+
+    /// Goes through `dgemm` for wide right-hand sides, whose buffers cost little next to the new result
+    fn multiply(&self, trans: Transpose, b: &ArrayView2<f64>) -> Result<Array2<f64>, MatrixError> {
+        let m = if trans == Transpose::No { self.nrows() } else { self.ncols() };
+        let mut c = Array2::<f64>::zeros((m, b.ncols()));
+        if b.ncols() > SKINNY_COLUMNS {
+            gemm(1.0, self, trans, b, Transpose::No, 0.0, &mut c)?;
+        } else {
+            self.mul_into(1.0, trans, b, 0.0, &mut c.view_mut())?;
+        }
+        Ok(c)
+    }
+
+    fn slice_rows(&self, rows: Range<usize>) -> Self {
+        self.slice(s![rows, ..]).to_owned()
+    }
+}
+
+/// Compressed sparse storage shared by CSR (outer axis = rows) and CSC (outer axis = columns)
+///
+/// Entry `k` of outer slice `o` sits at inner position `indices[k]` with value `data[k]`,
+/// for `k` in `indptr[o]..indptr[o + 1]`. Inner indices are sorted and unique within a slice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Compressed {
+    outer: usize,
+    inner: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    data: Vec<f64>,
+}
+
+impl Compressed {
+    fn new(outer: usize, inner: usize, indptr: Vec<usize>, indices: Vec<usize>, data: Vec<f64>) -> Result<Self, MatrixError> {
+        let invalid = |reason: &str| Err(MatrixError::InvalidSparse(reason.to_string()));
+        if indptr.len() != outer + 1 || indptr[0] != 0 {
+            return invalid("indptr must start at 0 and have one entry per row (column) plus one");
+        }
+        if indices.len() != data.len() || indptr[outer] != data.len() {
+            return invalid("indptr, indices and data disagree on the number of stored values");
+        }
+        // Every offset is checked before any row (column) is sliced
+        if indptr.windows(2).any(|pair| pair[0] > pair[1] || pair[1] > data.len()) {
+            return invalid("indptr must be non-decreasing and within the number of stored values");
+        }
+        for o in 0..outer {
+            let slice = &indices[indptr[o]..indptr[o + 1]];
+            if slice.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return invalid("indices must be sorted and unique within every row (column)");
+            }
+            if slice.last().is_some_and(|&i| i >= inner) {
+                return invalid("index out of bounds");
+            }
+        }
+        Ok(Self { outer, inner, indptr, indices, data })
+    }
+
+    /// Builds from `(outer, inner, value)` entries in any order, summing duplicates
+    fn from_entries(outer: usize, inner: usize, mut entries: Vec<(usize, usize, f64)>) -> Result<Self, MatrixError> {
+        if entries.iter().any(|&(o, i, _)| o >= outer || i >= inner) {
+            return Err(MatrixError::InvalidSparse("index out of bounds".to_string()));
+        }
+        entries.sort_by_key(|&(o, i, _)| (o, i));
+        let mut indptr = vec![0; outer + 1];
+        let mut indices = Vec::with_capacity(entries.len());
+        let mut data: Vec<f64> = Vec::with_capacity(entries.len());
+        let mut last = None;
+        for (o, i, value) in entries {
+            if last == Some((o, i)) {
+                *data.last_mut().expect("A previous entry exists") += value;
+                continue;
+            }
+            indptr[o + 1] += 1;
+            indices.push(i);
+            data.push(value);
+            last = Some((o, i));
+        }
+        for o in 0..outer {
+            indptr[o + 1] += indptr[o];
+        }
+        Ok(Self { outer, inner, indptr, indices, data })
+    }
+
+    fn slice(&self, o: usize) -> (&[usize], &[f64]) {
+        let range = self.indptr[o]..self.indptr[o + 1];
+        (&self.indices[range.clone()], &self.data[range])
+    }
+
+    /// Same matrix stored along the other axis
+    fn swap_axes(&self) -> Self {
+        let mut indptr = vec![0; self.inner + 1];
+        for &i in &self.indices {
+            indptr[i + 1] += 1;
+        }
+        for i in 0..self.inner {
+            indptr[i + 1] += indptr[i];
+        }
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.data.len()];
+        let mut data = vec![0.0; self.data.len()];
+        // Visiting outer slices in order keeps the new inner indices sorted
+        for o in 0..self.outer {
+            let (slice_indices, slice_data) = self.slice(o);
+            for (&i, &value) in slice_indices.iter().zip(slice_data) {
+                indices[next[i]] = o;
+                data[next[i]] = value;
+                next[i] += 1;
+            }
+        }
+        Self { outer: self.inner, inner: self.outer, indptr, indices, data }
+    }
+
+    fn to_dense(&self) -> Array2<f64> {
+        let mut dense = Array2::<f64>::zeros((self.outer, self.inner));
+        for o in 0..self.outer {
+            let (indices, data) = self.slice(o);
+            for (&i, &value) in indices.iter().zip(data) {
+                dense[(o, i)] = value;
+            }
+        }
+        dense
+    }
+
+    /// `C = alpha * op(M) * B + beta * C` for the block `M = self[outer, inner]`
+    #[allow(clippy::too_many_arguments)]
+    fn mul_into(
+        &self,
+        outer: Range<usize>,
+        inner: Range<usize>,
+        alpha: f64,
+        trans: Transpose,
+        b: &ArrayView2<f64>,
+        beta: f64,
+        c: &mut ArrayViewMut2<f64>,
+    ) -> Result<(), MatrixError> {
+        let (m, k) = match trans {
+            Transpose::No => (outer.len(), inner.len()),
+            Transpose::Yes => (inner.len(), outer.len()),
+        };
+        if b.nrows() != k {
+            return Err(MatrixError::IncompatibleShapes { operation: "sparse product", left: (m, k), right: b.dim() });
+        }
+        if c.dim() != (m, b.ncols()) {
+            return Err(MatrixError::IncompatibleShapes { operation: "sparse product output", left: (m, b.ncols()), right: c.dim() });
+        }
+
+        if beta == 0.0 {
+            c.fill(0.0);
+        } else if beta != 1.0 {
+            c.mapv_inplace(|v| beta * v);
+        }
+        let restrict_inner = inner != (0..self.inner);
+        for o in outer.clone() {
+            let (mut indices, mut data) = self.slice(o);
+            if restrict_inner {
+                // Inner indices are sorted, so the block is a contiguous run of the slice
+                let (first, last) = (indices.partition_point(|&i| i < inner.start), indices.partition_point(|&i| i < inner.end));
+                (indices, data) = (&indices[first..last], &data[first..last]);
+            }
+            for (&i, &value) in indices.iter().zip(data) {
+                // Row o of M scatters into row o of C, or gathers into row i when transposed
+                let (target, source) = match trans {
+                    Transpose::No => (o - outer.start, i - inner.start),
+                    Transpose::Yes => (i - inner.start, o - outer.start),
+                };
+                c.row_mut(target).scaled_add(alpha * value, &b.row(source));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compressed sparse row matrix, efficient for row slicing and `A * B`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsrMatrix {
+    storage: Compressed,
+}
+
+/// Compressed sparse column matrix, efficient for column access and `Aᵀ * B`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CscMatrix {
+    storage: Compressed,
+}
+
+impl CsrMatrix {
+    /// Creates a CSR matrix from its raw arrays, checking their consistency
+    ///
+    /// Column indices must be sorted and unique within every row.
+    pub fn new(
+        shape: (usize, usize),
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<f64>,
+    ) -> Result<Self, MatrixError> {
+        Ok(Self { storage: Compressed::new(shape.0, shape.1, indptr, indices, data)? })
+    }
+
+    /// Creates a CSR matrix from `(row, column, value)` entries in any order; duplicates are summed
+    pub fn from_triplets(shape: (usize, usize), triplets: Vec<(usize, usize, f64)>) -> Result<Self, MatrixError> {
+        Ok(Self { storage: Compressed::from_entries(shape.0, shape.1, triplets)? })
+    }
+
+    /// Stores the non-zero entries of a dense matrix
+    pub fn from_dense<S: Data<Elem = f64>>(dense: &ArrayBase<S, Ix2>) -> Self {
+        let triplets = dense.indexed_iter().filter(|(_, &v)| v != 0.0).map(|((r, c), &v)| (r, c, v)).collect();
+        Self::from_triplets(dense.dim(), triplets).expect("Entries of a dense matrix are in bounds")
+    }
+
+    pub fn to_dense(&self) -> Array2<f64> {
+        self.storage.to_dense()
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.storage.outer
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.storage.inner
+    }
+
+    /// Number of stored values
+    pub fn nnz(&self) -> usize {
+        self.storage.data.len()
+    }
+
+    pub fn indptr(&self) -> &[usize] {
+        &self.storage.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.storage.indices
+    }
+
+    pub fn data(&self) -> &[f64] {
+        &self.storage.data
+    }
+
+    /// Column indices and values stored in row `row`
+    pub fn row(&self, row: usize) -> (&[usize], &[f64]) {
+        self.storage.slice(row)
+    }
+
+    /// Applies `f(value, column)` to every stored value; implicit zeros stay zero
+    pub fn map_columns(&self, f: impl Fn(f64, usize) -> f64) -> Self {
+        let mut mapped = self.clone();
+        for (value, &col) in mapped.storage.data.iter_mut().zip(&self.storage.indices) {
+            *value = f(*value, col);
+        }
+        mapped
+    }
+
+    /// Prepends a column of ones, the sparse counterpart of `PolynomialFeatures::prepend_bias`
+    pub fn prepend_ones(&self) -> Self {
+        let n = self.nrows();
+        let mut indptr = Vec::with_capacity(n + 1);
+        let mut indices = Vec::with_capacity(self.nnz() + n);
+        let mut data = Vec::with_capacity(self.nnz() + n);
+        indptr.push(0);
+        for row in 0..n {
+            let (row_indices, row_data) = self.row(row);
+            indices.push(0);
+            data.push(1.0);
+            indices.extend(row_indices.iter().map(|&c| c + 1));
+            data.extend_from_slice(row_data);
+            indptr.push(data.len());
+        }
+        Self { storage: Compressed { outer: n, inner: self.ncols() + 1, indptr, indices, data } }
+    }
+
+    /// Transpose, sharing the same arrays (a CSR matrix read column-wise is the CSC of its transpose)
+    pub fn transpose(self) -> CscMatrix {
+        CscMatrix { storage: self.storage }
+    }
+
+    /// Same matrix in CSC format
+    pub fn to_csc(&self) -> CscMatrix {
+        CscMatrix { storage: self.storage.swap_axes() }
+    }
+}
+
+impl CscMatrix {
+    /// Creates a CSC matrix from its raw arrays, checking their consistency
+    ///
+    /// Row indices must be sorted and unique within every column.
+    pub fn new(
+        shape: (usize, usize),
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<f64>,
+    ) -> Result<Self, MatrixError> {
+        Ok(Self { storage: Compressed::new(shape.1, shape.0, indptr, indices, data)? })
+    }
+
+    /// Creates a CSC matrix from `(row, column, value)` entries in any order; duplicates are summed
+    pub fn from_triplets(shape: (usize, usize), triplets: Vec<(usize, usize, f64)>) -> Result<Self, MatrixError> {
+        let entries = triplets.into_iter().map(|(r, c, v)| (c, r, v)).collect();
+        Ok(Self { storage: Compressed::from_entries(shape.1, shape.0, entries)? })
+    }
+
+    /// Stores the non-zero entries of a dense matrix
+    pub fn from_dense<S: Data<Elem = f64>>(dense: &ArrayBase<S, Ix2>) -> Self {
+        CsrMatrix::from_dense(&dense.t()).transpose()
+    }
+
+    pub fn to_dense(&self) -> Array2<f64> {
+        self.storage.to_dense().reversed_axes().as_standard_layout().to_owned()
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.storage.inner
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.storage.outer
+    }
+
+    /// Number of stored values
+    pub fn nnz(&self) -> usize {
+        self.storage.data.len()
+    }
+
+    pub fn indptr(&self) -> &[usize] {
+        &self.storage.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.storage.indices
+    }
+
+    pub fn data(&self) -> &[f64] {
+        &self.storage.data
+    }
+
+    /// Row indices and values stored in column `col`
+    pub fn column(&self, col: usize) -> (&[usize], &[f64]) {
+        self.storage.slice(col)
+    }
+
+    /// Transpose, sharing the same arrays
+    pub fn transpose(self) -> CsrMatrix {
+        CsrMatrix { storage: self.storage }
+    }
+
+    /// Same matrix in CSR format
+    pub fn to_csr(&self) -> CsrMatrix {
+        CsrMatrix { storage: self.storage.swap_axes() }
+    }
+}
+
+impl LinearOperator for CsrMatrix {
+    fn dim(&self) -> (usize, usize) {
+        (self.nrows(), self.ncols())
+    }
+
+    fn mul_rows_into(
+        &self,
+        rows: Range<usize>,
+        alpha: f64,
+        trans: Transpose,
+        b: &ArrayView2<f64>,
+        beta: f64,
+        c: &mut ArrayViewMut2<f64>,
+    ) -> Result<(), MatrixError> {
+        check_rows(&rows, self.nrows());
+        self.storage.mul_into(rows, 0..self.ncols(), alpha, trans, b, beta, c)
+    }
+
+    fn slice_rows(&self, rows: Range<usize>) -> Self {
+        check_rows(&rows, self.nrows());
+        let values = self.storage.indptr[rows.start]..self.storage.indptr[rows.end];
+        let indptr = self.storage.indptr[rows.start..=rows.end].iter().map(|&p| p - values.start).collect();
+        Self {
+            storage: Compressed {
+                outer: rows.len(),
+                inner: self.ncols(),
+                indptr,
+                indices: self.storage.indices[values.clone()].to_vec(),
+                data: self.storage.data[values].to_vec(),
+            },
+        }
+    }
+}
+
+impl LinearOperator for CscMatrix {
+    fn dim(&self) -> (usize, usize) {
+        (self.nrows(), self.ncols())
+    }
+
+    fn mul_rows_into(
+        &self,
+        rows: Range<usize>,
+        alpha: f64,
+        trans: Transpose,
+        b: &ArrayView2<f64>,
+        beta: f64,
+        c: &mut ArrayViewMut2<f64>,
+    ) -> Result<(), MatrixError> {
+        check_rows(&rows, self.nrows());
+        // The storage holds the transpose, so the operation flips and the rows restrict the inner axis
+        let flipped = match trans {
+            Transpose::No => Transpose::Yes,
+            Transpose::Yes => Transpose::No,
+        };
+        self.storage.mul_into(0..self.ncols(), rows, alpha, flipped, b, beta, c)
+    }
+
+    fn slice_rows(&self, rows: Range<usize>) -> Self {
+        self.to_csr().slice_rows(rows).to_csc()
+    }
+}
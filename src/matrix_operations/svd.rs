@@ -0,0 +1,126 @@
+use ndarray::{s, Array1, Array2, Axis};
+use ndarray_rand::rand_distr::StandardNormal;
+use ndarray_rand::RandomExt;
+use crate::matrix_operations::{matrix_mul, qr_decomposition, LinearOperator, MatrixError, Transpose};
+use crate::utils::rng;
+
+/// Jacobi sweeps after which `svd` gives up
+const MAX_SWEEPS: usize = 100;
+
+/// Thin singular value decomposition, `(U, S, Vt)`
+pub type Svd = (Array2<f64>, Array1<f64>, Array2<f64>);
+
+/// Thin singular value decomposition by one-sided (Hestenes) Jacobi rotations.
+///
+/// For an `m x n` matrix with `k = min(m, n)` returns `(U, S, Vt)` with `U` of shape `m x k`,
+/// the singular values `S` in descending order and `Vt` of shape `k x n`, so that
+/// `A = U diag(S) Vt`. Columns of `U` for zero singular values are left as zeros.
+/// Returns `NonFinite` for NaN or infinite entries and `NotConverged` when columns are
+/// still being rotated after the last sweep.
+pub fn svd(a: &Array2<f64>) -> Result<Svd, MatrixError> {
+    let (m, n) = a.dim();
+    if m < n {
+        let (u, singular_values, vt) = svd(&a.t().to_owned())?;
+        return Ok((vt.t().to_owned(), singular_values, u.t().to_owned()));
+    }
+    if a.iter().any(|x| !x.is_finite()) {
+        return Err(MatrixError::NonFinite);
+    }
+
+    // Rows of `ut` are the columns of A being orthogonalized, rows of `vt` accumulate the rotations
+    let mut ut = a.t().to_owned();
+    let mut vt = Array2::<f64>::eye(n);
+    let mut converged = false;
+    for _sweep in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let alpha = ut.row(p).dot(&ut.row(p));
+                let beta = ut.row(q).dot(&ut.row(q));
+                let gamma = ut.row(p).dot(&ut.row(q));
+                if gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() || gamma == 0.0 {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (zeta * zeta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = c * t;
+                rotate_rows(&mut ut, p, q, c, s);
+                rotate_rows(&mut vt, p, q, c, s);
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(MatrixError::NotConverged { operation: "SVD", sweeps: MAX_SWEEPS });
+    }
+
+    let norms: Vec<f64> = ut.axis_iter(Axis(0)).map(|row| row.dot(&row).sqrt()).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+
+    let singular_values: Array1<f64> = order.iter().map(|&i| norms[i]).collect();
+    let mut u = Array2::<f64>::zeros((m, n));
+    for (col, &i) in order.iter().enumerate() {
+        if norms[i] > 0.0 {
+            u.column_mut(col).assign(&(&ut.row(i) / norms[i]));
+        }
+    }
+    Ok((u, singular_values, vt.select(Axis(0), &order)))
+}
+
+fn rotate_rows(x: &mut Array2<f64>, p: usize, q: usize, c: f64, s: f64) {
+    for k in 0..x.ncols() {
+        let (xp, xq) = (x[(p, k)], x[(q, k)]);
+        x[(p, k)] = c * xp - s * xq;
+        x[(q, k)] = s * xp + c * xq;
+    }
+}
+
+/// Function to approximate the leading singular triplets with a randomized range finder
+///
+/// Projects `a` on a random subspace of dimension `n_components + n_oversamples`,
+/// refines it with `n_iter` power iterations and takes the exact SVD of the small
+/// projected matrix (Halko, Martinsson and Tropp, 2011). `a` is only used through
+/// products, so a sparse matrix is never densified.
+///
+/// # Parameters:
+/// - `a: &A` - Matrix to decompose, dense or sparse
+/// - `n_components: usize` - Number of singular triplets to return
+/// - `n_oversamples: usize` - Extra random directions improving accuracy
+/// - `n_iter: usize` - Power iterations, useful when the spectrum decays slowly
+/// - `seed: Option<u64>` - Seed of the random projection
+///
+/// # Returns:
+/// - `Result<Svd, MatrixError>` - `(U, S, Vt)` truncated to `n_components` (or `min(m, n)` if smaller)
+pub fn randomized_svd<A: LinearOperator>(
+    a: &A,
+    n_components: usize,
+    n_oversamples: usize,
+    n_iter: usize,
+    seed: Option<u64>,
+) -> Result<Svd, MatrixError> {
+    let (m, n) = a.dim();
+    let n_random = (n_components + n_oversamples).min(m.min(n));
+    let omega = Array2::<f64>::random_using((n, n_random), StandardNormal, &mut rng(seed));
+
+    let (mut q, _) = qr_decomposition(&a.multiply(Transpose::No, &omega.view())?)?;
+    for _ in 0..n_iter {
+        // Re-orthonormalize after every product to keep the small directions accurate
+        let (q_t, _) = qr_decomposition(&a.multiply(Transpose::Yes, &q.view())?)?;
+        q = qr_decomposition(&a.multiply(Transpose::No, &q_t.view())?)?.0;
+    }
+
+    // Qᵀ A, computed as (Aᵀ Q)ᵀ
+    let (u_small, singular_values, vt) = svd(&a.multiply(Transpose::Yes, &q.view())?.reversed_axes())?;
+    let k = n_components.min(singular_values.len());
+    Ok((
+        matrix_mul(&q, &u_small.slice(s![.., ..k]).to_owned()),
+        singular_values.slice(s![..k]).to_owned(),
+        vt.slice(s![..k, ..]).to_owned(),
+    ))
+}
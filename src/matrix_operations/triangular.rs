@@ -0,0 +1,34 @@
+use ndarray::Array2;
+use crate::matrix_operations::MatrixError;
+
+/// Solves `T X = B` for a triangular `T` by forward (`lower`) or back substitution.
+///
+/// Only the relevant triangle of `T` is read. Returns `Singular` on a zero diagonal entry.
+pub fn solve_triangular(t: &Array2<f64>, b: &Array2<f64>, lower: bool) -> Result<Array2<f64>, MatrixError> {
+    let n = t.nrows();
+    if t.ncols() != n {
+        return Err(MatrixError::NotSquare(t.dim()));
+    }
+    if b.nrows() != n {
+        return Err(MatrixError::IncompatibleShapes { operation: "triangular solve", left: t.dim(), right: b.dim() });
+    }
+
+    let mut x = b.clone();
+    let rows: Vec<usize> = if lower { (0..n).collect() } else { (0..n).rev().collect() };
+    for &i in &rows {
+        let diagonal = t[(i, i)];
+        if diagonal == 0.0 {
+            return Err(MatrixError::Singular);
+        }
+        let solved: Vec<usize> = if lower { (0..i).collect() } else { (i + 1..n).collect() };
+        for &j in &solved {
+            let factor = t[(i, j)];
+            if factor != 0.0 {
+                let (mut row_i, row_j) = x.multi_slice_mut((ndarray::s![i, ..], ndarray::s![j, ..]));
+                row_i.scaled_add(-factor, &row_j);
+            }
+        }
+        x.row_mut(i).mapv_inplace(|v| v / diagonal);
+    }
+    Ok(x)
+}
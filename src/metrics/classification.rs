@@ -0,0 +1,287 @@
+use ndarray::{Array1, Array2, ArrayView1};
+use crate::metrics::{aggregate, aggregate_weighted, weighted_mean, MultiOutput};
+
+/// How per-class precision, recall and F1 values are combined within an output column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Average {
+    Binary(f64), // Only report the class with this label
+    Micro,       // Global counts of true/false positives and false negatives
+    Macro,       // Unweighted mean over classes
+    Weighted,    // Mean over classes weighted by their support in `y_true`
+}
+
+/// Confusion matrix of one output column; rows are true labels, columns predicted labels
+///
+/// Counts are `usize`, or summed sample weights (`f64`) for `confusion_matrix_weighted`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfusionMatrix<T = usize> {
+    pub labels: Vec<f64>,
+    pub matrix: Array2<T>,
+}
+
+/// Fraction of correctly predicted labels
+pub fn accuracy_score(y_true: &Array2<f64>, y_pred: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| {
+        t.iter().zip(p.iter()).filter(|(a, b)| a == b).count() as f64 / t.len() as f64
+    })
+}
+
+/// Weighted fraction of correctly predicted labels, every row counting its `sample_weight`
+pub fn accuracy_score_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| {
+        weighted_mean(t.iter().zip(p.iter()).map(|(a, b)| if a == b { 1.0 } else { 0.0 }), w)
+    })
+}
+
+/// Precision: true positives over predicted positives (0 when nothing is predicted positive)
+pub fn precision_score(y_true: &Array2<f64>, y_pred: &Array2<f64>, average: Average, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| averaged(t, p, None, average, precision))
+}
+
+/// Precision counting every row with its `sample_weight`
+pub fn precision_score_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, average: Average, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| averaged(t, p, Some(w), average, precision))
+}
+
+/// Recall: true positives over actual positives (0 when there are no positives)
+pub fn recall_score(y_true: &Array2<f64>, y_pred: &Array2<f64>, average: Average, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| averaged(t, p, None, average, recall))
+}
+
+/// Recall counting every row with its `sample_weight`
+pub fn recall_score_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, average: Average, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| averaged(t, p, Some(w), average, recall))
+}
+
+/// F1 score, the harmonic mean of precision and recall
+pub fn f1_score(y_true: &Array2<f64>, y_pred: &Array2<f64>, average: Average, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| averaged(t, p, None, average, f1))
+}
+
+/// F1 score counting every row with its `sample_weight`
+pub fn f1_score_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, average: Average, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| averaged(t, p, Some(w), average, f1))
+}
+
+/// Area under the ROC curve for binary (0/1) labels and scores of the positive class
+///
+/// Returns `NaN` for a column whose labels contain a single class.
+pub fn roc_auc_score(y_true: &Array2<f64>, y_score: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_score, multioutput, |t, s| roc_auc(t, s, None))
+}
+
+/// Area under the ROC curve, every positive/negative pair counting the product of their weights
+///
+/// Returns `NaN` for a column without positive or negative weight.
+pub fn roc_auc_score_weighted(y_true: &Array2<f64>, y_score: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_score, sample_weight, multioutput, |t, s, w| roc_auc(t, s, Some(w)))
+}
+
+/// Area under the precision-recall curve, computed as average precision
+///
+/// Labels are binary (0/1) and scores belong to the positive class. Returns `NaN`
+/// for a column without positives.
+pub fn pr_auc_score(y_true: &Array2<f64>, y_score: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_score, multioutput, |t, s| pr_auc(t, s, None))
+}
+
+/// Average precision with true and false positives counted by `sample_weight`
+pub fn pr_auc_score_weighted(y_true: &Array2<f64>, y_score: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_score, sample_weight, multioutput, |t, s, w| pr_auc(t, s, Some(w)))
+}
+
+/// Binary cross-entropy for 0/1 labels and predicted probabilities of the positive class
+///
+/// Probabilities are clipped to `[1e-15, 1 - 1e-15]`.
+pub fn log_loss(y_true: &Array2<f64>, y_prob: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_prob, multioutput, |t, p| {
+        t.iter()
+            .zip(p.iter())
+            .map(|(&label, &prob)| {
+                let prob = prob.clamp(1e-15, 1.0 - 1e-15);
+                -(label * prob.ln() + (1.0 - label) * (1.0 - prob).ln())
+            })
+            .sum::<f64>()
+            / t.len() as f64
+    })
+}
+
+/// Binary cross-entropy with every row weighted by `sample_weight`
+pub fn log_loss_weighted(y_true: &Array2<f64>, y_prob: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_prob, sample_weight, multioutput, |t, p, w| {
+        weighted_mean(t.iter().zip(p.iter()).map(|(&label, &prob)| {
+            let prob = prob.clamp(1e-15, 1.0 - 1e-15);
+            -(label * prob.ln() + (1.0 - label) * (1.0 - prob).ln())
+        }), w)
+    })
+}
+
+/// Confusion matrix of output column `column`, over the sorted union of true and predicted labels
+///
+/// Panics if a label is not finite.
+pub fn confusion_matrix(y_true: &Array2<f64>, y_pred: &Array2<f64>, column: usize) -> ConfusionMatrix {
+    assert_eq!(y_true.dim(), y_pred.dim(), "Shapes of y_true and y_pred do not match!");
+    let (t, p) = (y_true.column(column), y_pred.column(column));
+    let labels = labels(t, p);
+
+    let mut matrix = Array2::<usize>::zeros((labels.len(), labels.len()));
+    for (a, b) in t.iter().zip(p.iter()) {
+        matrix[(position(&labels, *a), position(&labels, *b))] += 1;
+    }
+    ConfusionMatrix { labels, matrix }
+}
+
+/// Confusion matrix of output column `column` whose cells sum the `sample_weight` of their rows
+pub fn confusion_matrix_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, column: usize) -> ConfusionMatrix<f64> {
+    assert_eq!(y_true.dim(), y_pred.dim(), "Shapes of y_true and y_pred do not match!");
+    assert_eq!(sample_weight.len(), y_true.nrows(), "One sample weight per row is required!");
+    let (t, p) = (y_true.column(column), y_pred.column(column));
+    let labels = labels(t, p);
+
+    let mut matrix = Array2::<f64>::zeros((labels.len(), labels.len()));
+    for ((a, b), w) in t.iter().zip(p.iter()).zip(sample_weight.iter()) {
+        matrix[(position(&labels, *a), position(&labels, *b))] += w;
+    }
+    ConfusionMatrix { labels, matrix }
+}
+
+struct ClassCounts {
+    tp: f64,
+    fp: f64,
+    fn_: f64,
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 { 0.0 } else { numerator / denominator }
+}
+
+fn precision(c: &ClassCounts) -> f64 {
+    ratio(c.tp, c.tp + c.fp)
+}
+
+fn recall(c: &ClassCounts) -> f64 {
+    ratio(c.tp, c.tp + c.fn_)
+}
+
+fn f1(c: &ClassCounts) -> f64 {
+    ratio(2.0 * c.tp, 2.0 * c.tp + c.fp + c.fn_)
+}
+
+/// Weight of row `row`, 1 without sample weights
+fn row_weight(weights: Option<ArrayView1<f64>>, row: usize) -> f64 {
+    weights.map_or(1.0, |w| w[row])
+}
+
+/// Mann-Whitney U statistic over (weighted) positive/negative pairs, a tie counting one half
+fn roc_auc(y_true: ArrayView1<f64>, y_score: ArrayView1<f64>, weights: Option<ArrayView1<f64>>) -> f64 {
+    let mut rows: Vec<usize> = (0..y_true.len()).collect();
+    rows.sort_by(|&a, &b| y_score[a].total_cmp(&y_score[b]));
+
+    let (mut negatives_below, mut positives, mut area) = (0.0, 0.0, 0.0);
+    let mut i = 0;
+    while i < rows.len() {
+        let (mut tied_positives, mut tied_negatives) = (0.0, 0.0);
+        let mut j = i;
+        while j < rows.len() && y_score[rows[j]] == y_score[rows[i]] {
+            let w = row_weight(weights, rows[j]);
+            if y_true[rows[j]] == 1.0 { tied_positives += w } else { tied_negatives += w }
+            j += 1;
+        }
+        area += tied_positives * (negatives_below + 0.5 * tied_negatives);
+        negatives_below += tied_negatives;
+        positives += tied_positives;
+        i = j;
+    }
+    if positives == 0.0 || negatives_below == 0.0 {
+        return f64::NAN;
+    }
+    area / (positives * negatives_below)
+}
+
+/// Average precision, stepping through the scores from the highest down
+fn pr_auc(y_true: ArrayView1<f64>, y_score: ArrayView1<f64>, weights: Option<ArrayView1<f64>>) -> f64 {
+    let mut rows: Vec<usize> = (0..y_true.len()).collect();
+    rows.sort_by(|&a, &b| y_score[b].total_cmp(&y_score[a]));
+
+    let positives: f64 = rows.iter().filter(|&&r| y_true[r] == 1.0).map(|&r| row_weight(weights, r)).sum();
+    if positives == 0.0 {
+        return f64::NAN;
+    }
+
+    let (mut tp, mut seen, mut previous_recall, mut area) = (0.0, 0.0, 0.0, 0.0);
+    let mut i = 0;
+    while i < rows.len() {
+        let mut j = i;
+        while j < rows.len() && y_score[rows[j]] == y_score[rows[i]] {
+            let w = row_weight(weights, rows[j]);
+            tp += if y_true[rows[j]] == 1.0 { w } else { 0.0 };
+            seen += w;
+            j += 1;
+        }
+        if seen > 0.0 {
+            let recall = tp / positives;
+            area += (recall - previous_recall) * (tp / seen);
+            previous_recall = recall;
+        }
+        i = j;
+    }
+    area
+}
+
+/// Sorted union of the labels; panics on a non-finite label, which matches no class
+fn labels(y_true: ArrayView1<f64>, y_pred: ArrayView1<f64>) -> Vec<f64> {
+    assert!(y_true.iter().chain(y_pred.iter()).all(|l| l.is_finite()), "Class labels must be finite!");
+    let mut labels: Vec<f64> = y_true.iter().chain(y_pred.iter()).copied().collect();
+    labels.sort_by(|a, b| a.total_cmp(b));
+    labels.dedup();
+    labels
+}
+
+fn position(labels: &[f64], label: f64) -> usize {
+    labels.iter().position(|&l| l == label).expect("Label missing from the union of labels")
+}
+
+fn counts(y_true: ArrayView1<f64>, y_pred: ArrayView1<f64>, weights: Option<ArrayView1<f64>>, label: f64) -> ClassCounts {
+    let mut c = ClassCounts { tp: 0.0, fp: 0.0, fn_: 0.0 };
+    for (row, (&a, &b)) in y_true.iter().zip(y_pred.iter()).enumerate() {
+        let w = row_weight(weights, row);
+        match (a == label, b == label) {
+            (true, true) => c.tp += w,
+            (false, true) => c.fp += w,
+            (true, false) => c.fn_ += w,
+            (false, false) => {}
+        }
+    }
+    c
+}
+
+fn averaged<F>(y_true: ArrayView1<f64>, y_pred: ArrayView1<f64>, weights: Option<ArrayView1<f64>>, average: Average, score: F) -> f64
+where
+    F: Fn(&ClassCounts) -> f64,
+{
+    let labels = labels(y_true, y_pred);
+    match average {
+        Average::Binary(positive) => score(&counts(y_true, y_pred, weights, positive)),
+        Average::Micro => {
+            let per_class: Vec<ClassCounts> = labels.iter().map(|&l| counts(y_true, y_pred, weights, l)).collect();
+            score(&ClassCounts {
+                tp: per_class.iter().map(|c| c.tp).sum(),
+                fp: per_class.iter().map(|c| c.fp).sum(),
+                fn_: per_class.iter().map(|c| c.fn_).sum(),
+            })
+        }
+        Average::Macro => {
+            labels.iter().map(|&l| score(&counts(y_true, y_pred, weights, l))).sum::<f64>() / labels.len() as f64
+        }
+        Average::Weighted => {
+            let total = weights.map_or(y_true.len() as f64, |w| w.sum());
+            labels
+                .iter()
+                .map(|&l| {
+                    let c = counts(y_true, y_pred, weights, l);
+                    score(&c) * (c.tp + c.fn_) / total
+                })
+                .sum()
+        }
+    }
+}
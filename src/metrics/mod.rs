@@ -0,0 +1,71 @@
+mod regression;
+mod classification;
+mod scorer;
+
+pub use regression::*;
+pub use classification::*;
+pub use scorer::*;
+
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+
+/// How a metric computed per output column is reduced to a single value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MultiOutput {
+    UniformAverage,   // Plain mean over output columns
+    VarianceWeighted, // Mean weighted by the variance of each `y_true` column
+    Output(usize),    // Value of a single output column
+}
+
+/// Applies `metric` to every output column and reduces the values as requested
+pub(crate) fn aggregate<F>(
+    y_true: &Array2<f64>,
+    y_pred: &Array2<f64>,
+    multioutput: MultiOutput,
+    metric: F,
+) -> f64
+where
+    F: Fn(ArrayView1<f64>, ArrayView1<f64>) -> f64,
+{
+    assert_eq!(y_true.dim(), y_pred.dim(), "Shapes of y_true and y_pred do not match!");
+
+    let columns = y_true.axis_iter(Axis(1)).zip(y_pred.axis_iter(Axis(1)));
+    match multioutput {
+        MultiOutput::Output(col) => metric(y_true.column(col), y_pred.column(col)),
+        MultiOutput::UniformAverage => {
+            let values: Vec<f64> = columns.map(|(t, p)| metric(t, p)).collect();
+            values.iter().sum::<f64>() / values.len() as f64
+        }
+        MultiOutput::VarianceWeighted => {
+            let weights: Vec<f64> = y_true.axis_iter(Axis(1)).map(|t| t.var(0.0)).collect();
+            let total: f64 = weights.iter().sum();
+            if total == 0.0 {
+                return aggregate(y_true, y_pred, MultiOutput::UniformAverage, metric);
+            }
+            columns
+                .zip(weights.iter())
+                .map(|((t, p), w)| w * metric(t, p))
+                .sum::<f64>()
+                / total
+        }
+    }
+}
+
+/// `aggregate` for metrics weighting every row by `sample_weight`
+pub(crate) fn aggregate_weighted<F>(
+    y_true: &Array2<f64>,
+    y_pred: &Array2<f64>,
+    sample_weight: &Array1<f64>,
+    multioutput: MultiOutput,
+    metric: F,
+) -> f64
+where
+    F: Fn(ArrayView1<f64>, ArrayView1<f64>, ArrayView1<f64>) -> f64,
+{
+    assert_eq!(sample_weight.len(), y_true.nrows(), "One sample weight per row is required!");
+    aggregate(y_true, y_pred, multioutput, |t, p| metric(t, p, sample_weight.view()))
+}
+
+/// Mean of `values` weighted by `weights`
+pub(crate) fn weighted_mean(values: impl Iterator<Item = f64>, weights: ArrayView1<f64>) -> f64 {
+    values.zip(weights.iter()).map(|(value, w)| w * value).sum::<f64>() / weights.sum()
+}
@@ -0,0 +1,188 @@
+use ndarray::{Array1, Array2, ArrayView1};
+use crate::metrics::{aggregate, aggregate_weighted, weighted_mean, MultiOutput};
+
+/// Coefficient of determination R²
+///
+/// A constant `y_true` column scores 1 when predicted exactly and 0 otherwise.
+pub fn r2_score(y_true: &Array2<f64>, y_pred: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, r2)
+}
+
+/// R² with every row weighted by `sample_weight`, around the weighted mean of `y_true`
+pub fn r2_score_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| {
+        let mean = weighted_mean(t.iter().copied(), w);
+        let ss_res = weighted_mean(t.iter().zip(p.iter()).map(|(a, b)| (a - b).powi(2)), w);
+        let ss_tot = weighted_mean(t.iter().map(|a| (a - mean).powi(2)), w);
+        if ss_tot == 0.0 {
+            return if ss_res == 0.0 { 1.0 } else { 0.0 };
+        }
+        1.0 - ss_res / ss_tot
+    })
+}
+
+/// R² adjusted for the number of input features used by the model
+///
+/// Returns `NaN` unless there are more samples than `n_features + 1`.
+pub fn adjusted_r2_score(
+    y_true: &Array2<f64>,
+    y_pred: &Array2<f64>,
+    n_features: usize,
+    multioutput: MultiOutput,
+) -> f64 {
+    let n = y_true.nrows() as f64;
+    let p = n_features as f64;
+    if n - p - 1.0 <= 0.0 {
+        return f64::NAN;
+    }
+    aggregate(y_true, y_pred, multioutput, |t, p_| {
+        1.0 - (1.0 - r2(t, p_)) * (n - 1.0) / (n - p - 1.0)
+    })
+}
+
+/// Root mean squared error
+pub fn root_mean_squared_error(y_true: &Array2<f64>, y_pred: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| {
+        (t.iter().zip(p.iter()).map(|(a, b)| (a - b).powi(2)).sum::<f64>() / t.len() as f64).sqrt()
+    })
+}
+
+/// Root mean squared error with every row weighted by `sample_weight`
+pub fn root_mean_squared_error_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| {
+        weighted_mean(t.iter().zip(p.iter()).map(|(a, b)| (a - b).powi(2)), w).sqrt()
+    })
+}
+
+/// Mean absolute error
+pub fn mean_absolute_error(y_true: &Array2<f64>, y_pred: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| {
+        t.iter().zip(p.iter()).map(|(a, b)| (a - b).abs()).sum::<f64>() / t.len() as f64
+    })
+}
+
+/// Mean absolute error with every row weighted by `sample_weight`
+pub fn mean_absolute_error_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| {
+        weighted_mean(t.iter().zip(p.iter()).map(|(a, b)| (a - b).abs()), w)
+    })
+}
+
+/// Mean absolute percentage error, as a fraction (0.1 is 10%)
+///
+/// `y_true` values of magnitude below `f64::EPSILON` are clamped to avoid division by zero.
+pub fn mean_absolute_percentage_error(
+    y_true: &Array2<f64>,
+    y_pred: &Array2<f64>,
+    multioutput: MultiOutput,
+) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| {
+        t.iter()
+            .zip(p.iter())
+            .map(|(a, b)| (a - b).abs() / a.abs().max(f64::EPSILON))
+            .sum::<f64>()
+            / t.len() as f64
+    })
+}
+
+/// Mean absolute percentage error with every row weighted by `sample_weight`
+pub fn mean_absolute_percentage_error_weighted(
+    y_true: &Array2<f64>,
+    y_pred: &Array2<f64>,
+    sample_weight: &Array1<f64>,
+    multioutput: MultiOutput,
+) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| {
+        weighted_mean(t.iter().zip(p.iter()).map(|(a, b)| (a - b).abs() / a.abs().max(f64::EPSILON)), w)
+    })
+}
+
+/// Median absolute error, robust to outliers; `NaN` without rows
+pub fn median_absolute_error(y_true: &Array2<f64>, y_pred: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| {
+        let mut errors: Vec<f64> = t.iter().zip(p.iter()).map(|(a, b)| (a - b).abs()).collect();
+        errors.sort_by(|a, b| a.total_cmp(b));
+        let mid = errors.len() / 2;
+        if errors.is_empty() {
+            f64::NAN
+        } else if errors.len().is_multiple_of(2) {
+            (errors[mid - 1] + errors[mid]) / 2.0
+        } else {
+            errors[mid]
+        }
+    })
+}
+
+/// Weighted median absolute error: the error at which the sorted rows reach half the total weight
+///
+/// When the cumulative weight lands exactly on half, the two neighbouring errors are averaged,
+/// so unit weights give `median_absolute_error`. `NaN` without rows or weight.
+pub fn median_absolute_error_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| {
+        let mut errors: Vec<(f64, f64)> = t.iter().zip(p.iter()).zip(w.iter()).map(|((a, b), &w)| ((a - b).abs(), w)).filter(|(_, w)| *w > 0.0).collect();
+        errors.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let half = errors.iter().map(|(_, w)| w).sum::<f64>() / 2.0;
+        let mut cumulative = 0.0;
+        for (i, (error, w)) in errors.iter().enumerate() {
+            cumulative += w;
+            if cumulative == half {
+                return (error + errors[i + 1].0) / 2.0;
+            }
+            if cumulative > half {
+                return *error;
+            }
+        }
+        f64::NAN
+    })
+}
+
+/// Explained variance score, like R² but ignoring a constant bias in the residuals
+pub fn explained_variance_score(y_true: &Array2<f64>, y_pred: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| {
+        let residual = &t - &p;
+        let denominator = t.var(0.0);
+        let numerator = residual.var(0.0);
+        if denominator == 0.0 {
+            return if numerator == 0.0 { 1.0 } else { 0.0 };
+        }
+        1.0 - numerator / denominator
+    })
+}
+
+/// Explained variance score with means and variances weighted by `sample_weight`
+pub fn explained_variance_score_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| {
+        let residual = &t - &p;
+        let (mean_true, mean_residual) = (weighted_mean(t.iter().copied(), w), weighted_mean(residual.iter().copied(), w));
+        let denominator = weighted_mean(t.iter().map(|a| (a - mean_true).powi(2)), w);
+        let numerator = weighted_mean(residual.iter().map(|r| (r - mean_residual).powi(2)), w);
+        if denominator == 0.0 {
+            return if numerator == 0.0 { 1.0 } else { 0.0 };
+        }
+        1.0 - numerator / denominator
+    })
+}
+
+/// Largest absolute error
+pub fn max_error(y_true: &Array2<f64>, y_pred: &Array2<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate(y_true, y_pred, multioutput, |t, p| {
+        t.iter().zip(p.iter()).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max)
+    })
+}
+
+/// Largest absolute error among rows of positive `sample_weight`
+pub fn max_error_weighted(y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>, multioutput: MultiOutput) -> f64 {
+    aggregate_weighted(y_true, y_pred, sample_weight, multioutput, |t, p, w| {
+        t.iter().zip(p.iter()).zip(w.iter()).filter(|(_, w)| **w > 0.0).map(|((a, b), _)| (a - b).abs()).fold(0.0, f64::max)
+    })
+}
+
+fn r2(y_true: ArrayView1<f64>, y_pred: ArrayView1<f64>) -> f64 {
+    let mean = y_true.mean().unwrap_or(0.0);
+    let ss_res: f64 = y_true.iter().zip(y_pred.iter()).map(|(a, b)| (a - b).powi(2)).sum();
+    let ss_tot: f64 = y_true.iter().map(|a| (a - mean).powi(2)).sum();
+    if ss_tot == 0.0 {
+        return if ss_res == 0.0 { 1.0 } else { 0.0 };
+    }
+    1.0 - ss_res / ss_tot
+}
@@ -0,0 +1,91 @@
+use ndarray::{Array1, Array2};
+use crate::metrics::*;
+
+/// Metric used to score predictions during cross-validation and search
+///
+/// Every scorer averages uniformly over output columns. All but `Custom` can also weight rows,
+/// see `score_weighted`.
+#[derive(Debug, Clone, Copy)]
+pub enum Scorer {
+    R2,
+    RootMeanSquaredError,
+    MeanAbsoluteError,
+    MeanAbsolutePercentageError,
+    MedianAbsoluteError,
+    ExplainedVariance,
+    MaxError,
+    RocAuc, // Predictions are used as scores of the positive class
+    PrAuc,
+    Custom {
+        name: &'static str,
+        metric: fn(&Array2<f64>, &Array2<f64>) -> f64,
+        greater_is_better: bool,
+    },
+    CustomWeighted { // Called with unit weights by `score`
+        name: &'static str,
+        metric: fn(&Array2<f64>, &Array2<f64>, &Array1<f64>) -> f64,
+        greater_is_better: bool,
+    },
+}
+
+impl Scorer {
+    pub fn score(&self, y_true: &Array2<f64>, y_pred: &Array2<f64>) -> f64 {
+        let avg = MultiOutput::UniformAverage;
+        match self {
+            Scorer::R2 => r2_score(y_true, y_pred, avg),
+            Scorer::RootMeanSquaredError => root_mean_squared_error(y_true, y_pred, avg),
+            Scorer::MeanAbsoluteError => mean_absolute_error(y_true, y_pred, avg),
+            Scorer::MeanAbsolutePercentageError => mean_absolute_percentage_error(y_true, y_pred, avg),
+            Scorer::MedianAbsoluteError => median_absolute_error(y_true, y_pred, avg),
+            Scorer::ExplainedVariance => explained_variance_score(y_true, y_pred, avg),
+            Scorer::MaxError => max_error(y_true, y_pred, avg),
+            Scorer::RocAuc => roc_auc_score(y_true, y_pred, avg),
+            Scorer::PrAuc => pr_auc_score(y_true, y_pred, avg),
+            Scorer::Custom { metric, .. } => metric(y_true, y_pred),
+            Scorer::CustomWeighted { metric, .. } => metric(y_true, y_pred, &Array1::ones(y_true.nrows())),
+        }
+    }
+
+    /// Score with every row counting its `sample_weight`; `None` for a `Custom` scorer, which cannot weight rows
+    pub fn score_weighted(&self, y_true: &Array2<f64>, y_pred: &Array2<f64>, sample_weight: &Array1<f64>) -> Option<f64> {
+        let avg = MultiOutput::UniformAverage;
+        let w = sample_weight;
+        Some(match self {
+            Scorer::R2 => r2_score_weighted(y_true, y_pred, w, avg),
+            Scorer::RootMeanSquaredError => root_mean_squared_error_weighted(y_true, y_pred, w, avg),
+            Scorer::MeanAbsoluteError => mean_absolute_error_weighted(y_true, y_pred, w, avg),
+            Scorer::MeanAbsolutePercentageError => mean_absolute_percentage_error_weighted(y_true, y_pred, w, avg),
+            Scorer::MedianAbsoluteError => median_absolute_error_weighted(y_true, y_pred, w, avg),
+            Scorer::ExplainedVariance => explained_variance_score_weighted(y_true, y_pred, w, avg),
+            Scorer::MaxError => max_error_weighted(y_true, y_pred, w, avg),
+            Scorer::RocAuc => roc_auc_score_weighted(y_true, y_pred, w, avg),
+            Scorer::PrAuc => pr_auc_score_weighted(y_true, y_pred, w, avg),
+            Scorer::Custom { .. } => return None,
+            Scorer::CustomWeighted { metric, .. } => metric(y_true, y_pred, w),
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scorer::R2 => "r2",
+            Scorer::RootMeanSquaredError => "rmse",
+            Scorer::MeanAbsoluteError => "mae",
+            Scorer::MeanAbsolutePercentageError => "mape",
+            Scorer::MedianAbsoluteError => "median_ae",
+            Scorer::ExplainedVariance => "explained_variance",
+            Scorer::MaxError => "max_error",
+            Scorer::RocAuc => "roc_auc",
+            Scorer::PrAuc => "pr_auc",
+            Scorer::Custom { name, .. } | Scorer::CustomWeighted { name, .. } => name,
+        }
+    }
+
+    /// Whether higher values are better (R², AUC) rather than worse (errors)
+    pub fn greater_is_better(&self) -> bool {
+        match self {
+            Scorer::R2 | Scorer::ExplainedVariance | Scorer::RocAuc | Scorer::PrAuc => true,
+            Scorer::Custom { greater_is_better, .. } | Scorer::CustomWeighted { greater_is_better, .. } => *greater_is_better,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::error::Error;
+use std::time::{Duration, Instant};
+use ndarray::{Array1, Array2, Axis};
+use crate::metrics::Scorer;
+use crate::model_selection::Splitter;
+use crate::multivariate_regression::training::train::check_sample_weight;
+use crate::traits::{Fit, FitWeighted, Predict};
+
+/// Scores and timings of one cross-validation fold
+#[derive(Debug, Clone)]
+pub struct FoldResult {
+    pub train_size: usize,
+    pub test_size: usize,
+    pub scores: Vec<f64>, // One score per scorer, in the order they were given
+    pub fit_time: Duration,
+    pub score_time: Duration,
+}
+
+/// Per-fold results of `cross_validate`
+#[derive(Debug, Clone)]
+pub struct CrossValidationResult {
+    pub scorer_names: Vec<&'static str>,
+    pub folds: Vec<FoldResult>,
+}
+
+impl CrossValidationResult {
+    /// Scores of scorer `scorer` on every fold
+    pub fn scores(&self, scorer: usize) -> Vec<f64> {
+        self.folds.iter().map(|fold| fold.scores[scorer]).collect()
+    }
+
+    pub fn mean_score(&self, scorer: usize) -> f64 {
+        let scores = self.scores(scorer);
+        scores.iter().sum::<f64>() / scores.len() as f64
+    }
+
+    /// Population standard deviation of the fold scores
+    pub fn std_score(&self, scorer: usize) -> f64 {
+        let mean = self.mean_score(scorer);
+        let scores = self.scores(scorer);
+        (scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / scores.len() as f64).sqrt()
+    }
+
+    pub fn total_fit_time(&self) -> Duration {
+        self.folds.iter().map(|fold| fold.fit_time).sum()
+    }
+
+    pub fn print(&self) {
+        for (i, fold) in self.folds.iter().enumerate() {
+            let scores: Vec<String> = self
+                .scorer_names
+                .iter()
+                .zip(fold.scores.iter())
+                .map(|(name, score)| format!("{}: {:.6}", name, score))
+                .collect();
+            println!("Fold {} ({} train / {} test, fit {:?}): {}", i, fold.train_size, fold.test_size, fold.fit_time, scores.join(", "));
+        }
+        for (i, name) in self.scorer_names.iter().enumerate() {
+            println!("{}: {:.6} ± {:.6}", name, self.mean_score(i), self.std_score(i));
+        }
+    }
+}
+
+/// Function to evaluate a model with cross-validation
+///
+/// # Parameters:
+/// - `model_factory: F` - Builds a fresh, unfitted model for every fold (e.g. a `LinearRegression`)
+/// - `x: &Array2<f64>` - Feature matrix
+/// - `y: &Array2<f64>` - Target matrix
+/// - `splitter: &S` - Strategy producing the train/test index sets
+/// - `scorers: &[Scorer]` - Metrics computed on every test fold
+///
+/// # Returns:
+/// - `Result<CrossValidationResult, Box<dyn Error>>` - Per-fold scores and timings
+pub fn cross_validate<F, E, S>(
+    model_factory: F,
+    x: &Array2<f64>,
+    y: &Array2<f64>,
+    splitter: &S,
+    scorers: &[Scorer],
+) -> Result<CrossValidationResult, Box<dyn Error>>
+where
+    F: Fn() -> E,
+    E: Fit + Predict,
+    S: Splitter + ?Sized,
+{
+    run_folds(model_factory, x, y, None, splitter, scorers, |model, x, y, _| model.fit(x, y))
+}
+
+/// Function to evaluate a model with cross-validation when rows carry sample weights
+///
+/// Every fold fits on the weights of its training rows and scores with the weights of its test rows.
+///
+/// # Parameters:
+/// - `model_factory: F` - Builds a fresh, unfitted model for every fold
+/// - `x: &Array2<f64>` - Feature matrix
+/// - `y: &Array2<f64>` - Target matrix
+/// - `sample_weight: &Array1<f64>` - Non-negative weight of every row
+/// - `splitter: &S` - Strategy producing the train/test index sets
+/// - `scorers: &[Scorer]` - Metrics computed on every test fold; `Scorer::Custom` cannot weight rows and is rejected
+///
+/// # Returns:
+/// - `Result<CrossValidationResult, Box<dyn Error>>` - Per-fold weighted scores and timings
+pub fn cross_validate_weighted<F, E, S>(
+    model_factory: F,
+    x: &Array2<f64>,
+    y: &Array2<f64>,
+    sample_weight: &Array1<f64>,
+    splitter: &S,
+    scorers: &[Scorer],
+) -> Result<CrossValidationResult, Box<dyn Error>>
+where
+    F: Fn() -> E,
+    E: FitWeighted + Predict,
+    S: Splitter + ?Sized,
+{
+    check_sample_weight(sample_weight, x.nrows())?;
+    if let Some(scorer) = scorers.iter().find(|scorer| matches!(scorer, Scorer::Custom { .. })) {
+        return Err(format!("Scorer '{}' cannot weight rows, use Scorer::CustomWeighted", scorer.name()).into());
+    }
+    run_folds(model_factory, x, y, Some(sample_weight), splitter, scorers, |model, x, y, w| {
+        model.fit_weighted(x, y, w.expect("Weighted folds carry their weights"))
+    })
+}
+
+/// Fits and scores every fold of `splitter`, with the fold's rows of `sample_weight` when given
+fn run_folds<F, E, S, G>(
+    model_factory: F,
+    x: &Array2<f64>,
+    y: &Array2<f64>,
+    sample_weight: Option<&Array1<f64>>,
+    splitter: &S,
+    scorers: &[Scorer],
+    fit: G,
+) -> Result<CrossValidationResult, Box<dyn Error>>
+where
+    F: Fn() -> E,
+    E: Predict,
+    S: Splitter + ?Sized,
+    G: Fn(&mut E, &Array2<f64>, &Array2<f64>, Option<&Array1<f64>>) -> Result<(), Box<dyn Error>>,
+{
+    if x.nrows() != y.nrows() {
+        return Err(format!("x has {} rows but y has {}", x.nrows(), y.nrows()).into());
+    }
+    if scorers.is_empty() {
+        return Err("At least one scorer is required".into());
+    }
+
+    let mut folds = Vec::new();
+    for fold in splitter.split(x, y)? {
+        if fold.train.is_empty() || fold.test.is_empty() {
+            return Err("Splitter produced an empty train or test set".into());
+        }
+        let (train_weight, test_weight) = match sample_weight {
+            Some(w) => (Some(w.select(Axis(0), &fold.train)), Some(w.select(Axis(0), &fold.test))),
+            None => (None, None),
+        };
+
+        let start = Instant::now();
+        let mut model = model_factory();
+        fit(&mut model, &x.select(Axis(0), &fold.train), &y.select(Axis(0), &fold.train), train_weight.as_ref())?;
+        let fit_time = start.elapsed();
+
+        let start = Instant::now();
+        let y_test = y.select(Axis(0), &fold.test);
+        let y_pred = model.predict(&x.select(Axis(0), &fold.test))?;
+        let scores = scorers
+            .iter()
+            .map(|scorer| match &test_weight {
+                Some(w) => scorer.score_weighted(&y_test, &y_pred, w).unwrap_or(f64::NAN),
+                None => scorer.score(&y_test, &y_pred),
+            })
+            .collect();
+        let score_time = start.elapsed();
+
+        folds.push(FoldResult {
+            train_size: fold.train.len(),
+            test_size: fold.test.len(),
+            scores,
+            fit_time,
+            score_time,
+        });
+    }
+
+    Ok(CrossValidationResult {
+        scorer_names: scorers.iter().map(|scorer| scorer.name()).collect(),
+        folds,
+    })
+}
@@ -0,0 +1,7 @@
+mod splitters;
+mod cross_validation;
+mod search;
+
+pub use splitters::*;
+pub use cross_validation::*;
+pub use search::*;
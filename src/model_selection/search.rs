@@ -0,0 +1,590 @@
+use std::error::Error;
+use std::time::Duration;
+use ndarray::Array2;
+use rand::rngs::StdRng;
+use rand::Rng;
+use crate::metrics::Scorer;
+use crate::model_selection::{cross_validate, Splitter};
+use crate::utils::rng;
+use crate::multivariate_regression::loss::LossFn;
+use crate::multivariate_regression::model::TrainedModel;
+use crate::multivariate_regression::normalization::normalization::NormalizationParameterType;
+use crate::multivariate_regression::regularization::regularization::{Regularization, RegularizationType};
+use crate::multivariate_regression::linear_regression::LinearRegression;
+use crate::multivariate_regression::training::train_config::{TrainConfig, TrainConfigBuilder};
+use crate::multivariate_regression::update_weight::{MiniBatchSize, UpdatationMethod};
+use crate::traits::Fit;
+
+/// One point of a search space: the `TrainConfigBuilder` fields being tuned
+///
+/// `None` fields keep the `train` defaults.
+#[derive(Debug, Clone)]
+pub struct TrainParams {
+    pub epochs: usize,
+    pub lr: f64,
+    pub regularization: Option<Regularization>,
+    pub loss: Option<LossFn>,
+    pub optimizer: Option<UpdatationMethod>,
+    pub mini_batch_size: Option<MiniBatchSize>,
+    pub normalization: Option<NormalizationParameterType>,
+    pub target_normalization: Option<NormalizationParameterType>,
+}
+
+impl Default for TrainParams {
+    fn default() -> Self {
+        let config = TrainConfig::default();
+        Self {
+            epochs: config.epochs,
+            lr: config.lr,
+            regularization: None,
+            loss: None,
+            optimizer: None,
+            mini_batch_size: None,
+            normalization: None,
+            target_normalization: None,
+        }
+    }
+}
+
+impl TrainParams {
+    pub fn to_config(&self) -> TrainConfig {
+        let mut builder = TrainConfigBuilder::new().epochs(self.epochs).learning_rate(self.lr);
+        if let Some(reg) = self.regularization {
+            builder = builder.regularization(reg);
+        }
+        if let Some(loss) = &self.loss {
+            builder = builder.loss(loss.clone());
+        }
+        if let Some(optimizer) = self.optimizer {
+            builder = builder.optimizer(optimizer);
+        }
+        if let Some(size) = self.mini_batch_size {
+            builder = builder.mini_batch_size(size);
+        }
+        if let Some(normalization) = self.normalization {
+            builder = builder.normalization(normalization);
+        }
+        if let Some(normalization) = self.target_normalization {
+            builder = builder.target_normalization(normalization);
+        }
+        builder.build()
+    }
+
+    /// Unfitted `LinearRegression` with these parameters
+    pub fn to_estimator(&self) -> LinearRegression {
+        LinearRegression::new(self.to_config())
+    }
+
+    /// Short human readable description, used in result tables
+    pub fn describe(&self) -> String {
+        let mut parts = vec![format!("epochs={}", self.epochs), format!("lr={:.3e}", self.lr)];
+        if let Some(reg) = self.regularization {
+            parts.push(match reg.regularization_type {
+                RegularizationType::LassoL1 => format!("l1={:.3e}", reg.lambda1),
+                RegularizationType::RidgeL2 => format!("l2={:.3e}", reg.lambda2),
+                RegularizationType::ElasticNet => format!("elastic_net=({:.3e}, {:.3e})", reg.lambda1, reg.lambda2),
+            });
+        }
+        if let Some(loss) = &self.loss {
+            parts.push(format!("loss={:?}", loss));
+        }
+        if let Some(optimizer) = self.optimizer {
+            parts.push(format!("optimizer={:?}", optimizer));
+        }
+        if let Some(size) = self.mini_batch_size {
+            parts.push(format!("mini_batch_size={:?}", size));
+        }
+        if let Some(normalization) = self.normalization {
+            parts.push(format!("normalization={:?}", normalization));
+        }
+        if let Some(normalization) = self.target_normalization {
+            parts.push(format!("target_normalization={:?}", normalization));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Exhaustive search space; every combination of the listed values is a candidate
+///
+/// Empty lists leave the field at the value of `base`.
+#[derive(Debug, Clone, Default)]
+pub struct ParamGrid {
+    pub base: TrainParams,
+    pub epochs: Vec<usize>,
+    pub learning_rate: Vec<f64>,
+    pub regularization: Vec<Regularization>,
+    pub loss: Vec<LossFn>,
+    pub optimizer: Vec<UpdatationMethod>,
+    pub mini_batch_size: Vec<MiniBatchSize>,
+    pub normalization: Vec<NormalizationParameterType>,
+}
+
+impl ParamGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn base(mut self, base: TrainParams) -> Self {
+        self.base = base;
+        self
+    }
+
+    pub fn epochs(mut self, epochs: Vec<usize>) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    pub fn learning_rate(mut self, lr: Vec<f64>) -> Self {
+        self.learning_rate = lr;
+        self
+    }
+
+    pub fn regularization(mut self, reg: Vec<Regularization>) -> Self {
+        self.regularization = reg;
+        self
+    }
+
+    pub fn loss(mut self, loss: Vec<LossFn>) -> Self {
+        self.loss = loss;
+        self
+    }
+
+    pub fn optimizer(mut self, optimizer: Vec<UpdatationMethod>) -> Self {
+        self.optimizer = optimizer;
+        self
+    }
+
+    pub fn mini_batch_size(mut self, size: Vec<MiniBatchSize>) -> Self {
+        self.mini_batch_size = size;
+        self
+    }
+
+    pub fn normalization(mut self, normalization: Vec<NormalizationParameterType>) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Cartesian product of all listed values
+    pub fn candidates(&self) -> Vec<TrainParams> {
+        fn expand<T: Clone>(
+            candidates: Vec<TrainParams>,
+            values: &[T],
+            set: impl Fn(&mut TrainParams, T),
+        ) -> Vec<TrainParams> {
+            if values.is_empty() {
+                return candidates;
+            }
+            candidates
+                .iter()
+                .flat_map(|candidate| {
+                    values.iter().map(|value| {
+                        let mut candidate = candidate.clone();
+                        set(&mut candidate, value.clone());
+                        candidate
+                    })
+                })
+                .collect()
+        }
+
+        let mut candidates = vec![self.base.clone()];
+        candidates = expand(candidates, &self.epochs, |p, v| p.epochs = v);
+        candidates = expand(candidates, &self.learning_rate, |p, v| p.lr = v);
+        candidates = expand(candidates, &self.regularization, |p, v| p.regularization = Some(v));
+        candidates = expand(candidates, &self.loss, |p, v| p.loss = Some(v));
+        candidates = expand(candidates, &self.optimizer, |p, v| p.optimizer = Some(v));
+        candidates = expand(candidates, &self.mini_batch_size, |p, v| p.mini_batch_size = Some(v));
+        candidates = expand(candidates, &self.normalization, |p, v| p.normalization = Some(v));
+        candidates
+    }
+}
+
+/// Distribution of a real valued parameter
+#[derive(Debug, Clone)]
+pub enum FloatDistribution {
+    Choice(Vec<f64>),
+    Uniform(f64, f64),    // Uniform on [low, high)
+    LogUniform(f64, f64), // Uniform in log space, both bounds positive
+}
+
+impl FloatDistribution {
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        match self {
+            FloatDistribution::Choice(values) => values[rng.gen_range(0..values.len())],
+            FloatDistribution::Uniform(low, high) => rng.gen_range(*low..*high),
+            FloatDistribution::LogUniform(low, high) => rng.gen_range(low.ln()..high.ln()).exp(),
+        }
+    }
+
+    fn check(&self) -> Result<(), Box<dyn Error>> {
+        match self {
+            FloatDistribution::Choice(values) if values.is_empty() => Err("Choice distribution is empty".into()),
+            FloatDistribution::Uniform(low, high) if low >= high => {
+                Err(format!("Invalid uniform range [{}, {})", low, high).into())
+            }
+            FloatDistribution::LogUniform(low, high) if *low <= 0. || low >= high => {
+                Err(format!("Invalid log-uniform range [{}, {})", low, high).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Distribution of an integer parameter
+#[derive(Debug, Clone)]
+pub enum IntDistribution {
+    Choice(Vec<usize>),
+    Range(usize, usize), // Uniform on [low, high]
+}
+
+impl IntDistribution {
+    fn sample(&self, rng: &mut StdRng) -> usize {
+        match self {
+            IntDistribution::Choice(values) => values[rng.gen_range(0..values.len())],
+            IntDistribution::Range(low, high) => rng.gen_range(*low..=*high),
+        }
+    }
+
+    fn check(&self) -> Result<(), Box<dyn Error>> {
+        match self {
+            IntDistribution::Choice(values) if values.is_empty() => Err("Choice distribution is empty".into()),
+            IntDistribution::Range(low, high) if low > high => {
+                Err(format!("Invalid integer range [{}, {}]", low, high).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Distribution over regularizations: a type is picked from `types`, then its lambdas are sampled
+#[derive(Debug, Clone)]
+pub struct RegularizationDistribution {
+    pub types: Vec<RegularizationType>,
+    pub lambda1: FloatDistribution,
+    pub lambda2: FloatDistribution,
+}
+
+/// Random search space; `None` fields keep the value of `base`
+#[derive(Debug, Clone, Default)]
+pub struct ParamDistributions {
+    pub base: TrainParams,
+    pub epochs: Option<IntDistribution>,
+    pub learning_rate: Option<FloatDistribution>,
+    pub regularization: Option<RegularizationDistribution>,
+    pub loss: Vec<LossFn>, // Picked uniformly when non-empty
+    pub optimizer: Vec<UpdatationMethod>,
+}
+
+impl ParamDistributions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn base(mut self, base: TrainParams) -> Self {
+        self.base = base;
+        self
+    }
+
+    pub fn epochs(mut self, epochs: IntDistribution) -> Self {
+        self.epochs = Some(epochs);
+        self
+    }
+
+    pub fn learning_rate(mut self, lr: FloatDistribution) -> Self {
+        self.learning_rate = Some(lr);
+        self
+    }
+
+    pub fn regularization(mut self, reg: RegularizationDistribution) -> Self {
+        self.regularization = Some(reg);
+        self
+    }
+
+    pub fn loss(mut self, loss: Vec<LossFn>) -> Self {
+        self.loss = loss;
+        self
+    }
+
+    pub fn optimizer(mut self, optimizer: Vec<UpdatationMethod>) -> Self {
+        self.optimizer = optimizer;
+        self
+    }
+
+    /// Draws `n_iter` candidates, reproducibly when `seed` is given
+    pub fn sample(&self, n_iter: usize, seed: Option<u64>) -> Result<Vec<TrainParams>, Box<dyn Error>> {
+        if let Some(epochs) = &self.epochs {
+            epochs.check()?;
+        }
+        if let Some(lr) = &self.learning_rate {
+            lr.check()?;
+        }
+        if let Some(reg) = &self.regularization {
+            if reg.types.is_empty() {
+                return Err("Regularization distribution has no types".into());
+            }
+            reg.lambda1.check()?;
+            reg.lambda2.check()?;
+        }
+
+        let mut rng = rng(seed);
+        Ok((0..n_iter)
+            .map(|_| {
+                let mut params = self.base.clone();
+                if let Some(epochs) = &self.epochs {
+                    params.epochs = epochs.sample(&mut rng);
+                }
+                if let Some(lr) = &self.learning_rate {
+                    params.lr = lr.sample(&mut rng);
+                }
+                if let Some(reg) = &self.regularization {
+                    params.regularization = Some(match reg.types[rng.gen_range(0..reg.types.len())] {
+                        RegularizationType::LassoL1 => Regularization::l1(reg.lambda1.sample(&mut rng)),
+                        RegularizationType::RidgeL2 => Regularization::l2(reg.lambda2.sample(&mut rng)),
+                        RegularizationType::ElasticNet => {
+                            Regularization::elastic_net(reg.lambda1.sample(&mut rng), reg.lambda2.sample(&mut rng))
+                        }
+                    });
+                }
+                if !self.loss.is_empty() {
+                    params.loss = Some(self.loss[rng.gen_range(0..self.loss.len())].clone());
+                }
+                if !self.optimizer.is_empty() {
+                    params.optimizer = Some(self.optimizer[rng.gen_range(0..self.optimizer.len())]);
+                }
+                params
+            })
+            .collect())
+    }
+}
+
+/// Cross-validated score of one candidate
+#[derive(Debug, Clone)]
+pub struct SearchEntry {
+    pub params: TrainParams,
+    pub rank: usize,          // 1 is best
+    pub round: usize,         // Successive-halving round the candidate was last evaluated in
+    pub epochs_used: usize,   // Lower than `params.epochs` for candidates dropped by successive halving
+    pub mean_score: f64,
+    pub std_score: f64,
+    pub fold_scores: Vec<f64>,
+    pub fit_time: Duration,   // Total over all folds
+}
+
+/// Ranked outcome of a search, best candidate first
+pub struct SearchResult {
+    pub scorer_name: &'static str,
+    pub entries: Vec<SearchEntry>,
+    pub best_model: Option<TrainedModel>, // Refit on all rows when refitting is enabled
+}
+
+impl SearchResult {
+    pub fn best(&self) -> &SearchEntry {
+        &self.entries[0]
+    }
+
+    pub fn best_params(&self) -> &TrainParams {
+        &self.entries[0].params
+    }
+
+    pub fn print(&self) {
+        println!("{:>4}  {:>12}  {:>10}  {:>7}  params", "rank", self.scorer_name, "std", "epochs");
+        for entry in &self.entries {
+            println!(
+                "{:>4}  {:>12.6}  {:>10.6}  {:>7}  {}",
+                entry.rank,
+                entry.mean_score,
+                entry.std_score,
+                entry.epochs_used,
+                entry.params.describe()
+            );
+        }
+    }
+}
+
+/// Cross-validated hyperparameter search
+///
+/// Candidates come from a `ParamGrid` (`grid`) or `ParamDistributions` (`random`).
+/// With `halving` enabled, epochs are the budget: all candidates start with few
+/// epochs and only the best `1 / factor` of each round continue with `factor`
+/// times as many. Rows are unweighted; evaluate a chosen candidate with
+/// `cross_validate_weighted` when rows carry sample weights.
+pub struct SearchCV<'a, S: Splitter + Sync + ?Sized> {
+    splitter: &'a S,
+    scorer: Scorer,
+    n_jobs: usize,
+    refit: bool,
+    halving: Option<(usize, usize)>, // (factor, min_epochs)
+}
+
+impl<'a, S: Splitter + Sync + ?Sized> SearchCV<'a, S> {
+    pub fn new(splitter: &'a S, scorer: Scorer) -> Self {
+        Self { splitter, scorer, n_jobs: 1, refit: true, halving: None }
+    }
+
+    /// Number of candidates evaluated concurrently
+    pub fn n_jobs(mut self, n_jobs: usize) -> Self {
+        self.n_jobs = n_jobs.max(1);
+        self
+    }
+
+    /// Whether to train the best candidate on all rows afterwards
+    pub fn refit(mut self, refit: bool) -> Self {
+        self.refit = refit;
+        self
+    }
+
+    /// Enables successive halving with epochs as the budget
+    pub fn halving(mut self, factor: usize, min_epochs: usize) -> Self {
+        self.halving = Some((factor, min_epochs));
+        self
+    }
+
+    pub fn grid(&self, grid: &ParamGrid, x: &Array2<f64>, y: &Array2<f64>) -> Result<SearchResult, Box<dyn Error>> {
+        self.search(grid.candidates(), x, y)
+    }
+
+    pub fn random(
+        &self,
+        distributions: &ParamDistributions,
+        n_iter: usize,
+        seed: Option<u64>,
+        x: &Array2<f64>,
+        y: &Array2<f64>,
+    ) -> Result<SearchResult, Box<dyn Error>> {
+        self.search(distributions.sample(n_iter, seed)?, x, y)
+    }
+
+    /// Evaluates explicit candidates
+    pub fn search(
+        &self,
+        candidates: Vec<TrainParams>,
+        x: &Array2<f64>,
+        y: &Array2<f64>,
+    ) -> Result<SearchResult, Box<dyn Error>> {
+        if candidates.is_empty() {
+            return Err("Search space has no candidates".into());
+        }
+
+        let mut entries = match self.halving {
+            None => self.evaluate_all(&candidates, x, y)?,
+            Some((factor, min_epochs)) => self.successive_halving(candidates, factor, min_epochs, x, y)?,
+        };
+
+        // Candidates that survived longer rank first, then by score; NaN scores rank last
+        let greater_is_better = self.scorer.greater_is_better();
+        entries.sort_by(|a, b| {
+            b.round.cmp(&a.round).then_with(|| {
+                let (a, b) = (a.mean_score, b.mean_score);
+                match (a.is_nan(), b.is_nan()) {
+                    (true, true) => std::cmp::Ordering::Equal,
+                    (true, false) => std::cmp::Ordering::Greater,
+                    (false, true) => std::cmp::Ordering::Less,
+                    _ if greater_is_better => b.total_cmp(&a),
+                    _ => a.total_cmp(&b),
+                }
+            })
+        });
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.rank = i + 1;
+        }
+
+        let best_model = if self.refit {
+            let mut estimator = entries[0].params.to_estimator();
+            estimator.fit(x, y)?;
+            estimator.into_model()
+        } else {
+            None
+        };
+
+        Ok(SearchResult { scorer_name: self.scorer.name(), entries, best_model })
+    }
+
+    fn evaluate(&self, params: &TrainParams, x: &Array2<f64>, y: &Array2<f64>) -> Result<SearchEntry, String> {
+        let cv = cross_validate(|| params.to_estimator(), x, y, self.splitter, &[self.scorer]).map_err(|e| e.to_string())?;
+        Ok(SearchEntry {
+            params: params.clone(),
+            rank: 0,
+            round: 0,
+            epochs_used: params.epochs,
+            mean_score: cv.mean_score(0),
+            std_score: cv.std_score(0),
+            fold_scores: cv.scores(0),
+            fit_time: cv.total_fit_time(),
+        })
+    }
+
+    /// Cross-validates every candidate, spreading them over `n_jobs` threads
+    fn evaluate_all(
+        &self,
+        candidates: &[TrainParams],
+        x: &Array2<f64>,
+        y: &Array2<f64>,
+    ) -> Result<Vec<SearchEntry>, Box<dyn Error>> {
+        let results: Vec<Result<SearchEntry, String>> = if self.n_jobs == 1 {
+            candidates.iter().map(|params| self.evaluate(params, x, y)).collect()
+        } else {
+            let chunk_size = candidates.len().div_ceil(self.n_jobs);
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = candidates
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        scope.spawn(move || chunk.iter().map(|params| self.evaluate(params, x, y)).collect::<Vec<_>>())
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .flat_map(|handle| handle.join().unwrap_or_else(|_| vec![Err("Search worker panicked".to_string())]))
+                    .collect()
+            })
+        };
+        results.into_iter().map(|result| result.map_err(|e| e.into())).collect()
+    }
+
+    fn successive_halving(
+        &self,
+        candidates: Vec<TrainParams>,
+        factor: usize,
+        min_epochs: usize,
+        x: &Array2<f64>,
+        y: &Array2<f64>,
+    ) -> Result<Vec<SearchEntry>, Box<dyn Error>> {
+        if factor < 2 {
+            return Err("Halving factor should be at least 2".into());
+        }
+        if min_epochs == 0 {
+            return Err("Minimum number of epochs should be positive".into());
+        }
+
+        let greater_is_better = self.scorer.greater_is_better();
+        let mut finished = Vec::new();
+        let mut survivors = candidates;
+        let mut epochs = min_epochs;
+        let mut round = 0;
+        loop {
+            // Every candidate is capped at its own epochs, so the last round trains it fully
+            let budgeted: Vec<TrainParams> = survivors
+                .iter()
+                .map(|params| TrainParams { epochs: epochs.min(params.epochs), ..params.clone() })
+                .collect();
+            let mut entries = self.evaluate_all(&budgeted, x, y)?;
+            for (entry, params) in entries.iter_mut().zip(survivors.iter()) {
+                entry.params = params.clone();
+                entry.round = round;
+            }
+
+            let complete = survivors.iter().all(|params| params.epochs <= epochs);
+            if survivors.len() == 1 || complete {
+                finished.extend(entries);
+                return Ok(finished);
+            }
+
+            // Best scores first, NaN last
+            let key = |score: f64| if score.is_nan() { f64::NEG_INFINITY } else if greater_is_better { score } else { -score };
+            entries.sort_by(|a, b| key(b.mean_score).total_cmp(&key(a.mean_score)));
+            let keep = survivors.len().div_ceil(factor);
+            survivors = entries[..keep].iter().map(|entry| entry.params.clone()).collect();
+            finished.extend(entries.into_iter().skip(keep));
+            epochs = epochs.saturating_mul(factor);
+            round += 1;
+        }
+    }
+}
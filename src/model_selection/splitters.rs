@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::error::Error;
+use ndarray::Array2;
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
+use crate::utils::rng;
+
+/// Row indices of one train/test split
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fold {
+    pub train: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+/// Strategy producing train/test index sets, independent of the model being evaluated
+pub trait Splitter {
+    fn split(&self, x: &Array2<f64>, y: &Array2<f64>) -> Result<Vec<Fold>, Box<dyn Error>>;
+}
+
+/// Builds folds from the test indices of each fold; train indices are the rest
+fn folds_from_tests(n_samples: usize, tests: Vec<Vec<usize>>) -> Vec<Fold> {
+    tests
+        .into_iter()
+        .map(|mut test| {
+            test.sort_unstable();
+            let mut in_test = vec![false; n_samples];
+            test.iter().for_each(|&i| in_test[i] = true);
+            let train = (0..n_samples).filter(|&i| !in_test[i]).collect();
+            Fold { train, test }
+        })
+        .collect()
+}
+
+fn check_splits(n_splits: usize, n_samples: usize) -> Result<(), Box<dyn Error>> {
+    if n_splits < 2 {
+        return Err("Number of splits should be at least 2".into());
+    }
+    if n_splits > n_samples {
+        return Err(format!("Cannot make {} splits from {} samples", n_splits, n_samples).into());
+    }
+    Ok(())
+}
+
+/// Splits rows into `n_splits` consecutive folds, optionally shuffled first
+#[derive(Debug, Clone)]
+pub struct KFold {
+    pub n_splits: usize,
+    pub shuffle: bool,
+    pub seed: Option<u64>,
+}
+
+impl KFold {
+    pub fn new(n_splits: usize) -> Self {
+        Self { n_splits, shuffle: false, seed: None }
+    }
+
+    /// Shuffles rows before splitting, reproducibly when `seed` is given
+    pub fn shuffled(n_splits: usize, seed: Option<u64>) -> Self {
+        Self { n_splits, shuffle: true, seed }
+    }
+
+    fn split_with_rng(&self, n_samples: usize, rng: &mut StdRng) -> Result<Vec<Fold>, Box<dyn Error>> {
+        check_splits(self.n_splits, n_samples)?;
+        let mut indices: Vec<usize> = (0..n_samples).collect();
+        if self.shuffle {
+            indices.shuffle(rng);
+        }
+
+        // The first n_samples % n_splits folds get one extra row
+        let mut tests = Vec::with_capacity(self.n_splits);
+        let mut start = 0;
+        for fold in 0..self.n_splits {
+            let size = n_samples / self.n_splits + usize::from(fold < n_samples % self.n_splits);
+            tests.push(indices[start..start + size].to_vec());
+            start += size;
+        }
+        Ok(folds_from_tests(n_samples, tests))
+    }
+}
+
+impl Splitter for KFold {
+    fn split(&self, x: &Array2<f64>, _y: &Array2<f64>) -> Result<Vec<Fold>, Box<dyn Error>> {
+        self.split_with_rng(x.nrows(), &mut rng(self.seed))
+    }
+}
+
+/// Shuffled K-fold repeated `n_repeats` times with different shuffles
+#[derive(Debug, Clone)]
+pub struct RepeatedKFold {
+    pub n_splits: usize,
+    pub n_repeats: usize,
+    pub seed: Option<u64>,
+}
+
+impl RepeatedKFold {
+    pub fn new(n_splits: usize, n_repeats: usize, seed: Option<u64>) -> Self {
+        Self { n_splits, n_repeats, seed }
+    }
+}
+
+impl Splitter for RepeatedKFold {
+    fn split(&self, x: &Array2<f64>, _y: &Array2<f64>) -> Result<Vec<Fold>, Box<dyn Error>> {
+        let mut rng = rng(self.seed);
+        let kfold = KFold::shuffled(self.n_splits, None);
+        let mut folds = Vec::with_capacity(self.n_splits * self.n_repeats);
+        for _ in 0..self.n_repeats {
+            folds.extend(kfold.split_with_rng(x.nrows(), &mut rng)?);
+        }
+        Ok(folds)
+    }
+}
+
+/// K-fold keeping the class proportions of a label column in every fold
+#[derive(Debug, Clone)]
+pub struct StratifiedKFold {
+    pub n_splits: usize,
+    pub label_column: usize,
+    pub shuffle: bool,
+    pub seed: Option<u64>,
+}
+
+impl StratifiedKFold {
+    /// Stratifies on column `label_column` of `y`
+    pub fn new(n_splits: usize, label_column: usize) -> Self {
+        Self { n_splits, label_column, shuffle: false, seed: None }
+    }
+
+    pub fn shuffled(n_splits: usize, label_column: usize, seed: Option<u64>) -> Self {
+        Self { n_splits, label_column, shuffle: true, seed }
+    }
+}
+
+impl Splitter for StratifiedKFold {
+    fn split(&self, _x: &Array2<f64>, y: &Array2<f64>) -> Result<Vec<Fold>, Box<dyn Error>> {
+        if self.label_column >= y.ncols() {
+            return Err(format!("Label column {} out of range for {} columns", self.label_column, y.ncols()).into());
+        }
+        check_splits(self.n_splits, y.nrows())?;
+        let mut rng = rng(self.seed);
+
+        let classes = stratify(y.column(self.label_column).iter().map(|label| label.to_bits()));
+        let mut tests = vec![Vec::new(); self.n_splits];
+        // Deal the rows of every class round-robin, continuing where the previous class stopped
+        let mut next = 0;
+        for mut members in classes {
+            if self.shuffle {
+                members.shuffle(&mut rng);
+            }
+            for i in members {
+                tests[next % self.n_splits].push(i);
+                next += 1;
+            }
+        }
+        Ok(folds_from_tests(y.nrows(), tests))
+    }
+}
+
+/// Groups row indices by key (label bits or group id), in order of first appearance
+pub(crate) fn stratify(keys: impl Iterator<Item = u64>) -> Vec<Vec<usize>> {
+    let mut classes: Vec<Vec<usize>> = Vec::new();
+    let mut lookup: HashMap<u64, usize> = HashMap::new();
+    for (i, key) in keys.enumerate() {
+        let class = *lookup.entry(key).or_insert_with(|| {
+            classes.push(Vec::new());
+            classes.len() - 1
+        });
+        classes[class].push(i);
+    }
+    classes
+}
+
+/// K-fold where rows sharing a group id always land in the same fold
+///
+/// Groups are assigned largest first to the currently smallest fold.
+#[derive(Debug, Clone)]
+pub struct GroupKFold {
+    pub n_splits: usize,
+    pub groups: Vec<i64>,
+}
+
+impl GroupKFold {
+    /// `groups` holds one group id per row
+    pub fn new(n_splits: usize, groups: Vec<i64>) -> Self {
+        Self { n_splits, groups }
+    }
+}
+
+impl Splitter for GroupKFold {
+    fn split(&self, x: &Array2<f64>, _y: &Array2<f64>) -> Result<Vec<Fold>, Box<dyn Error>> {
+        if self.groups.len() != x.nrows() {
+            return Err(format!("Got {} group ids for {} rows", self.groups.len(), x.nrows()).into());
+        }
+        let mut groups = stratify(self.groups.iter().map(|&g| g as u64));
+        check_splits(self.n_splits, groups.len())?;
+
+        groups.sort_by_key(|members| std::cmp::Reverse(members.len()));
+        let mut tests: Vec<Vec<usize>> = vec![Vec::new(); self.n_splits];
+        for members in groups {
+            let smallest = (0..self.n_splits).min_by_key(|&f| tests[f].len()).unwrap_or(0);
+            tests[smallest].extend(members);
+        }
+        Ok(folds_from_tests(x.nrows(), tests))
+    }
+}
+
+/// One fold per row, each testing on that single row
+#[derive(Debug, Clone, Default)]
+pub struct LeaveOneOut;
+
+impl Splitter for LeaveOneOut {
+    fn split(&self, x: &Array2<f64>, _y: &Array2<f64>) -> Result<Vec<Fold>, Box<dyn Error>> {
+        check_splits(2, x.nrows())?;
+        Ok(folds_from_tests(x.nrows(), (0..x.nrows()).map(|i| vec![i]).collect()))
+    }
+}
+
+/// Expanding-window splits for time-ordered rows: every fold trains on the past and tests on the next block
+#[derive(Debug, Clone)]
+pub struct TimeSeriesSplit {
+    pub n_splits: usize,
+    pub test_size: Option<usize>,      // Defaults to n_samples / (n_splits + 1)
+    pub max_train_size: Option<usize>, // Turns the expanding window into a sliding one
+    pub gap: usize,                    // Rows dropped between train and test
+}
+
+impl TimeSeriesSplit {
+    pub fn new(n_splits: usize) -> Self {
+        Self { n_splits, test_size: None, max_train_size: None, gap: 0 }
+    }
+}
+
+impl Splitter for TimeSeriesSplit {
+    fn split(&self, x: &Array2<f64>, _y: &Array2<f64>) -> Result<Vec<Fold>, Box<dyn Error>> {
+        let n_samples = x.nrows();
+        if self.n_splits < 1 {
+            return Err("Number of splits should be at least 1".into());
+        }
+        let test_size = self.test_size.unwrap_or(n_samples / (self.n_splits + 1));
+        if test_size == 0 || self.n_splits * test_size + self.gap >= n_samples {
+            return Err(format!(
+                "Cannot make {} splits of {} test rows with gap {} from {} samples",
+                self.n_splits, test_size, self.gap, n_samples
+            )
+            .into());
+        }
+
+        let first_test = n_samples - self.n_splits * test_size;
+        Ok((0..self.n_splits)
+            .map(|fold| {
+                let test_start = first_test + fold * test_size;
+                let train_end = test_start - self.gap;
+                let train_start = self.max_train_size.map_or(0, |max| train_end.saturating_sub(max));
+                Fold {
+                    train: (train_start..train_end).collect(),
+                    test: (test_start..test_start + test_size).collect(),
+                }
+            })
+            .collect())
+    }
+}
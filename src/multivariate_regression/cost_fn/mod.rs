@@ -1 +0,0 @@
-pub mod cost_fn;
\ No newline at end of file
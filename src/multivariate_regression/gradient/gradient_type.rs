@@ -1,6 +0,0 @@
-pub enum GradientType {
-    MeanAbsoluteError,
-    MeanSquaredError,
-    HuberError
-}
-
@@ -1,76 +1,97 @@
-use ndarray::Array2;
-use crate::matrix_operations::matrix_mul;
-use crate::multivariate_regression::gradient::gradient_type::GradientType;
+use std::ops::Range;
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, ArrayViewMut2};
+use serde::{Deserialize, Serialize};
+use crate::matrix_operations::{LinearOperator, Transpose};
+use crate::multivariate_regression::loss::LossFn;
 use crate::multivariate_regression::regularization::regularization::{Regularization, RegularizationType};
-use ndarray::indices;
-
-pub mod gradient_type;
-
 
+/// Gradient of a loss plus the penalty on the weights
+///
+/// The cost logged during training and the gradient both come from `loss`, so they cannot disagree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Gradient {
-    pub(crate) gradient : GradientType,
+    pub(crate) loss : LossFn,
     pub(crate) regularization: Regularization
 }
 
 impl Gradient {
-     pub fn mean_absolute_error(regularization: Regularization) -> Self{
-            Self{
-                gradient : GradientType::MeanAbsoluteError,
-                regularization
-            }
-        }
+    pub fn new(loss: LossFn, regularization: Regularization) -> Self {
+        Self { loss, regularization }
+    }
 
-    pub fn mean_squared_error(regularization: Regularization) -> Self{
-        Self{
-            gradient : GradientType::MeanAbsoluteError,
-            regularization,
-        }
+    pub fn loss(&self) -> &LossFn {
+        &self.loss
     }
 
-    pub fn huber_loss(regularization: Regularization) -> Self{
-        Self{
-            gradient : GradientType::HuberError,
-            regularization
-        }
+    /// Penalized mean loss of the predictions, see `LossFn::calculate_cost`
+    pub fn calculate_cost(&self, y_true : &Array2<f64>, y_pred : &Array2<f64>, weight : &Array2<f64>, sample_weight : Option<&Array1<f64>>) -> f64 {
+        self.loss.calculate_cost(y_true, y_pred, &self.regularization, weight, sample_weight)
     }
 
-    pub fn calculate_gradient(&self,delta : f64,input : &Array2<f64>,y_pred : &Array2<f64>,y_true : &Array2<f64>,weight : &Array2<f64>) -> Array2<f64> {
+    /// Gradient of the cost with respect to `weight`; `input` may be dense or sparse
+    ///
+    /// `input` carries the bias column explicitly. Rows are weighted by `sample_weight` when given,
+    /// as in `LossFn::calculate_cost`. Allocates its result, see `gradient_into` for the training loop.
+    pub fn calculate_gradient<M: LinearOperator>(&self,input : &M,y_pred : &Array2<f64>,y_true : &Array2<f64>,weight : &Array2<f64>,sample_weight : Option<&Array1<f64>>) -> Array2<f64> {
+        let mut residual_signal = y_pred.clone();
+        self.loss.derivative_into(&y_true.view(), &mut residual_signal.view_mut());
+        let total = apply_sample_weight(sample_weight.map(|w| w.view()), &mut residual_signal.view_mut());
+        // Xᵀ r without materializing Xᵀ (transposed strides, or a scatter over sparse rows), scaled inside the kernel
+        let mut raw_gradient_matrix = Array2::<f64>::zeros((input.dim().1, residual_signal.ncols()));
+        input.mul_into(1./total, Transpose::Yes, &residual_signal.view(), 0., &mut raw_gradient_matrix.view_mut())
+            .expect("Input and output rows must match");
+        self.add_regularization(weight, &mut raw_gradient_matrix);
+        raw_gradient_matrix
+    }
 
-        let total_elements : f64 = y_true.len() as f64;
-        let error_matrix = y_true - y_pred;
-        let mut raw_gradient_matrix = Array2::<f64>::zeros((weight.nrows(), weight.ncols()));
+    /// Gradient over rows `rows` of `input`, written into `gradient` without allocating
+    ///
+    /// Unlike `calculate_gradient`, `input` has no bias column: row 0 of `weight` and `gradient`
+    /// is the bias. `signal` holds the predictions of the rows on entry and is overwritten.
+    /// `sample_weight` holds the weights of the same rows.
+    #[allow(clippy::too_many_arguments)]
+    pub fn gradient_into<M: LinearOperator>(
+        &self,
+        input : &M,
+        rows : Range<usize>,
+        y_true : &ArrayView2<f64>,
+        sample_weight : Option<ArrayView1<f64>>,
+        signal : &mut ArrayViewMut2<f64>,
+        weight : &Array2<f64>,
+        gradient : &mut Array2<f64>,
+    ) {
+        self.loss.derivative_into(y_true, signal);
+        let scale = 1./apply_sample_weight(sample_weight, signal);
+        input.mul_rows_into(rows, scale, Transpose::Yes, &signal.view(), 0., &mut gradient.slice_mut(s![1.., ..]))
+            .expect("Input and output rows must match");
+        // The implicit bias column is all ones, so its gradient is the column sum of the signal
+        for (bias, column) in gradient.row_mut(0).iter_mut().zip(signal.columns()) {
+            *bias = scale * column.sum();
+        }
+        self.add_regularization(weight, gradient);
+    }
 
-        match &self.gradient {
-            GradientType::MeanAbsoluteError => {
-                let sign_error_matrix = error_matrix.mapv(|x| x.signum());
-                raw_gradient_matrix = (-1./total_elements) * matrix_mul(&input.t().to_owned(), &sign_error_matrix);
-            },
-            GradientType::MeanSquaredError => {
-                raw_gradient_matrix = (-1./total_elements) * matrix_mul(&input.t().to_owned(), &error_matrix);
+    /// Adds the gradient of the penalty on `weight` to `gradient` in place
+    fn add_regularization(&self, weight: &Array2<f64>, gradient: &mut Array2<f64>) {
+        let Regularization { regularization_type, lambda1, lambda2 } = self.regularization;
+        match regularization_type {
+            RegularizationType::LassoL1 => gradient.zip_mut_with(weight, |g, &w| *g += lambda1 * w.signum()),
+            RegularizationType::RidgeL2 => gradient.zip_mut_with(weight, |g, &w| *g += lambda2 * 2. * w),
+            RegularizationType::ElasticNet => gradient.zip_mut_with(weight, |g, &w| *g += lambda1 * w.signum() + lambda2 * 2. * w),
+        }
+    }
 
-            },
-            GradientType::HuberError => {
-                raw_gradient_matrix = (1./total_elements) * error_matrix.mapv(|x| {
-                    if x.abs()<=delta { x }
-                    else {delta*x.signum() }
-                });
-                println!("{:?}",raw_gradient_matrix);
+}
 
+/// Multiplies each row of `signal` by its sample weight; returns the total weight of the entries
+fn apply_sample_weight(sample_weight: Option<ArrayView1<f64>>, signal: &mut ArrayViewMut2<f64>) -> f64 {
+    match sample_weight {
+        Some(sample_weight) => {
+            for (mut row, &w) in signal.rows_mut().into_iter().zip(sample_weight.iter()) {
+                row *= w;
             }
+            sample_weight.sum() * signal.ncols() as f64
         }
-        match &self.regularization.regularization_type {
-             RegularizationType::LassoL1 => {
-                let sign_weight_matrix = weight.mapv(|x| x.signum());
-                (self.regularization.lambda1 * sign_weight_matrix) + raw_gradient_matrix
-            },
-            RegularizationType::RidgeL2 => {
-                (self.regularization.lambda2 * 2. * weight) + raw_gradient_matrix
-            },
-            RegularizationType::ElasticNet => {
-                let sign_weight_matrix = weight.mapv(|x| x.signum());
-                (self.regularization.lambda1 * sign_weight_matrix) + (self.regularization.lambda2 * 2. * weight) + raw_gradient_matrix
-            }
-        }
+        None => signal.len() as f64,
     }
-
 }
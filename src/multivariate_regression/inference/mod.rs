@@ -0,0 +1,328 @@
+use std::error::Error;
+use ndarray::{Array1, Array2, Axis};
+use crate::matrix_operations::{cholesky, cholesky_solve, matrix_mul};
+use crate::multivariate_regression::loss::LossFn;
+use crate::multivariate_regression::model::TrainedModel;
+use crate::multivariate_regression::regularization::regularization::RegularizationType;
+use crate::stats::{f_sf, students_t_ppf, students_t_sf};
+
+/// Estimator of the coefficient covariance matrix
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CovarianceType {
+    NonRobust, // Homoskedastic errors
+    HC0,       // White's heteroskedasticity-consistent estimator
+    HC1,       // HC0 scaled by n / (n - p)
+    HC2,       // Squared residuals scaled by 1 / (1 - leverage)
+    HC3,       // Squared residuals scaled by 1 / (1 - leverage)^2
+}
+
+pub struct SummaryConfig {
+    pub feature_names: Vec<String>, // One per input column; `x0`, `x1`, ... when empty
+    pub covariance_type: CovarianceType,
+    pub confidence_level: f64,
+}
+
+impl Default for SummaryConfig {
+    fn default() -> Self {
+        Self {
+            feature_names: Vec::new(),
+            covariance_type: CovarianceType::NonRobust,
+            confidence_level: 0.95,
+        }
+    }
+}
+
+pub struct SummaryConfigBuilder {
+    config: SummaryConfig,
+}
+
+impl Default for SummaryConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SummaryConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: SummaryConfig::default(),
+        }
+    }
+
+    pub fn feature_names(mut self, names: Vec<String>) -> Self {
+        self.config.feature_names = names;
+        self
+    }
+
+    pub fn covariance_type(mut self, covariance_type: CovarianceType) -> Self {
+        self.config.covariance_type = covariance_type;
+        self
+    }
+
+    pub fn confidence_level(mut self, level: f64) -> Self {
+        self.config.confidence_level = level;
+        self
+    }
+
+    pub fn build(self) -> SummaryConfig {
+        self.config
+    }
+}
+
+/// Inference for one coefficient, in original feature units
+#[derive(Debug, Clone)]
+pub struct CoefficientSummary {
+    pub name: String,
+    pub estimate: f64,
+    pub std_error: f64,
+    pub t_stat: f64,
+    pub p_value: f64, // Two-sided
+    pub ci_lower: f64,
+    pub ci_upper: f64,
+}
+
+/// Summary table of one output column
+#[derive(Debug, Clone)]
+pub struct RegressionSummary {
+    pub output: usize,
+    pub coefficients: Vec<CoefficientSummary>, // Intercept first
+    pub n_observations: usize,
+    pub df_model: f64, // Effective degrees of freedom, excluding the intercept (fractional for ridge)
+    pub df_resid: f64,
+    pub r2: f64,
+    pub adjusted_r2: f64,
+    pub f_statistic: f64,
+    pub f_p_value: f64,
+    pub log_likelihood: f64,
+    pub aic: f64,
+    pub bic: f64,
+    pub residual_std_error: f64,
+    pub covariance_type: CovarianceType,
+    pub confidence_level: f64,
+}
+
+impl RegressionSummary {
+    pub fn print(&self) {
+        println!("Output {} ({:?} covariance)", self.output, self.covariance_type);
+        println!(
+            "Observations: {}, Df model: {:.2}, Df residuals: {:.2}",
+            self.n_observations, self.df_model, self.df_resid
+        );
+        println!("R²: {:.6}, Adjusted R²: {:.6}", self.r2, self.adjusted_r2);
+        println!("F-statistic: {:.6} (p = {:.4e})", self.f_statistic, self.f_p_value);
+        println!(
+            "Log-likelihood: {:.4}, AIC: {:.4}, BIC: {:.4}, Residual std error: {:.6}",
+            self.log_likelihood, self.aic, self.bic, self.residual_std_error
+        );
+        let level = format!("{:.0}%", self.confidence_level * 100.);
+        println!(
+            "{:<16} {:>12} {:>12} {:>10} {:>10} {:>12} {:>12}",
+            "", "coef", "std err", "t", "P>|t|", format!("[{} lower", level), "upper]"
+        );
+        for c in &self.coefficients {
+            println!(
+                "{:<16} {:>12.6} {:>12.6} {:>10.4} {:>10.4} {:>12.6} {:>12.6}",
+                c.name, c.estimate, c.std_error, c.t_stat, c.p_value, c.ci_lower, c.ci_upper
+            );
+        }
+    }
+}
+
+impl TrainedModel {
+    /// Inference solves the unweighted squared-error normal equations, so it only describes such fits
+    pub(crate) fn check_least_squares(&self) -> Result<(), Box<dyn Error>> {
+        if !matches!(self.loss, Some(LossFn::SquaredError)) {
+            return Err(format!("Inference needs a model trained with the squared error loss, this one used {:?}", self.loss).into());
+        }
+        if self.weighted {
+            return Err("Inference needs a model trained without sample weights".into());
+        }
+        Ok(())
+    }
+
+    /// Ridge penalty matrix scale implied by the training objective
+    ///
+    /// The gradient averages the loss over all `rows * outputs` entries and adds
+    /// `2 * lambda2 * w` (bias included), so the stationary point solves
+    /// `(ZᵀZ + 2 * lambda2 * rows * outputs * I) w = Zᵀy`.
+    pub(crate) fn ridge_penalty(&self, rows: usize) -> Result<f64, Box<dyn Error>> {
+        let reg = self.regularization;
+        let lambda2 = match reg.regularization_type {
+            RegularizationType::RidgeL2 => reg.lambda2,
+            RegularizationType::ElasticNet if reg.lambda1 == 0.0 => reg.lambda2,
+            RegularizationType::LassoL1 if reg.lambda1 == 0.0 => 0.0,
+            _ => return Err(
+                "Inference is only available for OLS or ridge fits, and this model has an L1 penalty \
+                 (the default ElasticNet(1, 1) regularization has one); train with Regularization::l2 instead"
+                    .into(),
+            ),
+        };
+        Ok(2.0 * lambda2 * (rows * self.weight.ncols()) as f64)
+    }
+
+    /// Maps weights on normalized inputs to coefficients on raw inputs: `beta = T w`
+    fn unnormalization_matrix(&self) -> Result<Array2<f64>, Box<dyn Error>> {
+        let (shift, scale) = self
+            .normalization
+            .shift_scale()
+            .ok_or("Coefficients can only be mapped back through a linear column normalization")?;
+        let p = self.weight.nrows();
+        let mut transform = Array2::<f64>::eye(p);
+        for col in 0..p - 1 {
+            transform[(col + 1, col + 1)] = 1.0 / scale[col];
+            transform[(0, col + 1)] = -shift[col] / scale[col];
+        }
+        Ok(transform)
+    }
+
+    /// Function to compute coefficient inference and goodness of fit on the training data
+    ///
+    /// Coefficients are reported in the units of the raw input columns. They are the exact
+    /// minimizer of the model's OLS or ridge objective on `input`, solved from the normal
+    /// equations, so they do not depend on how far gradient descent converged; they equal
+    /// the trained weights once training has converged. For ridge fits the covariance is
+    /// the sandwich `A⁻¹ (ZᵀΩZ) A⁻¹` with `A = ZᵀZ + penalty`, and degrees of freedom are
+    /// the trace of the hat matrix.
+    ///
+    /// Models with an L1 penalty, including the default `ElasticNet(1, 1)` regularization,
+    /// are rejected with an error.
+    ///
+    /// # Parameters:
+    /// - `input: &Array2<f64>` - Raw input rows the model was trained on
+    /// - `output: &Array2<f64>` - Matching output rows
+    /// - `config: &SummaryConfig` - Feature names, covariance estimator and confidence level
+    ///
+    /// # Returns:
+    /// - `Result<Vec<RegressionSummary>, Box<dyn Error>>` - One summary per output column
+    pub fn summary(
+        &self,
+        input: &Array2<f64>,
+        output: &Array2<f64>,
+        config: &SummaryConfig,
+    ) -> Result<Vec<RegressionSummary>, Box<dyn Error>> {
+        let (n, p) = (input.nrows(), self.weight.nrows());
+        if input.ncols() + 1 != p || output.ncols() != self.weight.ncols() || output.nrows() != n {
+            return Err("Input and output shapes do not match the model".into());
+        }
+        self.check_least_squares()?;
+        if !config.feature_names.is_empty() && config.feature_names.len() != input.ncols() {
+            return Err(format!("Got {} feature names for {} input columns", config.feature_names.len(), input.ncols()).into());
+        }
+        if config.confidence_level <= 0.0 || config.confidence_level >= 1.0 {
+            return Err("Confidence level should be between 0 and 1".into());
+        }
+        if n <= p {
+            return Err(format!("Need more rows than coefficients, got {} rows for {} coefficients", n, p).into());
+        }
+
+        let design = self.prepare_input(input)?;
+        let gram = matrix_mul(&design.t(), &design);
+        let penalized = &gram + &(self.ridge_penalty(n)? * Array2::<f64>::eye(p));
+        let factor = cholesky(&penalized)?;
+        let penalized_inv = cholesky_solve(&factor, &Array2::eye(p))?;
+        let hat_core = cholesky_solve(&factor, &gram)?;
+        let df_total = hat_core.diag().sum();
+        let df_resid = n as f64 - df_total;
+        let df_model = df_total - 1.0;
+        let bread = matrix_mul(&hat_core, &penalized_inv);
+        let leverage: Array1<f64> = design
+            .axis_iter(Axis(0))
+            .map(|row| row.dot(&penalized_inv.dot(&row)))
+            .collect();
+
+        let transform = self.unnormalization_matrix()?;
+        let mut names = vec!["intercept".to_string()];
+        if config.feature_names.is_empty() {
+            names.extend((0..input.ncols()).map(|i| format!("x{}", i)));
+        } else {
+            names.extend(config.feature_names.iter().cloned());
+        }
+        let t_critical = students_t_ppf(1.0 - (1.0 - config.confidence_level) / 2.0, df_resid);
+
+        // Solve the normal equations on the target the model was trained on
+        let target = match &self.target_normalization {
+            Some(target_normalization) => {
+                let (shift, scale) = target_normalization
+                    .shift_scale()
+                    .ok_or("Only a linear target normalization can be folded into the weights")?;
+                (output - &shift.view().insert_axis(Axis(0))) / scale.view().insert_axis(Axis(0))
+            }
+            None => output.clone(),
+        };
+        let weight = self.fold_target_normalization(cholesky_solve(&factor, &matrix_mul(&design.t(), &target))?)?;
+        let predictions = matrix_mul(&design, &weight);
+        let mut summaries = Vec::with_capacity(output.ncols());
+        for o in 0..output.ncols() {
+            let y = output.column(o);
+            let residuals = &y - &predictions.column(o);
+            let rss = residuals.dot(&residuals);
+            let mean = y.mean().unwrap_or(0.0);
+            let tss = y.iter().map(|v| (v - mean).powi(2)).sum::<f64>();
+            let sigma2 = rss / df_resid;
+
+            let weight_covariance = match config.covariance_type {
+                CovarianceType::NonRobust => sigma2 * &bread,
+                robust => {
+                    let omega: Array1<f64> = residuals
+                        .iter()
+                        .zip(leverage.iter())
+                        .map(|(e, h)| {
+                            e * e * match robust {
+                                CovarianceType::HC1 => n as f64 / df_resid,
+                                CovarianceType::HC2 => 1.0 / (1.0 - h),
+                                CovarianceType::HC3 => 1.0 / (1.0 - h).powi(2),
+                                _ => 1.0,
+                            }
+                        })
+                        .collect();
+                    let weighted = &design * &omega.insert_axis(Axis(1));
+                    let meat = matrix_mul(&design.t(), &weighted);
+                    matrix_mul(&matrix_mul(&penalized_inv, &meat), &penalized_inv)
+                }
+            };
+            let covariance = matrix_mul(&matrix_mul(&transform, &weight_covariance), &transform.t());
+            let estimates = transform.dot(&weight.column(o));
+
+            let coefficients = names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    let estimate = estimates[i];
+                    let std_error = covariance[(i, i)].max(0.0).sqrt();
+                    let t_stat = estimate / std_error;
+                    CoefficientSummary {
+                        name: name.clone(),
+                        estimate,
+                        std_error,
+                        t_stat,
+                        p_value: 2.0 * students_t_sf(t_stat.abs(), df_resid),
+                        ci_lower: estimate - t_critical * std_error,
+                        ci_upper: estimate + t_critical * std_error,
+                    }
+                })
+                .collect();
+
+            let r2 = 1.0 - rss / tss;
+            let f_statistic = ((tss - rss) / df_model) / (rss / df_resid);
+            let log_likelihood = -(n as f64) / 2.0 * ((2.0 * std::f64::consts::PI).ln() + (rss / n as f64).ln() + 1.0);
+            summaries.push(RegressionSummary {
+                output: o,
+                coefficients,
+                n_observations: n,
+                df_model,
+                df_resid,
+                r2,
+                adjusted_r2: 1.0 - (1.0 - r2) * (n as f64 - 1.0) / df_resid,
+                f_statistic,
+                f_p_value: f_sf(f_statistic, df_model, df_resid),
+                log_likelihood,
+                aic: -2.0 * log_likelihood + 2.0 * df_total,
+                bic: -2.0 * log_likelihood + df_total * (n as f64).ln(),
+                residual_std_error: sigma2.sqrt(),
+                covariance_type: config.covariance_type,
+                confidence_level: config.confidence_level,
+            });
+        }
+        Ok(summaries)
+    }
+}
@@ -1,56 +1,59 @@
-use ndarray::{Array1, Array2, Axis};
-use crate::multivariate_regression::normalization::normalization::{normalize_data, NormalizationParameterType};
+use std::error::Error;
+use ndarray::Array2;
+use crate::multivariate_regression::normalization::normalization::{normalize_data, Normalization, NormalizationParameterType};
 
 pub struct Input {
     pub(crate) input: Array2<f64>,
     pub(crate) output: Array2<f64>,
-    normalization_parameter_type: NormalizationParameterType,
-    normalization_parameters: Array2<f64>,
+    normalization: Normalization,
+    target_normalization: Option<Normalization>,
 }
 impl Input {
     pub fn new(
         mut input: Array2<f64>,
-        output: Array2<f64>,
+        mut output: Array2<f64>,
         normalization_parameter_type: Option<NormalizationParameterType>,
-    ) -> Self {
+        target_normalization_parameter_type: Option<NormalizationParameterType>,
+    ) -> Result<Self, Box<dyn Error>> {
         let normalization_parameter_type =
             normalization_parameter_type.unwrap_or(NormalizationParameterType::MinMaxParameter);
-        let normalization_parameters = normalize_data(&normalization_parameter_type, &mut input);
+        let normalization = normalize_data(&normalization_parameter_type, &mut input)?;
 
-        Self {
+        // The output is only scaled on request, predictions are mapped back with `denormalize_data`
+        let target_normalization = match target_normalization_parameter_type {
+            Some(NormalizationParameterType::UnitNormParameter(_)) => {
+                return Err("Row normalization cannot be inverted, so it cannot scale the output".into());
+            }
+            Some(parameter_type) => Some(normalize_data(&parameter_type, &mut output)?),
+            None => None,
+        };
+
+        Ok(Self {
             input,
             output,
-            normalization_parameter_type,
-            normalization_parameters,
-        }
-    }
-
-    pub fn adjust_input(&mut self) {
-        let mut adjusted_array: Array2<f64> = Array2::zeros((0, self.input.dim().1 + 1));
-
-        for row in self.input.rows() {
-            let mut new_row = Array1::from_vec(vec![1.]);
-            if let Err(e) = new_row.append(Axis(0), row) {
-                println!("Linear Regression :: Input :: adjust_input :: Error occured while prepending 1 to the row : {}",e);
-            };
-            if let Err(e) = adjusted_array.push_row(new_row.view()) {
-                println!("Linear Regression :: Input :: adjust_input :: Error occured while pushing row to array : {}",e);
-            };
-        }
-
-        self.input = adjusted_array;
+            normalization,
+            target_normalization,
+        })
     }
 
     pub fn print(&self) {
         println!("Input: {:?}", self.input);
         println!("Output: {:?}", self.output);
         println!(
-            "Normalization Parameters: {:?}",
-            self.normalization_parameters
+            "Normalization: {:?}",
+            self.normalization
         );
+        println!(
+            "Target Normalization: {:?}",
+            self.target_normalization
+        );
+    }
+
+    pub fn get_normalization(&self) -> &Normalization {
+        &self.normalization
     }
 
-    pub fn get_normalization_parameters(&self) -> &Array2<f64> {
-        &self.normalization_parameters
+    pub fn get_target_normalization(&self) -> Option<&Normalization> {
+        self.target_normalization.as_ref()
     }
 }
@@ -0,0 +1,185 @@
+use std::error::Error;
+use ndarray::{Array1, Array2, Axis};
+use crate::data_utils::{split_indices, SplitStrategy, TestSize};
+use crate::matrix_operations::{cholesky, cholesky_solve, matrix_mul};
+use crate::multivariate_regression::model::TrainedModel;
+use crate::stats::students_t_ppf;
+
+/// Point predictions with lower and upper interval bounds, all of the same shape
+#[derive(Debug, Clone)]
+pub struct PredictionInterval {
+    pub prediction: Array2<f64>,
+    pub lower: Array2<f64>,
+    pub upper: Array2<f64>,
+}
+
+impl PredictionInterval {
+    /// Fraction of `y_true` entries inside their interval
+    pub fn coverage(&self, y_true: &Array2<f64>) -> f64 {
+        assert_eq!(y_true.dim(), self.prediction.dim(), "Shapes of y_true and the interval do not match!");
+        let inside = y_true
+            .iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .filter(|(y, (low, high))| *low <= *y && *y <= *high)
+            .count();
+        inside as f64 / y_true.len() as f64
+    }
+
+    pub fn width(&self) -> Array2<f64> {
+        &self.upper - &self.lower
+    }
+}
+
+impl TrainedModel {
+    /// Function to compute analytic prediction intervals of an OLS fit
+    ///
+    /// Assumes homoskedastic Gaussian errors: `ŷ ± t * s * sqrt(1 + x₀ᵀ(XᵀX)⁻¹x₀)`.
+    ///
+    /// # Parameters:
+    /// - `train_input: &Array2<f64>` - Raw input rows the model was trained on
+    /// - `train_output: &Array2<f64>` - Matching output rows
+    /// - `input: &Array2<f64>` - Raw input rows to predict
+    /// - `level: f64` - Nominal coverage, e.g. 0.95
+    ///
+    /// # Returns:
+    /// - `Result<PredictionInterval, Box<dyn Error>>` - Predictions with interval bounds
+    pub fn prediction_interval(
+        &self,
+        train_input: &Array2<f64>,
+        train_output: &Array2<f64>,
+        input: &Array2<f64>,
+        level: f64,
+    ) -> Result<PredictionInterval, Box<dyn Error>> {
+        let (n, p) = (train_input.nrows(), self.weight.nrows());
+        if train_input.ncols() + 1 != p || input.ncols() + 1 != p || train_output.ncols() != self.weight.ncols() || train_output.nrows() != n {
+            return Err("Input and output shapes do not match the model".into());
+        }
+        if level <= 0.0 || level >= 1.0 {
+            return Err("Interval level should be between 0 and 1".into());
+        }
+        self.check_least_squares()?;
+        if self.ridge_penalty(n)? != 0.0 {
+            return Err("Analytic prediction intervals need an unpenalized (OLS) fit".into());
+        }
+        if n <= p {
+            return Err(format!("Need more rows than coefficients, got {} rows for {} coefficients", n, p).into());
+        }
+
+        let design = self.prepare_input(train_input)?;
+        let gram_factor = cholesky(&matrix_mul(&design.t(), &design))?;
+        let gram_inv = cholesky_solve(&gram_factor, &Array2::eye(p))?;
+        let weight = self.output_weight()?;
+        let residuals = train_output - &matrix_mul(&design, &weight);
+        let df_resid = (n - p) as f64;
+        let sigma: Array1<f64> = residuals.axis_iter(Axis(1)).map(|e| (e.dot(&e) / df_resid).sqrt()).collect();
+        let t_critical = students_t_ppf(1.0 - (1.0 - level) / 2.0, df_resid);
+
+        let new_design = self.prepare_input(input)?;
+        let prediction = matrix_mul(&new_design, &weight);
+        let leverage: Array1<f64> = new_design.axis_iter(Axis(0)).map(|row| row.dot(&gram_inv.dot(&row))).collect();
+        let half_width = Array2::from_shape_fn(prediction.dim(), |(row, col)| {
+            t_critical * sigma[col] * (1.0 + leverage[row]).sqrt()
+        });
+
+        Ok(PredictionInterval {
+            lower: &prediction - &half_width,
+            upper: &prediction + &half_width,
+            prediction,
+        })
+    }
+}
+
+/// Split-conformal wrapper around any fitted regressor
+///
+/// Intervals are `ŷ ± q`, where `q` is the conformal quantile of absolute
+/// residuals on a calibration set, per output column. Coverage of at least
+/// `level` holds for exchangeable data regardless of the model.
+pub struct ConformalRegressor<P: Fn(&Array2<f64>) -> Array2<f64>> {
+    predictor: P,
+    half_width: Array1<f64>,
+    level: f64,
+}
+
+impl<P: Fn(&Array2<f64>) -> Array2<f64>> ConformalRegressor<P> {
+    /// Calibrates an already fitted `predictor` on rows it was not trained on
+    pub fn calibrate(predictor: P, x_calibration: &Array2<f64>, y_calibration: &Array2<f64>, level: f64) -> Result<Self, Box<dyn Error>> {
+        if level <= 0.0 || level >= 1.0 {
+            return Err("Interval level should be between 0 and 1".into());
+        }
+        if x_calibration.nrows() != y_calibration.nrows() || x_calibration.nrows() == 0 {
+            return Err("Calibration set should have matching, non-zero row counts".into());
+        }
+        let residuals = (y_calibration - &predictor(x_calibration)).mapv(f64::abs);
+
+        // The ceil((n + 1) * level)-th smallest residual; infinite when the calibration set is too small
+        let n = residuals.nrows();
+        let rank = ((n + 1) as f64 * level).ceil() as usize;
+        let half_width = residuals
+            .axis_iter(Axis(1))
+            .map(|column| {
+                if rank > n {
+                    return f64::INFINITY;
+                }
+                let mut sorted = column.to_vec();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                sorted[rank - 1]
+            })
+            .collect();
+        Ok(Self { predictor, half_width, level })
+    }
+
+    pub fn predict(&self, input: &Array2<f64>) -> Array2<f64> {
+        (self.predictor)(input)
+    }
+
+    pub fn predict_interval(&self, input: &Array2<f64>) -> PredictionInterval {
+        let prediction = self.predict(input);
+        let half_width = self.half_width.view().insert_axis(Axis(0));
+        PredictionInterval {
+            lower: &prediction - &half_width,
+            upper: &prediction + &half_width,
+            prediction,
+        }
+    }
+
+    /// Interval half-width of every output column
+    pub fn half_width(&self) -> &Array1<f64> {
+        &self.half_width
+    }
+
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+}
+
+/// Function to fit a regressor and calibrate split-conformal intervals in one go
+///
+/// # Parameters:
+/// - `fit: F` - Trains a model on (x, y) and returns its predict function
+/// - `x: &Array2<f64>` - Feature matrix
+/// - `y: &Array2<f64>` - Target matrix
+/// - `calibration_size: TestSize` - Rows held out for calibration
+/// - `level: f64` - Nominal coverage, e.g. 0.9
+/// - `seed: Option<u64>` - Seed for the train/calibration split
+///
+/// # Returns:
+/// - `Result<ConformalRegressor<P>, Box<dyn Error>>` - Calibrated regressor
+pub fn split_conformal<F, P>(
+    fit: F,
+    x: &Array2<f64>,
+    y: &Array2<f64>,
+    calibration_size: TestSize,
+    level: f64,
+    seed: Option<u64>,
+) -> Result<ConformalRegressor<P>, Box<dyn Error>>
+where
+    F: FnOnce(Array2<f64>, Array2<f64>) -> P,
+    P: Fn(&Array2<f64>) -> Array2<f64>,
+{
+    if x.nrows() != y.nrows() {
+        return Err(format!("x has {} rows but y has {}", x.nrows(), y.nrows()).into());
+    }
+    let (train, calibration) = split_indices(y, calibration_size, &SplitStrategy::Shuffle, seed)?;
+    let predictor = fit(x.select(Axis(0), &train), y.select(Axis(0), &train));
+    ConformalRegressor::calibrate(predictor, &x.select(Axis(0), &calibration), &y.select(Axis(0), &calibration), level)
+}
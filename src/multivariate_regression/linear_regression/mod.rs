@@ -0,0 +1,82 @@
+use std::error::Error;
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+use crate::multivariate_regression::model::TrainedModel;
+use crate::matrix_operations::CsrMatrix;
+use crate::multivariate_regression::training::train::{train_sparse, try_train, try_train_weighted};
+use crate::multivariate_regression::training::train_config::TrainConfig;
+use crate::traits::{Fit, FitWeighted, Persist, Predict, Score};
+
+/// Linear regression estimator: a `TrainConfig` and, once fitted, the `TrainedModel`
+///
+/// The trait based counterpart of `train`, usable wherever a `Fit + Predict` model is
+/// expected (cross-validation, pipelines). Refitting replaces the model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinearRegression {
+    config: TrainConfig,
+    model: Option<TrainedModel>,
+}
+
+impl LinearRegression {
+    /// Creates an unfitted estimator
+    pub fn new(config: TrainConfig) -> Self {
+        Self { config, model: None }
+    }
+
+    pub fn config(&self) -> &TrainConfig {
+        &self.config
+    }
+
+    /// Fitted model, `None` before `fit`
+    pub fn model(&self) -> Option<&TrainedModel> {
+        self.model.as_ref()
+    }
+
+    pub fn into_model(self) -> Option<TrainedModel> {
+        self.model
+    }
+
+    /// Fits on a sparse input without densifying it, see `train_sparse`
+    pub fn fit_sparse(&mut self, x: &CsrMatrix, y: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if x.nrows() != y.nrows() {
+            return Err(format!("x has {} rows but y has {}", x.nrows(), y.nrows()).into());
+        }
+        self.model = Some(train_sparse(x.clone(), y.clone(), self.config.clone())?);
+        Ok(())
+    }
+
+    pub fn predict_sparse(&self, x: &CsrMatrix) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.model.as_ref().ok_or("LinearRegression has not been fitted")?.predict_sparse(x)
+    }
+}
+
+impl Fit for LinearRegression {
+    fn fit(&mut self, x: &Array2<f64>, y: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if x.nrows() != y.nrows() {
+            return Err(format!("x has {} rows but y has {}", x.nrows(), y.nrows()).into());
+        }
+        self.model = Some(try_train(x.clone(), y.clone(), self.config.clone())?);
+        Ok(())
+    }
+}
+
+impl FitWeighted for LinearRegression {
+    /// Fits with a weight per row, see `train_weighted`
+    fn fit_weighted(&mut self, x: &Array2<f64>, y: &Array2<f64>, sample_weight: &Array1<f64>) -> Result<(), Box<dyn Error>> {
+        if x.nrows() != y.nrows() {
+            return Err(format!("x has {} rows but y has {}", x.nrows(), y.nrows()).into());
+        }
+        self.model = Some(try_train_weighted(x.clone(), y.clone(), sample_weight.clone(), self.config.clone())?);
+        Ok(())
+    }
+}
+
+impl Predict for LinearRegression {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.model.as_ref().ok_or("LinearRegression has not been fitted")?.try_predict(x)
+    }
+}
+
+impl Score for LinearRegression {}
+
+impl Persist for LinearRegression {}
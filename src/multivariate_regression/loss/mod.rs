@@ -0,0 +1,215 @@
+use std::error::Error;
+use std::fmt::Debug;
+use std::sync::Arc;
+use ndarray::{Array1, Array2, ArrayView2, ArrayViewMut2, Zip};
+use serde::{Deserialize, Serialize};
+use crate::multivariate_regression::regularization::regularization::Regularization;
+
+/// Per-element loss minimized by training, together with its derivative
+///
+/// Every method sees one entry of the output: the target, the prediction and the output
+/// column it belongs to. Implement it and wrap it in `LossFn::custom` to train on a loss of
+/// your own, such as an asymmetric business cost.
+pub trait Loss: Debug + Send + Sync {
+    /// Loss of predicting `y_pred` when the target is `y_true`
+    fn value(&self, y_true: f64, y_pred: f64, column: usize) -> f64;
+
+    /// Derivative of `value` with respect to `y_pred`
+    fn derivative(&self, y_true: f64, y_pred: f64, column: usize) -> f64;
+}
+
+/// Half the squared error, `(y - p)² / 2`, whose derivative is the residual `p - y`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SquaredError;
+
+impl Loss for SquaredError {
+    fn value(&self, y_true: f64, y_pred: f64, _: usize) -> f64 {
+        0.5 * (y_true - y_pred).powi(2)
+    }
+
+    fn derivative(&self, y_true: f64, y_pred: f64, _: usize) -> f64 {
+        y_pred - y_true
+    }
+}
+
+/// Absolute error `|y - p|`; the derivative at zero is taken as 0
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AbsoluteError;
+
+impl Loss for AbsoluteError {
+    fn value(&self, y_true: f64, y_pred: f64, _: usize) -> f64 {
+        (y_true - y_pred).abs()
+    }
+
+    fn derivative(&self, y_true: f64, y_pred: f64, _: usize) -> f64 {
+        if y_pred == y_true { 0. } else { (y_pred - y_true).signum() }
+    }
+}
+
+/// Huber loss: squared below `delta`, linear with slope `delta` above it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HuberLoss {
+    pub delta: f64,
+}
+
+impl Loss for HuberLoss {
+    fn value(&self, y_true: f64, y_pred: f64, _: usize) -> f64 {
+        let diff = (y_true - y_pred).abs();
+        if diff <= self.delta {
+            0.5 * diff.powi(2)
+        } else {
+            self.delta * diff - 0.5 * self.delta.powi(2)
+        }
+    }
+
+    fn derivative(&self, y_true: f64, y_pred: f64, _: usize) -> f64 {
+        (y_pred - y_true).clamp(-self.delta, self.delta)
+    }
+}
+
+/// Pinball loss; output column `j` is fit to quantile `quantiles[j % quantiles.len()]`
+///
+/// Built with `new`, which rejects an empty list and quantiles outside (0, 1); deserializing
+/// checks the same.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawQuantileLoss")]
+pub struct QuantileLoss {
+    quantiles: Vec<f64>,
+}
+
+impl QuantileLoss {
+    pub fn new(quantiles: Vec<f64>) -> Result<Self, Box<dyn Error>> {
+        if quantiles.is_empty() {
+            return Err("At least one quantile is required".into());
+        }
+        if let Some(q) = quantiles.iter().find(|&&q| !(q > 0.0 && q < 1.0)) {
+            return Err(format!("Quantiles should be between 0 and 1, got {}", q).into());
+        }
+        Ok(Self { quantiles })
+    }
+
+    pub fn quantiles(&self) -> &[f64] {
+        &self.quantiles
+    }
+}
+
+/// Unchecked form of `QuantileLoss` read by serde before validation
+#[derive(Deserialize)]
+struct RawQuantileLoss {
+    quantiles: Vec<f64>,
+}
+
+impl TryFrom<RawQuantileLoss> for QuantileLoss {
+    type Error = Box<dyn Error>;
+
+    fn try_from(raw: RawQuantileLoss) -> Result<Self, Self::Error> {
+        QuantileLoss::new(raw.quantiles)
+    }
+}
+
+impl Loss for QuantileLoss {
+    fn value(&self, y_true: f64, y_pred: f64, column: usize) -> f64 {
+        let tau = self.quantiles[column % self.quantiles.len()];
+        let diff = y_true - y_pred;
+        if diff >= 0. { tau * diff } else { (tau - 1.) * diff }
+    }
+
+    fn derivative(&self, y_true: f64, y_pred: f64, column: usize) -> f64 {
+        let tau = self.quantiles[column % self.quantiles.len()];
+        if y_true - y_pred < 0. { 1. - tau } else { -tau }
+    }
+}
+
+/// Loss used by training: one of the built-ins, or any `Loss` implementation
+///
+/// Built-ins are serialized with the model configuration; `Custom` losses cannot be,
+/// so saving a configuration holding one fails.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum LossFn {
+    #[default]
+    SquaredError,
+    AbsoluteError,
+    Huber(HuberLoss),
+    Quantile(QuantileLoss),
+    #[serde(skip)]
+    Custom(Arc<dyn Loss>),
+}
+
+impl LossFn {
+    pub fn huber(delta: f64) -> Self {
+        LossFn::Huber(HuberLoss { delta })
+    }
+
+    /// Pinball loss for one or more quantiles in (0, 1)
+    ///
+    /// With several quantiles, output column `j` is fit to quantile `j % quantiles.len()`.
+    /// An empty list, or a quantile outside (0, 1) or `NaN`, is an error.
+    pub fn quantile(quantiles: Vec<f64>) -> Result<Self, Box<dyn Error>> {
+        Ok(LossFn::Quantile(QuantileLoss::new(quantiles)?))
+    }
+
+    pub fn custom(loss: impl Loss + 'static) -> Self {
+        LossFn::Custom(Arc::new(loss))
+    }
+
+    /// Mean loss of the predictions plus the penalty on `weight`
+    ///
+    /// With `sample_weight`, each row's loss is multiplied by its weight and the sum is divided
+    /// by the total weight instead of the number of rows. The penalty is not averaged, matching
+    /// the penalty term added by `Gradient`, so this is the objective training descends.
+    pub fn calculate_cost(
+        &self,
+        y_true : &Array2<f64>,
+        y_pred : &Array2<f64>,
+        regularization : &Regularization,
+        weight : &Array2<f64>,
+        sample_weight : Option<&Array1<f64>>,
+    ) -> f64 {
+        let mut sum = 0.;
+        for ((row, col), &x) in y_true.indexed_iter() {
+            let loss = self.value(x, y_pred[(row, col)], col);
+            sum += sample_weight.map_or(loss, |sample_weight| sample_weight[row] * loss);
+        }
+        let total = sample_weight.map_or(y_true.len() as f64, |sample_weight| sample_weight.sum() * y_true.ncols() as f64);
+        sum/total + regularization.calculate_regularization(weight)
+    }
+
+    /// Overwrites the predictions in `signal` with the derivative of the loss
+    ///
+    /// Dispatches once per call, so the built-ins run without a virtual call per element.
+    pub(crate) fn derivative_into(&self, y_true: &ArrayView2<f64>, signal: &mut ArrayViewMut2<f64>) {
+        match self {
+            LossFn::SquaredError => fill_derivative(&SquaredError, y_true, signal),
+            LossFn::AbsoluteError => fill_derivative(&AbsoluteError, y_true, signal),
+            LossFn::Huber(loss) => fill_derivative(loss, y_true, signal),
+            LossFn::Quantile(loss) => fill_derivative(loss, y_true, signal),
+            LossFn::Custom(loss) => fill_derivative(loss.as_ref(), y_true, signal),
+        }
+    }
+}
+
+impl Loss for LossFn {
+    fn value(&self, y_true: f64, y_pred: f64, column: usize) -> f64 {
+        match self {
+            LossFn::SquaredError => SquaredError.value(y_true, y_pred, column),
+            LossFn::AbsoluteError => AbsoluteError.value(y_true, y_pred, column),
+            LossFn::Huber(loss) => loss.value(y_true, y_pred, column),
+            LossFn::Quantile(loss) => loss.value(y_true, y_pred, column),
+            LossFn::Custom(loss) => loss.value(y_true, y_pred, column),
+        }
+    }
+
+    fn derivative(&self, y_true: f64, y_pred: f64, column: usize) -> f64 {
+        match self {
+            LossFn::SquaredError => SquaredError.derivative(y_true, y_pred, column),
+            LossFn::AbsoluteError => AbsoluteError.derivative(y_true, y_pred, column),
+            LossFn::Huber(loss) => loss.derivative(y_true, y_pred, column),
+            LossFn::Quantile(loss) => loss.derivative(y_true, y_pred, column),
+            LossFn::Custom(loss) => loss.derivative(y_true, y_pred, column),
+        }
+    }
+}
+
+fn fill_derivative<L: Loss + ?Sized>(loss: &L, y_true: &ArrayView2<f64>, signal: &mut ArrayViewMut2<f64>) {
+    Zip::indexed(signal).and(y_true).for_each(|(_, col), p, &t| *p = loss.derivative(t, *p, col));
+}
@@ -1,8 +1,12 @@
-pub mod cost_fn;
+pub mod loss;
 pub mod regularization;
 pub mod gradient;
 pub mod update_weight;
 pub mod training;
 pub mod input;
-mod normalization;
+pub mod model;
+pub mod linear_regression;
+pub mod inference;
+pub mod intervals;
+pub mod normalization;
 mod weight;
@@ -0,0 +1,138 @@
+use std::error::Error;
+use ndarray::{Array2, ArrayView1, ArrayView2, Axis, s};
+use serde::{Deserialize, Serialize};
+use crate::matrix_operations::{try_matrix_mul, CsrMatrix, LinearOperator, Transpose};
+use crate::multivariate_regression::loss::LossFn;
+use crate::multivariate_regression::normalization::normalization::{apply_normalization, denormalize_data, Normalization};
+use crate::multivariate_regression::regularization::regularization::Regularization;
+use crate::preprocessing::PolynomialFeatures;
+use crate::traits::{Persist, Predict, Score};
+
+/// Linear model returned by `train`
+///
+/// Keeps the input normalization fitted on the training data so that `predict`
+/// takes raw, unnormalized inputs. Serializable, see `Persist`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainedModel {
+    pub(crate) weight: Array2<f64>, // Row 0 holds the bias, row i + 1 the weights of input column i
+    pub(crate) normalization: Normalization,
+    #[serde(default)]
+    pub(crate) target_normalization: Option<Normalization>, // Output scaling applied during training, undone by `predict`
+    pub(crate) regularization: Regularization, // Penalty used by the gradient during training
+    #[serde(default)]
+    pub(crate) loss: Option<LossFn>, // Loss minimized during training; `None` for a custom loss or a model saved before it was recorded
+    #[serde(default)]
+    pub(crate) weighted: bool, // Whether training rows carried sample weights
+}
+
+impl TrainedModel {
+    /// Predicts the output matrix for raw input rows, in original output units
+    ///
+    /// Panics when `input` does not have the training columns; `Predict::predict` returns the error instead.
+    pub fn predict(&self, input: &Array2<f64>) -> Array2<f64> {
+        self.try_predict(input)
+            .unwrap_or_else(|e| panic!("Failed to predict: {}", e))
+    }
+
+    pub(crate) fn try_predict(&self, input: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let mut prediction = try_matrix_mul(&self.prepare_input(input)?, &self.weight)?;
+        if let Some(target_normalization) = &self.target_normalization {
+            denormalize_data(&mut prediction, target_normalization)?;
+        }
+        Ok(prediction)
+    }
+
+    /// Predicts the output matrix for raw sparse input rows without densifying them
+    ///
+    /// Needs a normalization that keeps zeros in place, as fitted by `train_sparse`.
+    pub fn predict_sparse(&self, input: &CsrMatrix) -> Result<Array2<f64>, Box<dyn Error>> {
+        let design = self.normalization.transform_sparse(input)?.prepend_ones();
+        let mut prediction = design.multiply(Transpose::No, &self.weight.view())?;
+        if let Some(target_normalization) = &self.target_normalization {
+            denormalize_data(&mut prediction, target_normalization)?;
+        }
+        Ok(prediction)
+    }
+
+    /// Weights mapping the prepared input directly to original output units
+    ///
+    /// Equal to `weights()` without target normalization; needs a linear one otherwise.
+    pub(crate) fn output_weight(&self) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.fold_target_normalization(self.weight.clone())
+    }
+
+    /// Maps `weight`, fit on the normalized target, to original output units
+    pub(crate) fn fold_target_normalization(&self, weight: Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let Some(target_normalization) = &self.target_normalization else {
+            return Ok(weight);
+        };
+        let (shift, scale) = target_normalization
+            .shift_scale()
+            .ok_or("Only a linear target normalization can be folded into the weights")?;
+        let mut weight = weight * scale.view().insert_axis(Axis(0));
+        weight.row_mut(0).scaled_add(1.0, shift);
+        Ok(weight)
+    }
+
+    /// Normalizes `input` with the training parameters and prepends the bias column
+    ///
+    /// Fails when `input` does not have the training columns; row-wise normalizations do not check widths themselves.
+    pub(crate) fn prepare_input(&self, input: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let n_features = self.weight.nrows() - 1;
+        if input.ncols() != n_features {
+            return Err(format!("Input has {} columns but the model was trained on {}", input.ncols(), n_features).into());
+        }
+        let mut normalized = input.clone();
+        apply_normalization(&mut normalized, &self.normalization)
+            .map_err(|e| format!("Failed to normalize input: {}", e))?;
+
+        Ok(PolynomialFeatures::prepend_bias(&normalized))
+    }
+
+    /// Full weight matrix, bias row first (in normalized input and, if enabled, normalized output space)
+    pub fn weights(&self) -> &Array2<f64> {
+        &self.weight
+    }
+
+    /// Bias of every output (in normalized input space)
+    pub fn bias(&self) -> ArrayView1<'_, f64> {
+        self.weight.index_axis(Axis(0), 0)
+    }
+
+    /// Weights of the input columns, one row per input column (in normalized input space)
+    pub fn coefficients(&self) -> ArrayView2<'_, f64> {
+        self.weight.slice(s![1.., ..])
+    }
+
+    pub fn get_normalization(&self) -> &Normalization {
+        &self.normalization
+    }
+
+    pub fn get_target_normalization(&self) -> Option<&Normalization> {
+        self.target_normalization.as_ref()
+    }
+
+    pub fn get_regularization(&self) -> &Regularization {
+        &self.regularization
+    }
+
+    /// Loss minimized during training, `None` when it was a custom loss
+    pub fn get_loss(&self) -> Option<&LossFn> {
+        self.loss.as_ref()
+    }
+
+    /// Whether the model was trained with sample weights
+    pub fn is_weighted(&self) -> bool {
+        self.weighted
+    }
+}
+
+impl Predict for TrainedModel {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.try_predict(x)
+    }
+}
+
+impl Score for TrainedModel {}
+
+impl Persist for TrainedModel {}
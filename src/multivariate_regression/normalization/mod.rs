@@ -1 +1,2 @@
+#[allow(clippy::module_inception)]
 pub mod normalization;
\ No newline at end of file
@@ -1,79 +1,160 @@
-use ndarray::{Array2};
+use std::error::Error;
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+use crate::matrix_operations::CsrMatrix;
+use crate::preprocessing::{
+    MaxAbsScaler, MinMaxScaler, Norm, Normalizer, PowerMethod, PowerTransformer, QuantileOutput, QuantileTransformer,
+    RobustScaler, Scaler, StandardScaler,
+};
+use crate::traits::{InverseTransform, Transform};
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum NormalizationParameterType {
-    ZParameter,      // Stores mean and variance
-    MinMaxParameter, // Stores min and max values
+    ZParameter,                        // Stores mean and standard deviation
+    MinMaxParameter,                   // Stores min and max values
+    MaxAbsParameter,                   // Stores the maximum absolute value
+    RobustParameter,                   // Stores median and interquartile range
+    QuantileParameter(QuantileOutput), // Stores 1000 empirical quantiles per column
+    PowerParameter(PowerMethod),       // Stores a lambda per column, output standardized
+    UnitNormParameter(Norm),           // Row-wise, stores nothing
 }
 
-// Function to normalize data column-wise (modifies input in-place)
-pub fn normalize_data(
-    normalization_parameter_type: &NormalizationParameterType,
-    input: &mut Array2<f64>,
-) -> Array2<f64> {
-    // Returns normalization parameters
-    let (rows, cols) = input.dim();
-    let mut parameters = Array2::<f64>::zeros((2, cols));
+/// Fitted input normalization of any `NormalizationParameterType`, as stored by a model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Normalization {
+    Standard(StandardScaler),
+    MinMax(MinMaxScaler),
+    MaxAbs(MaxAbsScaler),
+    Robust(RobustScaler),
+    Quantile(QuantileTransformer),
+    Power(PowerTransformer),
+    UnitNorm(Normalizer),
+}
+
+impl Normalization {
+    /// Unfitted scaler of the given type
+    pub fn new(normalization_parameter_type: &NormalizationParameterType) -> Self {
+        match *normalization_parameter_type {
+            NormalizationParameterType::ZParameter => Normalization::Standard(StandardScaler::new()),
+            NormalizationParameterType::MinMaxParameter => Normalization::MinMax(MinMaxScaler::new()),
+            NormalizationParameterType::MaxAbsParameter => Normalization::MaxAbs(MaxAbsScaler::new()),
+            NormalizationParameterType::RobustParameter => Normalization::Robust(RobustScaler::new()),
+            NormalizationParameterType::QuantileParameter(output) => Normalization::Quantile(QuantileTransformer::new(1000, output)),
+            NormalizationParameterType::PowerParameter(method) => Normalization::Power(PowerTransformer::new(method, true)),
+            NormalizationParameterType::UnitNormParameter(norm) => Normalization::UnitNorm(Normalizer::new(norm)),
+        }
+    }
+
+    fn scaler(&self) -> &dyn Scaler {
+        match self {
+            Normalization::Standard(s) => s,
+            Normalization::MinMax(s) => s,
+            Normalization::MaxAbs(s) => s,
+            Normalization::Robust(s) => s,
+            Normalization::Quantile(s) => s,
+            Normalization::Power(s) => s,
+            Normalization::UnitNorm(s) => s,
+        }
+    }
 
-    for col in 0..cols {
-        let column = input.column(col); // Immutable view of column
+    fn scaler_mut(&mut self) -> &mut dyn Scaler {
+        match self {
+            Normalization::Standard(s) => s,
+            Normalization::MinMax(s) => s,
+            Normalization::MaxAbs(s) => s,
+            Normalization::Robust(s) => s,
+            Normalization::Quantile(s) => s,
+            Normalization::Power(s) => s,
+            Normalization::UnitNorm(s) => s,
+        }
+    }
 
-        match normalization_parameter_type {
-            NormalizationParameterType::ZParameter => {
-                let mean = column.mean().unwrap();
-                let std = column.std(0.0);
-                parameters[(0, col)] = mean;
-                parameters[(1, col)] = std;
-                if std != 0.0 {
-                    for row in 0..rows {
-                        input[(row, col)] = (input[(row, col)] - mean) / std;
-                    }
-                }
-            }
-            NormalizationParameterType::MinMaxParameter => {
-                let min = column.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-                let max = column.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-                parameters[(0, col)] = min;
-                parameters[(1, col)] = max;
-                if max != min {
-                    for row in 0..rows {
-                        input[(row, col)] = (input[(row, col)] - min) / (max - min);
-                    }
-                }
-            }
+    /// `(shift, scale)` with `transform(x) = (x - shift) / scale` for linear column scalers, `None` otherwise
+    pub fn shift_scale(&self) -> Option<(&Array1<f64>, &Array1<f64>)> {
+        match self {
+            Normalization::Standard(s) => s.shift_scale(),
+            Normalization::MinMax(s) => s.shift_scale(),
+            Normalization::MaxAbs(s) => s.shift_scale(),
+            Normalization::Robust(s) => s.shift_scale(),
+            _ => None,
         }
     }
+}
 
-    parameters
+impl Normalization {
+    /// Applies the fitted normalization to a CSR matrix without densifying it
+    ///
+    /// Only linear scalers with a zero shift keep zeros in place (e.g. `MaxAbsParameter`).
+    pub fn transform_sparse(&self, x: &CsrMatrix) -> Result<CsrMatrix, Box<dyn Error>> {
+        let (shift, scale) = self
+            .shift_scale()
+            .ok_or("Only a fitted linear normalization can scale a sparse input")?;
+        if shift.iter().any(|&s| s != 0.0) {
+            return Err("Centering would densify a sparse input, use MaxAbsParameter".into());
+        }
+        if x.ncols() != scale.len() {
+            return Err(format!("Normalization was fitted on {} columns but got {}", scale.len(), x.ncols()).into());
+        }
+        Ok(x.map_columns(|v, col| v / scale[col]))
+    }
 }
 
-// Function to denormalize data column-wise (modifies input in-place)
-pub fn denormalize_data(
+impl Transform for Normalization {
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        self.scaler_mut().fit(x)
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.scaler().transform(x)
+    }
+}
+
+impl InverseTransform for Normalization {
+    fn inverse_transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.scaler().inverse_transform(x)
+    }
+}
+
+// Function to normalize data column-wise (modifies input in-place)
+pub fn normalize_data(
+    normalization_parameter_type: &NormalizationParameterType,
     input: &mut Array2<f64>,
+) -> Result<Normalization, Box<dyn Error>> {
+    // Returns the fitted normalization
+    let mut normalization = Normalization::new(normalization_parameter_type);
+    *input = normalization.fit_transform(input)?;
+    Ok(normalization)
+}
+
+// Function to normalize a sparse input column-wise, returns the fitted normalization and the scaled copy
+pub fn normalize_sparse(
     normalization_parameter_type: &NormalizationParameterType,
-    normalization_parameters: &Array2<f64>,
-) {
-    let (rows, cols) = input.dim();
+    input: &CsrMatrix,
+) -> Result<(Normalization, CsrMatrix), Box<dyn Error>> {
+    let NormalizationParameterType::MaxAbsParameter = normalization_parameter_type else {
+        return Err(format!("{:?} does not keep a sparse input sparse, use MaxAbsParameter", normalization_parameter_type).into());
+    };
+    let mut scaler = MaxAbsScaler::new();
+    scaler.fit_sparse(input)?;
+    let normalization = Normalization::MaxAbs(scaler);
+    let scaled = normalization.transform_sparse(input)?;
+    Ok((normalization, scaled))
+}
 
-    for col in 0..cols {
-        match normalization_parameter_type {
-            NormalizationParameterType::ZParameter => {
-                let mean = normalization_parameters[(0, col)];
-                let std = normalization_parameters[(1, col)];
-                if std != 0.0 {
-                    for row in 0..rows {
-                        input[(row, col)] = input[(row, col)] * std + mean;
-                    }
-                }
-            }
-            NormalizationParameterType::MinMaxParameter => {
-                let min = normalization_parameters[(0, col)];
-                let max = normalization_parameters[(1, col)];
-                if max != min {
-                    for row in 0..rows {
-                        input[(row, col)] = input[(row, col)] * (max - min) + min;
-                    }
-                }
-            }
-        }
-    }
+// Function to normalize data with a previously fitted normalization (modifies input in-place)
+pub fn apply_normalization(
+    input: &mut Array2<f64>,
+    normalization: &Normalization,
+) -> Result<(), Box<dyn Error>> {
+    *input = normalization.transform(input)?;
+    Ok(())
+}
+
+// Function to denormalize data (modifies input in-place)
+pub fn denormalize_data(
+    input: &mut Array2<f64>,
+    normalization: &Normalization,
+) -> Result<(), Box<dyn Error>> {
+    *input = normalization.inverse_transform(input)?;
+    Ok(())
 }
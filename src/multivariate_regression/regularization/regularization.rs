@@ -1,13 +1,14 @@
 use ndarray::Array2;
+use serde::{Deserialize, Serialize};
 
-#[derive(Copy,Clone)]
+#[derive(Debug,Copy,Clone,PartialEq,Serialize,Deserialize)]
 pub enum RegularizationType {
     LassoL1,
     RidgeL2,
     ElasticNet,
 }
 
-#[derive(Copy,Clone)]
+#[derive(Debug,Copy,Clone,Serialize,Deserialize)]
 pub struct Regularization {
     pub regularization_type: RegularizationType,
     pub lambda1: f64,
@@ -1,2 +1,3 @@
 pub mod train;
-pub mod train_config;
\ No newline at end of file
+pub mod train_config;
+pub mod quantile;
@@ -0,0 +1,81 @@
+use std::error::Error;
+use ndarray::{Array2, Axis};
+use crate::multivariate_regression::loss::LossFn;
+use crate::multivariate_regression::model::TrainedModel;
+use crate::multivariate_regression::training::train::try_train;
+use crate::multivariate_regression::training::train_config::TrainConfig;
+
+/// Linear model fit to several quantiles of every output at once
+pub struct QuantileModel {
+    model: TrainedModel, // Output column o * quantiles.len() + q predicts quantile q of output o
+    quantiles: Vec<f64>,
+    n_outputs: usize,
+}
+
+impl QuantileModel {
+    /// Predicts every quantile, one matrix per quantile in ascending quantile order
+    ///
+    /// Quantiles are fit independently and may cross; each row is sorted across
+    /// quantiles so that the returned predictions never do.
+    pub fn predict(&self, input: &Array2<f64>) -> Vec<Array2<f64>> {
+        let tiled = self.model.predict(input);
+        let n_quantiles = self.quantiles.len();
+        let mut predictions = vec![Array2::<f64>::zeros((input.nrows(), self.n_outputs)); n_quantiles];
+        for (row, values) in tiled.axis_iter(Axis(0)).enumerate() {
+            for output in 0..self.n_outputs {
+                let mut column: Vec<f64> = (0..n_quantiles).map(|q| values[output * n_quantiles + q]).collect();
+                column.sort_by(|a, b| a.total_cmp(b));
+                for (q, value) in column.into_iter().enumerate() {
+                    predictions[q][(row, output)] = value;
+                }
+            }
+        }
+        predictions
+    }
+
+    /// Fitted quantiles, ascending
+    pub fn quantiles(&self) -> &[f64] {
+        &self.quantiles
+    }
+
+    /// Underlying model over the tiled outputs
+    pub fn model(&self) -> &TrainedModel {
+        &self.model
+    }
+}
+
+/// Function to fit several quantiles of every output with the pinball loss
+///
+/// The output matrix is tiled once per quantile and trained in a single run;
+/// The `loss` of `config` is replaced by the pinball loss.
+///
+/// # Parameters:
+/// - `input: Array2<f64>` - Feature matrix
+/// - `output: Array2<f64>` - Target matrix
+/// - `quantiles: &[f64]` - Quantiles in (0, 1)
+/// - `config: TrainConfig` - Remaining training settings
+///
+/// # Returns:
+/// - `Result<QuantileModel, Box<dyn Error>>` - Model predicting every quantile
+pub fn train_quantiles(
+    input: Array2<f64>,
+    output: Array2<f64>,
+    quantiles: &[f64],
+    mut config: TrainConfig,
+) -> Result<QuantileModel, Box<dyn Error>> {
+    let mut quantiles = quantiles.to_vec();
+    quantiles.sort_by(|a, b| a.total_cmp(b));
+    let loss = LossFn::quantile(quantiles.clone())?;
+
+    let n_outputs = output.ncols();
+    let tiled_columns: Vec<usize> = (0..n_outputs).flat_map(|o| std::iter::repeat_n(o, quantiles.len())).collect();
+    let tiled = output.select(Axis(1), &tiled_columns);
+
+    config.loss = Some(loss);
+
+    Ok(QuantileModel {
+        model: try_train(input, tiled, config)?,
+        quantiles,
+        n_outputs,
+    })
+}
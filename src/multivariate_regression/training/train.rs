@@ -1,42 +1,216 @@
-use ndarray::Array2;
-use crate::multivariate_regression::cost_fn::cost_fn::{CostFn, CostFnType};
+use std::error::Error;
+use ndarray::{Array1, Array2};
+use crate::matrix_operations::{CsrMatrix, LinearOperator};
 use crate::multivariate_regression::gradient::Gradient;
-use crate::multivariate_regression::gradient::gradient_type::GradientType;
 use crate::multivariate_regression::input::input::Input;
+use crate::multivariate_regression::loss::LossFn;
+use crate::multivariate_regression::model::TrainedModel;
+use crate::multivariate_regression::normalization::normalization::{normalize_data, normalize_sparse, NormalizationParameterType};
 use crate::multivariate_regression::regularization::regularization::{Regularization, RegularizationType};
 use crate::multivariate_regression::training::train_config::TrainConfig;
-use crate::multivariate_regression::update_weight::{update_weight, MiniBatchSize, UpdatationMethod};
+use crate::multivariate_regression::update_weight::{update_weight, TrainingWorkspace, UpdatationMethod};
 use crate::multivariate_regression::weight::weight::Weight;
 
 pub fn train(
     input: Array2<f64>,
     output: Array2<f64>,
     config : TrainConfig
-){
+) -> TrainedModel {
+    try_train(input, output, config)
+        .unwrap_or_else(|e| panic!("Linear Regression :: train :: {}", e))
+}
+
+/// Function to train a linear model, returning an error where `train` panics
+///
+/// # Parameters:
+/// - `input: Array2<f64>` - Input matrix
+/// - `output: Array2<f64>` - Output matrix
+/// - `config: TrainConfig` - As for `train`
+///
+/// # Returns:
+/// - `Result<TrainedModel, Box<dyn Error>>` - Fails when a normalization cannot be fitted to the data
+pub fn try_train(
+    input: Array2<f64>,
+    output: Array2<f64>,
+    config : TrainConfig
+) -> Result<TrainedModel, Box<dyn Error>> {
+    // normalization parameter
+    let input_struct = Input::new(input,output,config.normalization_parameter_type,config.target_normalization_parameter_type)
+        .map_err(|e| format!("Failed to normalize input : {}", e))?;
+
+    let (weight, regularization, loss) = fit_weight(&input_struct.input, &input_struct.output, None, config);
+
+    Ok(TrainedModel {
+        weight,
+        normalization: input_struct.get_normalization().clone(),
+        target_normalization: input_struct.get_target_normalization().cloned(),
+        regularization,
+        loss,
+        weighted: false,
+    })
+}
+
+/// Function to train a linear model in which some rows count more than others
+///
+/// # Parameters:
+/// - `input: Array2<f64>` - Input matrix
+/// - `output: Array2<f64>` - Output matrix
+/// - `sample_weight: Array1<f64>` - Non-negative weight of every row; scaling all weights by a constant changes nothing
+/// - `config: TrainConfig` - As for `train`
+///
+/// The loss and gradient of every batch weight each row's contribution, so a row of weight 2
+/// acts like the same row included twice. Normalization statistics are not weighted.
+///
+/// # Returns:
+/// - `TrainedModel`
+pub fn train_weighted(
+    input: Array2<f64>,
+    output: Array2<f64>,
+    sample_weight: Array1<f64>,
+    config : TrainConfig
+) -> TrainedModel {
+    try_train_weighted(input, output, sample_weight, config)
+        .unwrap_or_else(|e| panic!("Linear Regression :: train_weighted :: {}", e))
+}
+
+/// Function to train a weighted linear model, returning an error where `train_weighted` panics
+///
+/// # Parameters:
+/// - `input: Array2<f64>` - Input matrix
+/// - `output: Array2<f64>` - Output matrix
+/// - `sample_weight: Array1<f64>` - As for `train_weighted`
+/// - `config: TrainConfig` - As for `train`
+///
+/// # Returns:
+/// - `Result<TrainedModel, Box<dyn Error>>` - Fails on invalid sample weights or a normalization that cannot be fitted
+pub fn try_train_weighted(
+    input: Array2<f64>,
+    output: Array2<f64>,
+    sample_weight: Array1<f64>,
+    config : TrainConfig
+) -> Result<TrainedModel, Box<dyn Error>> {
+    check_sample_weight(&sample_weight, input.nrows())?;
+    let input_struct = Input::new(input,output,config.normalization_parameter_type,config.target_normalization_parameter_type)
+        .map_err(|e| format!("Failed to normalize input : {}", e))?;
+
+    let (weight, regularization, loss) = fit_weight(&input_struct.input, &input_struct.output, Some(&sample_weight), config);
+
+    Ok(TrainedModel {
+        weight,
+        normalization: input_struct.get_normalization().clone(),
+        target_normalization: input_struct.get_target_normalization().cloned(),
+        regularization,
+        loss,
+        weighted: true,
+    })
+}
+
+/// Sample weights must be finite, non-negative, not all zero and one per row
+pub(crate) fn check_sample_weight(sample_weight: &Array1<f64>, n_rows: usize) -> Result<(), Box<dyn Error>> {
+    if sample_weight.len() != n_rows {
+        return Err(format!("{} sample weights for {} rows", sample_weight.len(), n_rows).into());
+    }
+    if sample_weight.iter().any(|w| !w.is_finite() || *w < 0.) {
+        return Err("Sample weights must be finite and non-negative".into());
+    }
+    if sample_weight.sum() == 0. {
+        return Err("Sample weights must not all be zero".into());
+    }
+    Ok(())
+}
+
+/// Function to train a linear model on a sparse input without densifying it
+///
+/// # Parameters:
+/// - `input: CsrMatrix` - Sparse input matrix
+/// - `output: Array2<f64>` - Dense output matrix
+/// - `config: TrainConfig` - As for `train`; the input normalization defaults to, and must be, `MaxAbsParameter`
+///
+/// Shapes and normalizations are checked before training starts: a normalization that would
+/// densify the input, or a row (`UnitNormParameter`) target normalization, which cannot be
+/// inverted, is an error.
+///
+/// # Returns:
+/// - `Result<TrainedModel, Box<dyn Error>>` - Predicts dense inputs with `predict` and sparse ones with `predict_sparse`
+pub fn train_sparse(
+    input: CsrMatrix,
+    output: Array2<f64>,
+    config : TrainConfig
+) -> Result<TrainedModel, Box<dyn Error>> {
+    fit_sparse(input, output, None, config)
+}
+
+/// Function to train a linear model on a sparse input in which some rows count more than others
+///
+/// # Parameters:
+/// - `input: CsrMatrix` - Sparse input matrix
+/// - `output: Array2<f64>` - Dense output matrix
+/// - `sample_weight: Array1<f64>` - Non-negative weight of every row, as for `train_weighted`
+/// - `config: TrainConfig` - As for `train_sparse`
+///
+/// # Returns:
+/// - `Result<TrainedModel, Box<dyn Error>>`
+pub fn train_sparse_weighted(
+    input: CsrMatrix,
+    output: Array2<f64>,
+    sample_weight: Array1<f64>,
+    config : TrainConfig
+) -> Result<TrainedModel, Box<dyn Error>> {
+    check_sample_weight(&sample_weight, input.nrows())?;
+    fit_sparse(input, output, Some(&sample_weight), config)
+}
+
+fn fit_sparse(
+    input: CsrMatrix,
+    mut output: Array2<f64>,
+    sample_weight: Option<&Array1<f64>>,
+    config : TrainConfig
+) -> Result<TrainedModel, Box<dyn Error>> {
+    if input.nrows() != output.nrows() {
+        return Err(format!("Sparse input has {} rows but output has {}", input.nrows(), output.nrows()).into());
+    }
+    if let Some(NormalizationParameterType::UnitNormParameter(_)) = config.target_normalization_parameter_type {
+        return Err("Row normalization cannot be inverted, so it cannot scale the output of train_sparse".into());
+    }
+
+    let normalization_parameter_type = config.normalization_parameter_type.unwrap_or(NormalizationParameterType::MaxAbsParameter);
+    let (normalization, scaled) = normalize_sparse(&normalization_parameter_type, &input)
+        .map_err(|e| format!("Failed to normalize sparse input: {}", e))?;
+    let target_normalization = match config.target_normalization_parameter_type {
+        Some(parameter_type) => Some(normalize_data(&parameter_type, &mut output)
+            .map_err(|e| format!("Failed to normalize output: {}", e))?),
+        None => None,
+    };
+
+    let (weight, regularization, loss) = fit_weight(&scaled, &output, sample_weight, config);
+
+    Ok(TrainedModel {
+        weight,
+        normalization,
+        target_normalization,
+        regularization,
+        loss,
+        weighted: sample_weight.is_some(),
+    })
+}
+
+/// Runs the configured optimizer on a normalized design matrix; the bias is row 0 of the weights, not a column
+///
+/// Returns the weights, the regularization used by the gradient and the loss, `None` if custom.
+fn fit_weight<M: LinearOperator>(design: &M, output: &Array2<f64>, sample_weight: Option<&Array1<f64>>, config: TrainConfig) -> (Array2<f64>, Regularization, Option<LossFn>) {
     let TrainConfig {
         epochs,
         lr,
-        normalization_parameter_type,
         optimizer,
         mini_batch_size,
         regularization,
-        cost_fn,
-        gradient_fn,
-        delta,
+        loss,
         print_log,
+        ..
     } = config; 
-    // normalization parameter
-    let mut input_struct = Input::new(input.clone(),output.clone(),normalization_parameter_type);
-    input_struct.adjust_input(); // adjust input to accomodate bias term of weight
     
     // initialize weight
-    let mut weight = Weight::init((input_struct.input.ncols(),input_struct.output.ncols()));
-    
-    // initialize cost function
-    let cost_fn = cost_fn.unwrap_or(CostFn {
-        cost_fn_type : CostFnType::MeanSquaredError,
-        parameter : 1.,
-    });
+    let mut weight = Weight::init((design.dim().1 + 1,output.ncols()));
 
     let optimizer = optimizer.unwrap_or(UpdatationMethod::BGD);
     
@@ -48,20 +222,21 @@ pub fn train(
         }
     );
     
-    let gradient_fn = gradient_fn.unwrap_or(
-        Gradient{
-            gradient : GradientType::MeanSquaredError,
-            regularization,
-        }
-    );
+    // one loss drives both the logged cost and the gradient
+    let gradient_fn = Gradient::new(loss.unwrap_or_default(), regularization);
     
-    let delta = delta.unwrap_or(1.);
-    
-    // main loop for training
+    // main loop for training, every epoch reuses the same buffers
+    let mut workspace = TrainingWorkspace::new();
     for epoch in 0..epochs {
         if print_log{
             println!("Epoch {}:", epoch);
         }
-        update_weight(&input_struct.input, &output, weight.get_mut(), &optimizer, &mini_batch_size, regularization, &gradient_fn,  &cost_fn, delta, lr, print_log);
+        update_weight(design, output, sample_weight, weight.get_mut(), &optimizer, &mini_batch_size, &gradient_fn, lr, print_log, &mut workspace);
     }
-}
\ No newline at end of file
+
+    let loss = match gradient_fn.loss {
+        LossFn::Custom(_) => None, // Not serializable with the model
+        loss => Some(loss),
+    };
+    (weight.get(), gradient_fn.regularization, loss)
+}
@@ -1,19 +1,20 @@
-use crate::multivariate_regression::cost_fn::cost_fn::CostFn;
-use crate::multivariate_regression::gradient::Gradient;
+use serde::{Deserialize, Serialize};
+use crate::multivariate_regression::loss::LossFn;
 use crate::multivariate_regression::normalization::normalization::NormalizationParameterType;
 use crate::multivariate_regression::regularization::regularization::Regularization;
 use crate::multivariate_regression::update_weight::{MiniBatchSize, UpdatationMethod};
 
+/// Hyperparameters of `train`, see `TrainConfigBuilder`
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrainConfig {
     pub epochs: usize,
     pub lr: f64,
     pub normalization_parameter_type: Option<NormalizationParameterType>,
+    pub target_normalization_parameter_type: Option<NormalizationParameterType>,
     pub optimizer: Option<UpdatationMethod>,
     pub mini_batch_size: Option<MiniBatchSize>,
     pub regularization: Option<Regularization>,
-    pub cost_fn: Option<CostFn>,
-    pub gradient_fn: Option<Gradient>,
-    pub delta: Option<f64>,
+    pub loss: Option<LossFn>, // Minimized loss, squared error when `None`
     pub print_log: bool,
 }
 
@@ -23,12 +24,11 @@ impl Default for TrainConfig {
             epochs: 1000,
             lr: 0.01,
             normalization_parameter_type: None,
+            target_normalization_parameter_type: None,
             optimizer: None,
             mini_batch_size: None,
             regularization: None,
-            cost_fn: None,
-            gradient_fn: None,
-            delta: Some(1.0),
+            loss: None,
             print_log: false,
         }
     }
@@ -38,6 +38,12 @@ pub struct TrainConfigBuilder {
     config: TrainConfig,
 }
 
+impl Default for TrainConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TrainConfigBuilder {
     pub fn new() -> Self {
         Self {
@@ -60,6 +66,12 @@ impl TrainConfigBuilder {
         self
     }
 
+    /// Also scales the output for training; predictions are mapped back to original units
+    pub fn target_normalization(mut self, normalization: NormalizationParameterType) -> Self {
+        self.config.target_normalization_parameter_type = Some(normalization);
+        self
+    }
+
     pub fn optimizer(mut self, optimizer: UpdatationMethod) -> Self {
         self.config.optimizer = Some(optimizer);
         self
@@ -75,18 +87,9 @@ impl TrainConfigBuilder {
         self
     }
 
-    pub fn cost_fn(mut self, cost: CostFn) -> Self {
-        self.config.cost_fn = Some(cost);
-        self
-    }
-
-    pub fn gradient_fn(mut self, gradient: Gradient) -> Self {
-        self.config.gradient_fn = Some(gradient);
-        self
-    }
-
-    pub fn delta(mut self, delta: f64) -> Self {
-        self.config.delta = Some(delta);
+    /// Loss used for both the logged cost and the gradient
+    pub fn loss(mut self, loss: LossFn) -> Self {
+        self.config.loss = Some(loss);
         self
     }
 
@@ -1,16 +1,16 @@
-use ndarray::{Array2, Axis, s};
-use crate::matrix_operations::matrix_mul;
-use crate::multivariate_regression::cost_fn::cost_fn::CostFn;
+use ndarray::{Array1, Array2, s};
+use serde::{Deserialize, Serialize};
+use crate::matrix_operations::{LinearOperator, Transpose};
 use crate::multivariate_regression::gradient::Gradient;
-use crate::multivariate_regression::regularization::regularization::Regularization;
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum UpdatationMethod {
     SGD,
     BGD,
     MiniBatchGD
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum MiniBatchSize {
     Small = 4,
     Medium = 16,
@@ -18,61 +18,79 @@ pub enum MiniBatchSize {
     ExtraLarge = 256
 }
 
-pub fn update_weight(
-    input : &Array2<f64>,
+/// Buffers reused by every step of `update_weight`, so that training allocates only once
+///
+/// Sized on first use for the batch size, input width and output width; later steps borrow them.
+#[derive(Debug, Clone, Default)]
+pub struct TrainingWorkspace {
+    prediction: Array2<f64>, // Predictions of a batch, turned into the loss derivative in place
+    gradient: Array2<f64>,   // Same shape as the weights, bias row first
+}
+
+impl TrainingWorkspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reallocates only when the shapes change, which happens once per training run
+    fn reserve(&mut self, batch_rows: usize, n_features: usize, n_outputs: usize) {
+        if self.prediction.dim() != (batch_rows, n_outputs) {
+            self.prediction = Array2::zeros((batch_rows, n_outputs));
+        }
+        if self.gradient.dim() != (n_features + 1, n_outputs) {
+            self.gradient = Array2::zeros((n_features + 1, n_outputs));
+        }
+    }
+}
+
+/// Runs one epoch of the chosen optimizer without allocating per step
+///
+/// `input` may be a dense `Array2` or a `CsrMatrix` and has no bias column: row 0 of `weight`
+/// is the bias. Batches are read in place and the gradient is accumulated in `workspace`.
+/// With `sample_weight`, every batch weights its rows and a batch of zero total weight is skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn update_weight<M: LinearOperator>(
+    input : &M,
     output : &Array2<f64>,
+    sample_weight : Option<&Array1<f64>>,
     weight: &mut Array2<f64>,
     updatation_method: &UpdatationMethod,
     mini_batch_size: &Option<MiniBatchSize>,
-    regularization: Regularization,
     grad : &Gradient,
-    cost_fn : &CostFn,
-    delta : f64,
     lr : f64,
     log : bool,
-    
+    workspace : &mut TrainingWorkspace,
 ) {
-    match updatation_method {
-        UpdatationMethod::SGD => {
-            for i in 0..input.nrows() {
-                let input_row = input.slice(s![i..i+1, ..]).to_owned();  // 2D slice of one row
-                let output_row = output.slice(s![i..i+1, ..]).to_owned();
-                let pred = matrix_mul(&input_row, weight); 
-                if log {
-                    let cost = cost_fn.calculate_cost(&output_row, &pred, &regularization, weight);
-                    println!(" {}", cost);
-                }
-                let gradient = grad.calculate_gradient(delta, &input_row, &pred, &output_row, weight);
-                *weight -= &(lr * gradient);
-            }
-        },
-        UpdatationMethod::BGD => {
-            let pred = matrix_mul(input,&weight);
-            if log {
-                let cost = cost_fn.calculate_cost(output,&pred,&regularization,&weight);
-                println!(" {}", cost);
-            }
-            let gradient = grad.calculate_gradient(delta,input,&pred,output,&weight);
-            *weight -= &(lr * gradient);
-        },
-        UpdatationMethod::MiniBatchGD => {
-            let batch_size = mini_batch_size.clone().unwrap_or(MiniBatchSize::Medium) as usize;
-            let mut batches = input.nrows()/batch_size as usize + 1;
-            let mut tmp = 0;
-            while batches > 0 {
-                let batch_ip = input.slice(s![tmp..tmp+batch_size, ..]).to_owned();
-                let batch_op = output.slice(s![tmp..tmp+batch_size, ..]).to_owned();
-                let pred = matrix_mul(&batch_ip,&weight);
-                if log {
-                    let cost = cost_fn.calculate_cost(output,&pred,&regularization,&weight);
-                    print!(" {}", cost);
-                } 
-                let gradient = grad.calculate_gradient(delta,&batch_ip,&pred,&batch_op,&weight);
-                *weight -= &(lr * gradient);
-                batches -= 1;
-                tmp += batch_ip.len();
+    let (n_rows, n_features) = input.dim();
+    let batch_size = match updatation_method {
+        UpdatationMethod::SGD => 1,
+        UpdatationMethod::BGD => n_rows,
+        UpdatationMethod::MiniBatchGD => mini_batch_size.unwrap_or(MiniBatchSize::Medium) as usize,
+    };
+    workspace.reserve(batch_size.min(n_rows), n_features, output.ncols());
+    let TrainingWorkspace { prediction, gradient } = workspace;
+
+    // The last batch holds the remaining rows and may be smaller
+    for start in (0..n_rows).step_by(batch_size.max(1)) {
+        let end = (start + batch_size).min(n_rows);
+        let batch_op = output.slice(s![start..end, ..]);
+        let batch_weight = sample_weight.map(|w| w.slice(s![start..end]));
+        if batch_weight.is_some_and(|w| w.sum() == 0.) {
+            continue;
+        }
+        let mut pred = prediction.slice_mut(s![..end - start, ..]);
+        input
+            .mul_rows_into(start..end, 1., Transpose::No, &weight.slice(s![1.., ..]), 0., &mut pred)
+            .unwrap_or_else(|e| panic!("Matrix dimensions do not match for multiplication! {}", e));
+        pred += &weight.row(0);
+        if log {
+            let cost = grad.calculate_cost(&batch_op.to_owned(), &pred.to_owned(), weight, batch_weight.map(|w| w.to_owned()).as_ref());
+            match updatation_method {
+                UpdatationMethod::MiniBatchGD => print!(" {}", cost),
+                _ => println!(" {}", cost),
             }
-            
         }
+        grad.gradient_into(input, start..end, &batch_op, batch_weight, &mut pred, weight, gradient);
+        weight.scaled_add(-lr, gradient);
     }
 }
\ No newline at end of file
@@ -20,15 +20,5 @@ impl Weight {
 
         }
     }
-    pub fn update(&mut self ,lr : f64, gradient : &Array2<f64>){
-        self.weight_matrix = &self.weight_matrix - (lr*gradient);
-    }
-    pub fn multiply(&self, input: &Array2<f64>) -> Array2<f64> {
-        let result = self.weight_matrix.dot(input);
-        result
-    }
-    pub fn print(&self){
-        println!("weight matrix : {}",self.weight_matrix);
-    }
 
 }
@@ -0,0 +1,154 @@
+use std::error::Error;
+use ndarray::{Array2, ArrayView2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::data_utils::ColumnSelector;
+use crate::pipeline::Step;
+use crate::traits::Transform;
+
+/// What a `ColumnTransformer` does with the columns no step selected
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Remainder {
+    Drop,
+    Passthrough, // Appended unchanged after the transformed columns
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ColumnStep {
+    name: String,
+    step: Step,
+    columns: Vec<ColumnSelector>,
+    indices: Vec<usize>, // Resolved positions of `columns`, set by `fit`
+}
+
+/// Applies different steps to different named columns and joins their outputs
+///
+/// Outputs are placed side by side in the order the steps were added, followed by
+/// the remainder columns when they are passed through. A column may feed several steps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnTransformer {
+    column_names: Vec<String>,
+    steps: Vec<ColumnStep>,
+    remainder: Remainder,
+    remainder_indices: Vec<usize>,
+    fitted: bool,
+}
+
+impl ColumnTransformer {
+    /// Creates a transformer without steps
+    ///
+    /// # Parameters:
+    /// - `column_names: &[String]` - Names of the input columns, e.g. `Dataset::column_names()`
+    /// - `remainder: Remainder` - What to do with the columns no step selected
+    pub fn new(column_names: &[String], remainder: Remainder) -> Self {
+        Self {
+            column_names: column_names.to_vec(),
+            steps: Vec::new(),
+            remainder,
+            remainder_indices: Vec::new(),
+            fitted: false,
+        }
+    }
+
+    /// Adds a step applied to the selected columns, by name or position
+    pub fn add<C: Into<ColumnSelector>>(
+        mut self,
+        name: &str,
+        step: impl Into<Step>,
+        columns: impl IntoIterator<Item = C>,
+    ) -> Self {
+        self.steps.push(ColumnStep {
+            name: name.to_string(),
+            step: step.into(),
+            columns: columns.into_iter().map(Into::into).collect(),
+            indices: Vec::new(),
+        });
+        self
+    }
+
+    /// Output column names for the given input names, in output order
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        if !self.fitted {
+            return Err("ColumnTransformer has not been fitted".into());
+        }
+        if input_names.len() != self.column_names.len() {
+            return Err(format!("Got {} names for {} input columns", input_names.len(), self.column_names.len()).into());
+        }
+
+        let mut names = Vec::new();
+        for column_step in &self.steps {
+            let step_names: Vec<String> = column_step.indices.iter().map(|&i| input_names[i].clone()).collect();
+            names.extend(column_step.step.feature_names(&step_names)?);
+        }
+        if self.remainder == Remainder::Passthrough {
+            names.extend(self.remainder_indices.iter().map(|&i| input_names[i].clone()));
+        }
+        Ok(names)
+    }
+
+    /// Output column names for the column names given at construction
+    pub fn output_names(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        self.feature_names(&self.column_names)
+    }
+
+    /// Step added under `name`
+    pub fn get_step(&self, name: &str) -> Option<&Step> {
+        self.steps.iter().find(|column_step| column_step.name == name).map(|column_step| &column_step.step)
+    }
+
+    fn check_columns(&self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if x.ncols() != self.column_names.len() {
+            return Err(format!(
+                "ColumnTransformer has {} named columns but got {}",
+                self.column_names.len(),
+                x.ncols()
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl Transform for ColumnTransformer {
+    /// Resolves the selected columns and fits every step on its columns of `x`
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        self.check_columns(x)?;
+        let mut used = vec![false; x.ncols()];
+        for column_step in &mut self.steps {
+            if column_step.columns.is_empty() {
+                return Err(format!("Step '{}' selects no columns", column_step.name).into());
+            }
+            column_step.indices = column_step
+                .columns
+                .iter()
+                .map(|column| column.resolve(&self.column_names))
+                .collect::<Result<Vec<usize>, _>>()?;
+            column_step.indices.iter().for_each(|&i| used[i] = true);
+            column_step.step.fit(&x.select(Axis(1), &column_step.indices))?;
+        }
+        self.remainder_indices = (0..x.ncols()).filter(|&i| !used[i]).collect();
+        self.fitted = true;
+        Ok(())
+    }
+
+    /// Applies every fitted step to its columns of `x` and concatenates the results
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        if !self.fitted {
+            return Err("ColumnTransformer has not been fitted".into());
+        }
+        self.check_columns(x)?;
+
+        let mut parts = self
+            .steps
+            .iter()
+            .map(|column_step| column_step.step.transform(&x.select(Axis(1), &column_step.indices)))
+            .collect::<Result<Vec<Array2<f64>>, _>>()?;
+        if self.remainder == Remainder::Passthrough {
+            parts.push(x.select(Axis(1), &self.remainder_indices));
+        }
+        if parts.is_empty() {
+            return Ok(Array2::zeros((x.nrows(), 0)));
+        }
+        let views: Vec<ArrayView2<f64>> = parts.iter().map(|part| part.view()).collect();
+        Ok(ndarray::concatenate(Axis(1), &views)?)
+    }
+}
@@ -0,0 +1,8 @@
+mod step;
+mod column_transformer;
+#[allow(clippy::module_inception)]
+mod pipeline;
+
+pub use step::*;
+pub use column_transformer::*;
+pub use pipeline::*;
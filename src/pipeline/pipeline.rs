@@ -0,0 +1,92 @@
+use std::error::Error;
+use ndarray::Array2;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::multivariate_regression::linear_regression::LinearRegression;
+use crate::pipeline::Step;
+use crate::traits::{Fit, Persist, Predict, Score, Transform};
+
+/// Chain of named transformer steps followed by an estimator
+///
+/// `fit` fits every step in order on the output of the previous one, then fits the
+/// estimator on the final features. `predict` applies the same fitted steps, so the
+/// whole preprocessing travels with the model and is saved with it as one JSON file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pipeline<E = LinearRegression> {
+    steps: Vec<(String, Step)>,
+    estimator: E,
+}
+
+impl<E> Pipeline<E> {
+    /// Creates a pipeline without steps around an unfitted estimator
+    pub fn new(estimator: E) -> Self {
+        Self { steps: Vec::new(), estimator }
+    }
+
+    /// Appends a step under `name`
+    pub fn step(mut self, name: &str, step: impl Into<Step>) -> Self {
+        self.steps.push((name.to_string(), step.into()));
+        self
+    }
+
+    /// Applies the fitted steps to `input`, giving the features seen by the estimator
+    pub fn transform(&self, input: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let mut features = input.clone();
+        for (name, step) in &self.steps {
+            features = step
+                .transform(&features)
+                .map_err(|e| format!("Pipeline step '{}' failed : {}", name, e))?;
+        }
+        Ok(features)
+    }
+
+    /// Names of the estimator features for the given raw input names
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut names = input_names.to_vec();
+        for (_, step) in &self.steps {
+            names = step.feature_names(&names)?;
+        }
+        Ok(names)
+    }
+
+    pub fn steps(&self) -> &[(String, Step)] {
+        &self.steps
+    }
+
+    /// Step appended under `name`
+    pub fn get_step(&self, name: &str) -> Option<&Step> {
+        self.steps.iter().find(|(step_name, _)| step_name == name).map(|(_, step)| step)
+    }
+
+    pub fn estimator(&self) -> &E {
+        &self.estimator
+    }
+}
+
+impl<E: Fit> Fit for Pipeline<E> {
+    /// Fits all steps in order (feature selectors see `y`), then the estimator on the transformed input
+    fn fit(&mut self, x: &Array2<f64>, y: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if x.nrows() != y.nrows() {
+            return Err(format!("x has {} rows but y has {}", x.nrows(), y.nrows()).into());
+        }
+        let mut features = x.clone();
+        for (name, step) in &mut self.steps {
+            features = step
+                .fit_with_target(&features, y)
+                .and_then(|_| step.transform(&features))
+                .map_err(|e| format!("Pipeline step '{}' failed : {}", name, e))?;
+        }
+        self.estimator.fit(&features, y)
+    }
+}
+
+impl<E: Predict> Predict for Pipeline<E> {
+    /// Predicts raw `x` through the fitted steps and the estimator
+    fn predict(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.estimator.predict(&self.transform(x)?)
+    }
+}
+
+impl<E: Predict> Score for Pipeline<E> {}
+
+impl<E: Serialize + DeserializeOwned> Persist for Pipeline<E> {}
@@ -0,0 +1,159 @@
+use std::error::Error;
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+use crate::decomposition::{IncrementalPca, Pca, TruncatedSvd};
+use crate::feature_selection::{CorrelationFilter, Rfe, SelectFromModel, UnivariateSelector, VarianceThreshold};
+use crate::multivariate_regression::normalization::normalization::Normalization;
+use crate::pipeline::ColumnTransformer;
+use crate::preprocessing::{
+    KBinsDiscretizer, KnnImputer, MaxAbsScaler, MinMaxScaler, Normalizer, PolynomialFeatures, PowerTransformer,
+    QuantileTransformer, RobustScaler, SimpleImputer, SplineTransformer, StandardScaler,
+};
+use crate::traits::{Fit, SupervisedTransform, Transform};
+
+/// Transformer of any supported kind, as stored by a `Pipeline` or `ColumnTransformer`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Step {
+    Scaler(Normalization), // Any scaler or the row normalizer
+    SimpleImputer(SimpleImputer),
+    KnnImputer(KnnImputer),
+    Polynomial(PolynomialFeatures),
+    Spline(SplineTransformer),
+    KBins(KBinsDiscretizer),
+    Columns(ColumnTransformer), // Different steps on different columns
+    VarianceThreshold(VarianceThreshold),
+    CorrelationFilter(CorrelationFilter),
+    Univariate(UnivariateSelector), // Needs the targets, see `fit_with_target`
+    Rfe(Rfe),                       // Needs the targets
+    FromModel(SelectFromModel),     // Needs the targets
+    Pca(Pca),
+    IncrementalPca(IncrementalPca),
+    TruncatedSvd(TruncatedSvd),
+}
+
+impl Transform for Step {
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        match self {
+            Step::Scaler(step) => step.fit(x),
+            Step::SimpleImputer(step) => step.fit(x),
+            Step::KnnImputer(step) => step.fit(x),
+            Step::Polynomial(step) => step.fit(x),
+            Step::Spline(step) => step.fit(x),
+            Step::KBins(step) => step.fit(x),
+            Step::Columns(step) => step.fit(x),
+            Step::VarianceThreshold(step) => step.fit(x),
+            Step::CorrelationFilter(step) => step.fit(x),
+            Step::Pca(step) => step.fit(x),
+            Step::IncrementalPca(step) => step.fit(x),
+            Step::TruncatedSvd(step) => step.fit(x),
+            Step::Univariate(_) | Step::Rfe(_) | Step::FromModel(_) => {
+                Err("Supervised feature selection needs the targets, fit it with `fit_with_target`".into())
+            }
+        }
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        match self {
+            Step::Scaler(step) => step.transform(x),
+            Step::SimpleImputer(step) => step.transform(x),
+            Step::KnnImputer(step) => step.transform(x),
+            Step::Polynomial(step) => step.transform(x),
+            Step::Spline(step) => step.transform(x),
+            Step::KBins(step) => step.transform(x),
+            Step::Columns(step) => step.transform(x),
+            Step::VarianceThreshold(step) => step.transform(x),
+            Step::CorrelationFilter(step) => step.transform(x),
+            Step::Univariate(step) => step.transform(x),
+            Step::Rfe(step) => step.transform(x),
+            Step::FromModel(step) => step.transform(x),
+            Step::Pca(step) => step.transform(x),
+            Step::IncrementalPca(step) => step.transform(x),
+            Step::TruncatedSvd(step) => step.transform(x),
+        }
+    }
+}
+
+impl Step {
+    /// Fits the step on `x`, passing the targets `y` to the supervised feature selectors
+    pub fn fit_with_target(&mut self, x: &Array2<f64>, y: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        match self {
+            Step::Univariate(step) => step.fit(x, y),
+            Step::Rfe(step) => step.fit(x, y),
+            Step::FromModel(step) => step.fit(x, y),
+            step => step.fit(x),
+        }
+    }
+
+    /// Output column names of the fitted step for the given input names
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        match self {
+            Step::Scaler(_) => Ok(input_names.to_vec()),
+            Step::SimpleImputer(step) => step.feature_names(input_names),
+            Step::KnnImputer(step) => step.feature_names(input_names),
+            Step::Polynomial(step) => step.feature_names(input_names),
+            Step::Spline(step) => step.feature_names(input_names),
+            Step::KBins(step) => step.feature_names(input_names),
+            Step::Columns(step) => step.feature_names(input_names),
+            Step::VarianceThreshold(step) => step.feature_names(input_names),
+            Step::CorrelationFilter(step) => step.feature_names(input_names),
+            Step::Univariate(step) => step.feature_names(input_names),
+            Step::Rfe(step) => step.feature_names(input_names),
+            Step::FromModel(step) => step.feature_names(input_names),
+            Step::Pca(step) => step.feature_names(),
+            Step::IncrementalPca(step) => step.feature_names(),
+            Step::TruncatedSvd(step) => step.feature_names(),
+        }
+    }
+}
+
+macro_rules! step_from {
+    ($($source:ty => $variant:path),* $(,)?) => {
+        $(
+            impl From<$source> for Step {
+                fn from(step: $source) -> Self {
+                    $variant(step)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! scaler_step_from {
+    ($($source:ty => $variant:path),* $(,)?) => {
+        $(
+            impl From<$source> for Step {
+                fn from(scaler: $source) -> Self {
+                    Step::Scaler($variant(scaler))
+                }
+            }
+        )*
+    };
+}
+
+step_from! {
+    Normalization => Step::Scaler,
+    SimpleImputer => Step::SimpleImputer,
+    KnnImputer => Step::KnnImputer,
+    PolynomialFeatures => Step::Polynomial,
+    SplineTransformer => Step::Spline,
+    KBinsDiscretizer => Step::KBins,
+    ColumnTransformer => Step::Columns,
+    VarianceThreshold => Step::VarianceThreshold,
+    CorrelationFilter => Step::CorrelationFilter,
+    UnivariateSelector => Step::Univariate,
+    Rfe => Step::Rfe,
+    SelectFromModel => Step::FromModel,
+    Pca => Step::Pca,
+    IncrementalPca => Step::IncrementalPca,
+    TruncatedSvd => Step::TruncatedSvd,
+}
+
+scaler_step_from! {
+    StandardScaler => Normalization::Standard,
+    MinMaxScaler => Normalization::MinMax,
+    MaxAbsScaler => Normalization::MaxAbs,
+    RobustScaler => Normalization::Robust,
+    QuantileTransformer => Normalization::Quantile,
+    PowerTransformer => Normalization::Power,
+    Normalizer => Normalization::UnitNorm,
+}
@@ -0,0 +1,195 @@
+use std::error::Error;
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::stats::quantile;
+
+/// How `KBinsDiscretizer` places bin edges
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BinStrategy {
+    Uniform,  // Equal-width bins
+    Quantile, // Bins holding roughly equal numbers of rows
+    KMeans,   // Edges halfway between 1D k-means centers
+}
+
+/// Output of `KBinsDiscretizer`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BinEncoding {
+    OneHot,  // One 0/1 column per bin
+    Ordinal, // The bin index, one column per input
+}
+
+/// Buckets every column into intervals learned on training data
+///
+/// Values outside the fitted range fall in the first or last bin. Quantile and
+/// k-means bins can end up fewer than requested when a column has few distinct values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KBinsDiscretizer {
+    n_bins: usize,
+    strategy: BinStrategy,
+    encoding: BinEncoding,
+    bin_edges: Option<Vec<Array1<f64>>>, // n_bins + 1 edges of every column
+}
+
+impl KBinsDiscretizer {
+    /// Creates an unfitted discretizer
+    ///
+    /// # Parameters:
+    /// - `n_bins: usize` - Requested number of bins per column, at least 2
+    /// - `strategy: BinStrategy` - Uniform, quantile or k-means edges
+    /// - `encoding: BinEncoding` - One-hot or ordinal output
+    pub fn new(n_bins: usize, strategy: BinStrategy, encoding: BinEncoding) -> Self {
+        Self {
+            n_bins,
+            strategy,
+            encoding,
+            bin_edges: None,
+        }
+    }
+
+    /// Learns the bin edges of every column of `x`
+    pub fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if self.n_bins < 2 {
+            return Err("Number of bins should be at least 2".into());
+        }
+        if x.nrows() == 0 {
+            return Err("KBinsDiscretizer needs at least one row to place bin edges".into());
+        }
+
+        let mut bin_edges = Vec::with_capacity(x.ncols());
+        for (col, column) in x.axis_iter(Axis(1)).enumerate() {
+            let values: Vec<f64> = column.to_vec();
+            if values.iter().any(|v| v.is_nan()) {
+                return Err(format!("Column {} has missing values", col).into());
+            }
+            let (min, max) = (quantile(&values, 0.0), quantile(&values, 1.0));
+            if min == max {
+                return Err(format!("Column {} is constant and cannot be binned", col).into());
+            }
+
+            let steps = (0..=self.n_bins).map(|i| i as f64 / self.n_bins as f64);
+            let mut edges: Vec<f64> = match self.strategy {
+                BinStrategy::Uniform => steps.map(|t| min + t * (max - min)).collect(),
+                BinStrategy::Quantile => steps.map(|t| quantile(&values, t)).collect(),
+                BinStrategy::KMeans => {
+                    let centers = kmeans_1d(&values, self.n_bins, min, max);
+                    std::iter::once(min)
+                        .chain(centers.windows(2).map(|pair| 0.5 * (pair[0] + pair[1])))
+                        .chain(std::iter::once(max))
+                        .collect()
+                }
+            };
+            if edges.iter().any(|e| !e.is_finite()) {
+                return Err(format!("Column {} has infinite values, its bin edges are not finite", col).into());
+            }
+            edges.dedup();
+            bin_edges.push(Array1::from_vec(edges));
+        }
+
+        self.bin_edges = Some(bin_edges);
+        Ok(())
+    }
+
+    /// Bins every value of `x` with the fitted edges
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let bin_edges = self.bin_edges.as_ref().ok_or("KBinsDiscretizer has not been fitted")?;
+        if x.ncols() != bin_edges.len() {
+            return Err(format!("KBinsDiscretizer was fitted on {} columns but got {}", bin_edges.len(), x.ncols()).into());
+        }
+        if x.iter().any(|v| v.is_nan()) {
+            return Err("Cannot bin missing values".into());
+        }
+
+        let bin_of = |value: f64, edges: &Array1<f64>| {
+            // Number of interior edges at or below the value
+            edges.slice(ndarray::s![1..edges.len() - 1]).iter().filter(|&&edge| edge <= value).count()
+        };
+
+        match self.encoding {
+            BinEncoding::Ordinal => {
+                let mut binned = x.clone();
+                for (mut column, edges) in binned.axis_iter_mut(Axis(1)).zip(bin_edges.iter()) {
+                    column.mapv_inplace(|value| bin_of(value, edges) as f64);
+                }
+                Ok(binned)
+            }
+            BinEncoding::OneHot => {
+                let offsets: Vec<usize> = bin_edges
+                    .iter()
+                    .scan(0, |offset, edges| {
+                        let start = *offset;
+                        *offset += edges.len() - 1;
+                        Some(start)
+                    })
+                    .collect();
+                let mut encoded = Array2::<f64>::zeros((x.nrows(), self.n_output_features()));
+                for (row, values) in x.axis_iter(Axis(0)).enumerate() {
+                    for (col, &value) in values.iter().enumerate() {
+                        encoded[(row, offsets[col] + bin_of(value, &bin_edges[col]))] = 1.0;
+                    }
+                }
+                Ok(encoded)
+            }
+        }
+    }
+
+    pub fn fit_transform(&mut self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.fit(x)?;
+        self.transform(x)
+    }
+
+    /// Names of the generated columns: the input names for ordinal output, `name_bin0`, ... for one-hot
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        let bin_edges = self.bin_edges.as_ref().ok_or("KBinsDiscretizer has not been fitted")?;
+        if input_names.len() != bin_edges.len() {
+            return Err(format!("Got {} names for {} input columns", input_names.len(), bin_edges.len()).into());
+        }
+        Ok(match self.encoding {
+            BinEncoding::Ordinal => input_names.to_vec(),
+            BinEncoding::OneHot => input_names
+                .iter()
+                .zip(bin_edges.iter())
+                .flat_map(|(name, edges)| (0..edges.len() - 1).map(move |bin| format!("{}_bin{}", name, bin)))
+                .collect(),
+        })
+    }
+
+    /// Fitted edges of every column, `None` before `fit`
+    pub fn bin_edges(&self) -> Option<&[Array1<f64>]> {
+        self.bin_edges.as_deref()
+    }
+
+    /// Number of output columns after `fit`
+    pub fn n_output_features(&self) -> usize {
+        match (&self.bin_edges, self.encoding) {
+            (None, _) => 0,
+            (Some(edges), BinEncoding::Ordinal) => edges.len(),
+            (Some(edges), BinEncoding::OneHot) => edges.iter().map(|e| e.len() - 1).sum(),
+        }
+    }
+}
+
+/// Sorted centers of 1D k-means (Lloyd's algorithm) started from uniform centers
+fn kmeans_1d(values: &[f64], k: usize, min: f64, max: f64) -> Vec<f64> {
+    let mut centers: Vec<f64> = (0..k).map(|i| min + (i as f64 + 0.5) / k as f64 * (max - min)).collect();
+    for _ in 0..100 {
+        let mut sums = vec![0.0; k];
+        let mut counts = vec![0usize; k];
+        for &value in values {
+            let nearest = (0..k)
+                .min_by(|&a, &b| (value - centers[a]).abs().total_cmp(&(value - centers[b]).abs()))
+                .unwrap_or(0);
+            sums[nearest] += value;
+            counts[nearest] += 1;
+        }
+        let updated: Vec<f64> = (0..k)
+            .map(|c| if counts[c] > 0 { sums[c] / counts[c] as f64 } else { centers[c] })
+            .collect();
+        let converged = updated.iter().zip(centers.iter()).all(|(a, b)| (a - b).abs() <= 1e-12 * (max - min));
+        centers = updated;
+        if converged {
+            break;
+        }
+    }
+    centers.sort_by(|a, b| a.total_cmp(b));
+    centers
+}
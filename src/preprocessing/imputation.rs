@@ -0,0 +1,352 @@
+use std::error::Error;
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use serde::{Deserialize, Serialize};
+
+/// Statistic used by `SimpleImputer` to fill missing values of a column
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ImputeStrategy {
+    Mean,
+    Median,
+    MostFrequent,
+    Constant(f64),
+}
+
+/// Column-wise imputer replacing missing (`NaN`) values by a statistic learned on training data
+///
+/// Fit it on the training set, then call `transform` on the training, test and
+/// prediction inputs so that all of them are filled with the same statistics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimpleImputer {
+    strategy: ImputeStrategy,
+    add_indicator: bool,
+    statistics: Option<Array1<f64>>,
+    indicator_columns: Vec<usize>,
+}
+
+impl SimpleImputer {
+    /// Creates an unfitted imputer
+    ///
+    /// # Parameters:
+    /// - `strategy: ImputeStrategy` - Statistic used to fill missing values
+    /// - `add_indicator: bool` - Append a 0/1 indicator column for every column that had missing values during `fit`
+    pub fn new(strategy: ImputeStrategy, add_indicator: bool) -> Self {
+        Self {
+            strategy,
+            add_indicator,
+            statistics: None,
+            indicator_columns: Vec::new(),
+        }
+    }
+
+    pub fn mean() -> Self {
+        Self::new(ImputeStrategy::Mean, false)
+    }
+
+    pub fn median() -> Self {
+        Self::new(ImputeStrategy::Median, false)
+    }
+
+    pub fn most_frequent() -> Self {
+        Self::new(ImputeStrategy::MostFrequent, false)
+    }
+
+    pub fn constant(value: f64) -> Self {
+        Self::new(ImputeStrategy::Constant(value), false)
+    }
+
+    /// Learns the fill value of every column from the observed (non-`NaN`) values of `x`
+    pub fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        let mut statistics = Array1::<f64>::zeros(x.ncols());
+
+        for (col, column) in x.axis_iter(Axis(1)).enumerate() {
+            let observed: Vec<f64> = column.iter().copied().filter(|v| !v.is_nan()).collect();
+            if observed.is_empty() {
+                if let ImputeStrategy::Constant(value) = self.strategy {
+                    statistics[col] = value;
+                    continue;
+                }
+                return Err(format!("Column {} has no observed values to impute from", col).into());
+            }
+            statistics[col] = match self.strategy {
+                ImputeStrategy::Mean => observed.iter().sum::<f64>() / observed.len() as f64,
+                ImputeStrategy::Median => median(observed),
+                ImputeStrategy::MostFrequent => most_frequent(observed),
+                ImputeStrategy::Constant(value) => value,
+            };
+        }
+
+        self.indicator_columns = columns_with_missing(x);
+        self.statistics = Some(statistics);
+        Ok(())
+    }
+
+    /// Replaces missing values of `x` with the fitted statistics
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let statistics = self.statistics.as_ref().ok_or("SimpleImputer has not been fitted")?;
+        if x.ncols() != statistics.len() {
+            return Err(format!(
+                "SimpleImputer was fitted on {} columns but got {}",
+                statistics.len(),
+                x.ncols()
+            )
+            .into());
+        }
+
+        let mut filled = x.clone();
+        for (mut column, &fill) in filled.axis_iter_mut(Axis(1)).zip(statistics.iter()) {
+            column.mapv_inplace(|v| if v.is_nan() { fill } else { v });
+        }
+
+        if self.add_indicator {
+            append_indicator(filled, x, &self.indicator_columns)
+        } else {
+            Ok(filled)
+        }
+    }
+
+    pub fn fit_transform(&mut self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.fit(x)?;
+        self.transform(x)
+    }
+
+    /// Fill value learned for every column, `None` before `fit`
+    pub fn statistics(&self) -> Option<&Array1<f64>> {
+        self.statistics.as_ref()
+    }
+
+    /// Columns that receive a missing-indicator column when `add_indicator` is set
+    pub fn indicator_columns(&self) -> &[usize] {
+        &self.indicator_columns
+    }
+
+    /// Output column names: the inputs, then `missing_{name}` for every indicator column
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        let statistics = self.statistics.as_ref().ok_or("SimpleImputer has not been fitted")?;
+        indicator_names(input_names, statistics.len(), self.add_indicator, &self.indicator_columns)
+    }
+}
+
+/// Imputer filling each missing value with the mean of that feature over the
+/// `n_neighbors` nearest training rows
+///
+/// Distances are NaN-aware euclidean distances computed on the coordinates
+/// observed in both rows, scaled up by the fraction of coordinates present.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnnImputer {
+    n_neighbors: usize,
+    add_indicator: bool,
+    #[serde(with = "nan_matrix")]
+    fit_data: Option<Array2<f64>>, // Keeps its `NaN`s, which JSON cannot represent as numbers
+    column_means: Option<Array1<f64>>,
+    indicator_columns: Vec<usize>,
+}
+
+impl KnnImputer {
+    /// Creates an unfitted imputer
+    ///
+    /// # Parameters:
+    /// - `n_neighbors: usize` - Number of neighbours averaged for every missing value
+    /// - `add_indicator: bool` - Append a 0/1 indicator column for every column that had missing values during `fit`
+    pub fn new(n_neighbors: usize, add_indicator: bool) -> Self {
+        Self {
+            n_neighbors: n_neighbors.max(1),
+            add_indicator,
+            fit_data: None,
+            column_means: None,
+            indicator_columns: Vec::new(),
+        }
+    }
+
+    /// Stores the training rows used as neighbours
+    pub fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        let mut column_means = Array1::<f64>::zeros(x.ncols());
+        for (col, column) in x.axis_iter(Axis(1)).enumerate() {
+            let observed: Vec<f64> = column.iter().copied().filter(|v| !v.is_nan()).collect();
+            if observed.is_empty() {
+                return Err(format!("Column {} has no observed values to impute from", col).into());
+            }
+            column_means[col] = observed.iter().sum::<f64>() / observed.len() as f64;
+        }
+
+        self.indicator_columns = columns_with_missing(x);
+        self.column_means = Some(column_means);
+        self.fit_data = Some(x.clone());
+        Ok(())
+    }
+
+    /// Replaces missing values of `x` using the nearest training rows
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let fit_data = self.fit_data.as_ref().ok_or("KnnImputer has not been fitted")?;
+        let column_means = self.column_means.as_ref().ok_or("KnnImputer has not been fitted")?;
+        if x.ncols() != fit_data.ncols() {
+            return Err(format!(
+                "KnnImputer was fitted on {} columns but got {}",
+                fit_data.ncols(),
+                x.ncols()
+            )
+            .into());
+        }
+
+        let mut filled = x.clone();
+        for (row_idx, row) in x.axis_iter(Axis(0)).enumerate() {
+            let missing: Vec<usize> = (0..row.len()).filter(|&c| row[c].is_nan()).collect();
+            if missing.is_empty() {
+                continue;
+            }
+
+            let distances: Vec<f64> = fit_data
+                .axis_iter(Axis(0))
+                .map(|other| nan_euclidean_distance(row, other))
+                .collect();
+
+            for col in missing {
+                let mut candidates: Vec<(f64, f64)> = fit_data
+                    .column(col)
+                    .iter()
+                    .zip(distances.iter())
+                    .filter(|(v, d)| !v.is_nan() && d.is_finite())
+                    .map(|(&v, &d)| (d, v))
+                    .collect();
+                candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+                candidates.truncate(self.n_neighbors);
+
+                filled[(row_idx, col)] = if candidates.is_empty() {
+                    column_means[col]
+                } else {
+                    candidates.iter().map(|(_, v)| v).sum::<f64>() / candidates.len() as f64
+                };
+            }
+        }
+
+        if self.add_indicator {
+            append_indicator(filled, x, &self.indicator_columns)
+        } else {
+            Ok(filled)
+        }
+    }
+
+    pub fn fit_transform(&mut self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.fit(x)?;
+        self.transform(x)
+    }
+
+    /// Columns that receive a missing-indicator column when `add_indicator` is set
+    pub fn indicator_columns(&self) -> &[usize] {
+        &self.indicator_columns
+    }
+
+    /// Output column names: the inputs, then `missing_{name}` for every indicator column
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        let fit_data = self.fit_data.as_ref().ok_or("KnnImputer has not been fitted")?;
+        indicator_names(input_names, fit_data.ncols(), self.add_indicator, &self.indicator_columns)
+    }
+}
+
+// (De)serializes a matrix with missing values, writing `NaN` as `null`
+mod nan_matrix {
+    use ndarray::Array2;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Stored {
+        shape: (usize, usize),
+        data: Vec<Option<f64>>,
+    }
+
+    pub fn serialize<S: Serializer>(x: &Option<Array2<f64>>, serializer: S) -> Result<S::Ok, S::Error> {
+        x.as_ref()
+            .map(|x| Stored {
+                shape: x.dim(),
+                data: x.iter().map(|&v| if v.is_nan() { None } else { Some(v) }).collect(),
+            })
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Array2<f64>>, D::Error> {
+        Option::<Stored>::deserialize(deserializer)?
+            .map(|stored| {
+                let data = stored.data.into_iter().map(|v| v.unwrap_or(f64::NAN)).collect();
+                Array2::from_shape_vec(stored.shape, data).map_err(serde::de::Error::custom)
+            })
+            .transpose()
+    }
+}
+
+fn nan_euclidean_distance(a: ArrayView1<f64>, b: ArrayView1<f64>) -> f64 {
+    let mut sum = 0.0;
+    let mut present = 0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        if !x.is_nan() && !y.is_nan() {
+            sum += (x - y).powi(2);
+            present += 1;
+        }
+    }
+    if present == 0 {
+        return f64::INFINITY;
+    }
+    (sum * a.len() as f64 / present as f64).sqrt()
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+// Ties resolve to the smallest value
+fn most_frequent(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let (mut best, mut best_count) = (values[0], 0);
+    let mut i = 0;
+    while i < values.len() {
+        let mut j = i;
+        while j < values.len() && values[j] == values[i] {
+            j += 1;
+        }
+        if j - i > best_count {
+            best = values[i];
+            best_count = j - i;
+        }
+        i = j;
+    }
+    best
+}
+
+fn columns_with_missing(x: &Array2<f64>) -> Vec<usize> {
+    x.axis_iter(Axis(1))
+        .enumerate()
+        .filter(|(_, column)| column.iter().any(|v| v.is_nan()))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn indicator_names(
+    input_names: &[String],
+    n_features: usize,
+    add_indicator: bool,
+    columns: &[usize],
+) -> Result<Vec<String>, Box<dyn Error>> {
+    if input_names.len() != n_features {
+        return Err(format!("Got {} names for {} input columns", input_names.len(), n_features).into());
+    }
+    let mut names = input_names.to_vec();
+    if add_indicator {
+        names.extend(columns.iter().map(|&c| format!("missing_{}", input_names[c])));
+    }
+    Ok(names)
+}
+
+fn append_indicator(
+    filled: Array2<f64>,
+    original: &Array2<f64>,
+    columns: &[usize],
+) -> Result<Array2<f64>, Box<dyn Error>> {
+    let indicator = Array2::from_shape_fn((original.nrows(), columns.len()), |(row, i)| {
+        if original[(row, columns[i])].is_nan() { 1.0 } else { 0.0 }
+    });
+    Ok(ndarray::concatenate(Axis(1), &[filled.view(), indicator.view()])?)
+}
@@ -0,0 +1,34 @@
+mod imputation;
+mod polynomial;
+mod spline;
+mod discretization;
+mod scaling;
+
+pub use imputation::*;
+pub use polynomial::*;
+pub use spline::*;
+pub use discretization::*;
+pub use scaling::*;
+
+use std::error::Error;
+use ndarray::Array2;
+use crate::traits::Transform;
+
+// Exposes the inherent `fit` and `transform` of the transformers through `Transform`
+macro_rules! impl_transform {
+    ($($name:ty),* $(,)?) => {
+        $(
+            impl Transform for $name {
+                fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+                    <$name>::fit(self, x)
+                }
+
+                fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+                    <$name>::transform(self, x)
+                }
+            }
+        )*
+    };
+}
+
+impl_transform!(SimpleImputer, KnnImputer, PolynomialFeatures, SplineTransformer, KBinsDiscretizer);
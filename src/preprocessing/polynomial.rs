@@ -0,0 +1,145 @@
+use std::error::Error;
+use ndarray::{s, Array2, Axis};
+use serde::{Deserialize, Serialize};
+
+/// Expands input columns into all polynomial terms up to a given degree
+///
+/// Terms are ordered by degree, then lexicographically by column index:
+/// `1, a, b, a^2, a b, b^2, ...` for inputs `a`, `b`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolynomialFeatures {
+    degree: usize,
+    interaction_only: bool, // Only products of distinct columns (no `a^2`)
+    include_bias: bool,     // Prepend a column of ones
+    n_input_features: Option<usize>,
+    terms: Vec<Vec<usize>>, // Input column of every factor, one entry per generated non-bias column
+}
+
+impl PolynomialFeatures {
+    /// Creates an unfitted expansion
+    ///
+    /// # Parameters:
+    /// - `degree: usize` - Maximum total degree of a term, at least 1
+    /// - `interaction_only: bool` - Skip terms containing a column more than once
+    /// - `include_bias: bool` - Prepend a column of ones
+    pub fn new(degree: usize, interaction_only: bool, include_bias: bool) -> Self {
+        Self {
+            degree,
+            interaction_only,
+            include_bias,
+            n_input_features: None,
+            terms: Vec::new(),
+        }
+    }
+
+    /// Degree-1 expansion that only prepends the bias column
+    pub fn bias() -> Self {
+        Self::new(1, false, true)
+    }
+
+    /// Prepends a column of ones to `x`
+    pub fn prepend_bias(x: &Array2<f64>) -> Array2<f64> {
+        let mut biased = Array2::<f64>::ones((x.nrows(), x.ncols() + 1));
+        biased.slice_mut(s![.., 1..]).assign(x);
+        biased
+    }
+
+    /// Enumerates the terms for the number of columns of `x`
+    pub fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if self.degree == 0 {
+            return Err("Polynomial degree should be at least 1".into());
+        }
+
+        let n_features = x.ncols();
+        let mut terms: Vec<Vec<usize>> = Vec::new();
+        let mut previous: Vec<Vec<usize>> = vec![Vec::new()];
+        for _ in 0..self.degree {
+            // Extend every term of the previous degree with a column index not below its last one
+            let current: Vec<Vec<usize>> = previous
+                .iter()
+                .flat_map(|term| {
+                    let start = match term.last() {
+                        Some(&last) if self.interaction_only => last + 1,
+                        Some(&last) => last,
+                        None => 0,
+                    };
+                    (start..n_features).map(move |col| {
+                        let mut next = term.clone();
+                        next.push(col);
+                        next
+                    })
+                })
+                .collect();
+            terms.extend(current.iter().cloned());
+            previous = current;
+        }
+
+        self.terms = terms;
+        self.n_input_features = Some(n_features);
+        Ok(())
+    }
+
+    /// Computes the fitted terms for every row of `x`
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let n_features = self.n_input_features.ok_or("PolynomialFeatures has not been fitted")?;
+        if x.ncols() != n_features {
+            return Err(format!("PolynomialFeatures was fitted on {} columns but got {}", n_features, x.ncols()).into());
+        }
+
+        let mut expanded = Array2::<f64>::ones((x.nrows(), self.terms.len()));
+        for (mut column, term) in expanded.axis_iter_mut(Axis(1)).zip(self.terms.iter()) {
+            for &col in term {
+                column *= &x.column(col);
+            }
+        }
+
+        if self.include_bias {
+            Ok(Self::prepend_bias(&expanded))
+        } else {
+            Ok(expanded)
+        }
+    }
+
+    pub fn fit_transform(&mut self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.fit(x)?;
+        self.transform(x)
+    }
+
+    /// Names of the generated columns, e.g. `1`, `age`, `age^2`, `age income`
+    ///
+    /// # Parameters:
+    /// - `input_names: &[String]` - Name of every input column, such as the CSV headers
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        let n_features = self.n_input_features.ok_or("PolynomialFeatures has not been fitted")?;
+        if input_names.len() != n_features {
+            return Err(format!("Got {} names for {} input columns", input_names.len(), n_features).into());
+        }
+
+        let mut names = Vec::with_capacity(self.n_output_features());
+        if self.include_bias {
+            names.push("1".to_string());
+        }
+        for term in &self.terms {
+            // Terms are sorted, so equal columns are adjacent
+            let mut factors: Vec<String> = Vec::new();
+            let mut i = 0;
+            while i < term.len() {
+                let power = term[i..].iter().take_while(|&&col| col == term[i]).count();
+                let name = &input_names[term[i]];
+                factors.push(if power == 1 { name.clone() } else { format!("{}^{}", name, power) });
+                i += power;
+            }
+            names.push(factors.join(" "));
+        }
+        Ok(names)
+    }
+
+    /// Input column of every factor of every generated non-bias column
+    pub fn terms(&self) -> &[Vec<usize>] {
+        &self.terms
+    }
+
+    pub fn n_output_features(&self) -> usize {
+        self.terms.len() + usize::from(self.include_bias)
+    }
+}
@@ -0,0 +1,447 @@
+use std::error::Error;
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::matrix_operations::CsrMatrix;
+use crate::stats::{normal_cdf, normal_ppf, quantile};
+use crate::traits::{InverseTransform, Transform};
+
+/// Invertible column transformation learned on training data and reapplied to new data
+pub trait Scaler: Transform + InverseTransform {}
+
+impl<T: Transform + InverseTransform> Scaler for T {}
+
+fn check_columns(name: &str, fitted: usize, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+    if x.ncols() != fitted {
+        return Err(format!("{} was fitted on {} columns but got {}", name, fitted, x.ncols()).into());
+    }
+    Ok(())
+}
+
+/// Applies `f(value, column)` to every entry of `x`
+fn map_columns(x: &Array2<f64>, f: impl Fn(f64, usize) -> f64) -> Array2<f64> {
+    let mut mapped = x.clone();
+    for (col, mut column) in mapped.axis_iter_mut(Axis(1)).enumerate() {
+        column.mapv_inplace(|v| f(v, col));
+    }
+    mapped
+}
+
+/// Fitted `(x - shift) / scale` per column; constant columns (scale 0) are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Affine {
+    shift: Array1<f64>,
+    scale: Array1<f64>,
+}
+
+impl Affine {
+    fn new(shift: Array1<f64>, scale: Array1<f64>) -> Self {
+        // Constant columns keep their values, as `normalize_data` always did
+        let keep: Vec<bool> = scale.iter().map(|&s| s == 0.0).collect();
+        let shift = Array1::from_iter(shift.iter().zip(keep.iter()).map(|(&s, &k)| if k { 0.0 } else { s }));
+        let scale = scale.mapv(|s| if s == 0.0 { 1.0 } else { s });
+        Self { shift, scale }
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Array2<f64> {
+        map_columns(x, |v, col| (v - self.shift[col]) / self.scale[col])
+    }
+
+    fn inverse_transform(&self, x: &Array2<f64>) -> Array2<f64> {
+        map_columns(x, |v, col| v * self.scale[col] + self.shift[col])
+    }
+}
+
+fn column_stat(x: &Array2<f64>, f: impl Fn(&[f64]) -> f64) -> Array1<f64> {
+    x.axis_iter(Axis(1)).map(|column| f(&column.to_vec())).collect()
+}
+
+macro_rules! affine_scaler {
+    ($name:ident, $doc:literal, $fit:expr) => {
+        #[doc = $doc]
+        #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+        pub struct $name {
+            params: Option<Affine>,
+        }
+
+        impl $name {
+            pub fn new() -> Self {
+                Self { params: None }
+            }
+
+            /// Fitted `(shift, scale)` with `transform(x) = (x - shift) / scale`, `None` before `fit`
+            pub fn shift_scale(&self) -> Option<(&Array1<f64>, &Array1<f64>)> {
+                self.params.as_ref().map(|p| (&p.shift, &p.scale))
+            }
+        }
+
+        impl Transform for $name {
+            fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+                if x.nrows() == 0 {
+                    return Err(format!("{} needs at least one row", stringify!($name)).into());
+                }
+                let (shift, scale) = $fit(x);
+                self.params = Some(Affine::new(shift, scale));
+                Ok(())
+            }
+
+            fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+                let params = self.params.as_ref().ok_or(concat!(stringify!($name), " has not been fitted"))?;
+                check_columns(stringify!($name), params.shift.len(), x)?;
+                Ok(params.transform(x))
+            }
+        }
+
+        impl InverseTransform for $name {
+            fn inverse_transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+                let params = self.params.as_ref().ok_or(concat!(stringify!($name), " has not been fitted"))?;
+                check_columns(stringify!($name), params.shift.len(), x)?;
+                Ok(params.inverse_transform(x))
+            }
+        }
+    };
+}
+
+affine_scaler!(StandardScaler, "Centers every column on its mean and divides by its (population) standard deviation", |x: &Array2<f64>| {
+    (column_stat(x, |c| c.iter().sum::<f64>() / c.len() as f64), x.std_axis(Axis(0), 0.0))
+});
+
+affine_scaler!(MinMaxScaler, "Maps every column linearly onto [0, 1]", |x: &Array2<f64>| {
+    let min = column_stat(x, |c| c.iter().fold(f64::INFINITY, |a, &b| a.min(b)));
+    let max = column_stat(x, |c| c.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)));
+    let range = &max - &min;
+    (min, range)
+});
+
+affine_scaler!(MaxAbsScaler, "Divides every column by its maximum absolute value; keeps zeros (and sparsity) intact", |x: &Array2<f64>| {
+    (Array1::zeros(x.ncols()), column_stat(x, |c| c.iter().fold(0.0, |a: f64, &b| a.max(b.abs()))))
+});
+
+affine_scaler!(RobustScaler, "Centers every column on its median and divides by its interquartile range", |x: &Array2<f64>| {
+    let median = column_stat(x, |c| quantile(c, 0.5));
+    let iqr = column_stat(x, |c| quantile(c, 0.75) - quantile(c, 0.25));
+    (median, iqr)
+});
+
+impl MaxAbsScaler {
+    /// Fits on a CSR matrix without densifying it; implicit zeros never raise a maximum
+    pub fn fit_sparse(&mut self, x: &CsrMatrix) -> Result<(), Box<dyn Error>> {
+        if x.nrows() == 0 {
+            return Err("MaxAbsScaler needs at least one row".into());
+        }
+        let mut max_abs = Array1::<f64>::zeros(x.ncols());
+        for (&col, &value) in x.indices().iter().zip(x.data()) {
+            max_abs[col] = max_abs[col].max(value.abs());
+        }
+        self.params = Some(Affine::new(Array1::zeros(x.ncols()), max_abs));
+        Ok(())
+    }
+}
+
+/// Target distribution of `QuantileTransformer`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum QuantileOutput {
+    Uniform, // Values in [0, 1]
+    Normal,  // Standard normal scores
+}
+
+/// Maps every column through its empirical CDF, then optionally to normal scores
+///
+/// Values outside the training range are clipped to its bounds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileTransformer {
+    n_quantiles: usize,
+    output: QuantileOutput,
+    references: Option<Vec<Array1<f64>>>, // Column values at evenly spaced quantile levels
+}
+
+impl QuantileTransformer {
+    /// # Parameters:
+    /// - `n_quantiles: usize` - Number of landmarks of the empirical CDF, capped at the number of rows
+    /// - `output: QuantileOutput` - Uniform or normal output
+    pub fn new(n_quantiles: usize, output: QuantileOutput) -> Self {
+        Self { n_quantiles, output, references: None }
+    }
+
+    fn levels(n: usize) -> Array1<f64> {
+        Array1::linspace(0.0, 1.0, n)
+    }
+
+    /// Levels of the fitted references; none when fitted on zero columns
+    fn fitted_levels(references: &[Array1<f64>]) -> Array1<f64> {
+        Self::levels(references.first().map_or(0, Array1::len))
+    }
+}
+
+/// Piecewise-linear interpolation of `x` on increasing `xp`, constant outside; ties take the last match
+fn interp(x: f64, xp: &Array1<f64>, fp: &Array1<f64>) -> f64 {
+    let upper = xp.iter().take_while(|&&v| v <= x).count();
+    if upper == 0 {
+        return fp[0];
+    }
+    if upper == xp.len() {
+        return fp[xp.len() - 1];
+    }
+    let (x0, x1) = (xp[upper - 1], xp[upper]);
+    fp[upper - 1] + (x - x0) / (x1 - x0) * (fp[upper] - fp[upper - 1])
+}
+
+/// Bound keeping normal scores finite at the CDF extremes
+const QUANTILE_CLIP: f64 = 1e-7;
+
+impl Transform for QuantileTransformer {
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if self.n_quantiles < 2 {
+            return Err("QuantileTransformer needs at least 2 quantiles".into());
+        }
+        if x.nrows() < 2 {
+            return Err("QuantileTransformer needs at least 2 rows".into());
+        }
+        let levels = Self::levels(self.n_quantiles.min(x.nrows()));
+        self.references = Some(
+            x.axis_iter(Axis(1))
+                .map(|column| {
+                    let column = column.to_vec();
+                    levels.mapv(|q| quantile(&column, q))
+                })
+                .collect(),
+        );
+        Ok(())
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let references = self.references.as_ref().ok_or("QuantileTransformer has not been fitted")?;
+        check_columns("QuantileTransformer", references.len(), x)?;
+        let levels = Self::fitted_levels(references);
+        let reversed_levels = levels.slice(ndarray::s![..;-1]).mapv(|v| -v);
+        let reversed_refs: Vec<Array1<f64>> = references.iter().map(|r| r.slice(ndarray::s![..;-1]).mapv(|v| -v)).collect();
+
+        Ok(map_columns(x, |v, col| {
+            // Average of interpolating from both ends, so repeated reference values map to their middle level
+            let forward = interp(v, &references[col], &levels);
+            let backward = -interp(-v, &reversed_refs[col], &reversed_levels);
+            let p = 0.5 * (forward + backward);
+            match self.output {
+                QuantileOutput::Uniform => p,
+                QuantileOutput::Normal => normal_ppf(p.clamp(QUANTILE_CLIP, 1.0 - QUANTILE_CLIP)),
+            }
+        }))
+    }
+}
+
+impl InverseTransform for QuantileTransformer {
+    fn inverse_transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let references = self.references.as_ref().ok_or("QuantileTransformer has not been fitted")?;
+        check_columns("QuantileTransformer", references.len(), x)?;
+        let levels = Self::fitted_levels(references);
+        Ok(map_columns(x, |v, col| {
+            let p = match self.output {
+                QuantileOutput::Uniform => v,
+                QuantileOutput::Normal => normal_cdf(v),
+            };
+            interp(p, &levels, &references[col])
+        }))
+    }
+}
+
+/// Family of `PowerTransformer`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PowerMethod {
+    BoxCox,     // Strictly positive data only
+    YeoJohnson, // Any real data
+}
+
+/// Per-column power transform making data more Gaussian, with lambda chosen by maximum likelihood
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerTransformer {
+    method: PowerMethod,
+    standardize: bool, // Standardize the transformed columns to zero mean, unit variance
+    lambdas: Option<Array1<f64>>,
+    standard_scaler: Option<StandardScaler>,
+}
+
+impl PowerTransformer {
+    pub fn new(method: PowerMethod, standardize: bool) -> Self {
+        Self { method, standardize, lambdas: None, standard_scaler: None }
+    }
+
+    pub fn box_cox() -> Self {
+        Self::new(PowerMethod::BoxCox, true)
+    }
+
+    pub fn yeo_johnson() -> Self {
+        Self::new(PowerMethod::YeoJohnson, true)
+    }
+
+    /// Fitted lambda of every column, `None` before `fit`
+    pub fn lambdas(&self) -> Option<&Array1<f64>> {
+        self.lambdas.as_ref()
+    }
+
+    fn forward(method: PowerMethod, x: f64, lambda: f64) -> f64 {
+        match method {
+            PowerMethod::BoxCox if lambda.abs() < 1e-12 => x.ln(),
+            PowerMethod::BoxCox => (x.powf(lambda) - 1.0) / lambda,
+            PowerMethod::YeoJohnson if x >= 0.0 => {
+                if lambda.abs() < 1e-12 { x.ln_1p() } else { ((x + 1.0).powf(lambda) - 1.0) / lambda }
+            }
+            PowerMethod::YeoJohnson => {
+                if (lambda - 2.0).abs() < 1e-12 { -(-x).ln_1p() } else { -((1.0 - x).powf(2.0 - lambda) - 1.0) / (2.0 - lambda) }
+            }
+        }
+    }
+
+    fn inverse(method: PowerMethod, y: f64, lambda: f64) -> f64 {
+        match method {
+            PowerMethod::BoxCox if lambda.abs() < 1e-12 => y.exp(),
+            PowerMethod::BoxCox => (lambda * y + 1.0).powf(1.0 / lambda),
+            PowerMethod::YeoJohnson if y >= 0.0 => {
+                if lambda.abs() < 1e-12 { y.exp_m1() } else { (lambda * y + 1.0).powf(1.0 / lambda) - 1.0 }
+            }
+            PowerMethod::YeoJohnson => {
+                if (lambda - 2.0).abs() < 1e-12 { -(-y).exp_m1() } else { 1.0 - (1.0 - (2.0 - lambda) * y).powf(1.0 / (2.0 - lambda)) }
+            }
+        }
+    }
+
+    /// Profile log-likelihood of `lambda` under a Gaussian model of the transformed column
+    fn log_likelihood(method: PowerMethod, column: &[f64], lambda: f64) -> f64 {
+        let n = column.len() as f64;
+        let transformed: Vec<f64> = column.iter().map(|&x| Self::forward(method, x, lambda)).collect();
+        let mean = transformed.iter().sum::<f64>() / n;
+        let variance = transformed.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / n;
+        let jacobian: f64 = match method {
+            PowerMethod::BoxCox => column.iter().map(|x| x.ln()).sum(),
+            PowerMethod::YeoJohnson => column.iter().map(|x| x.signum() * x.abs().ln_1p()).sum(),
+        };
+        -n / 2.0 * variance.ln() + (lambda - 1.0) * jacobian
+    }
+
+    /// Golden-section search for the lambda maximizing the log-likelihood on [-5, 5]
+    fn optimal_lambda(method: PowerMethod, column: &[f64]) -> f64 {
+        let ratio = (5f64.sqrt() - 1.0) / 2.0;
+        let (mut low, mut high) = (-5.0, 5.0);
+        let objective = |lambda: f64| {
+            let value = Self::log_likelihood(method, column, lambda);
+            if value.is_nan() { f64::NEG_INFINITY } else { value }
+        };
+        let mut a = high - ratio * (high - low);
+        let mut b = low + ratio * (high - low);
+        let (mut fa, mut fb) = (objective(a), objective(b));
+        while high - low > 1e-8 {
+            if fa < fb {
+                low = a;
+                a = b;
+                fa = fb;
+                b = low + ratio * (high - low);
+                fb = objective(b);
+            } else {
+                high = b;
+                b = a;
+                fb = fa;
+                a = high - ratio * (high - low);
+                fa = objective(a);
+            }
+        }
+        0.5 * (low + high)
+    }
+}
+
+impl Transform for PowerTransformer {
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        if x.nrows() < 2 {
+            return Err("PowerTransformer needs at least 2 rows".into());
+        }
+        if self.method == PowerMethod::BoxCox && x.iter().any(|&v| v <= 0.0) {
+            return Err("Box-Cox needs strictly positive data".into());
+        }
+        let lambdas: Array1<f64> = x
+            .axis_iter(Axis(1))
+            .map(|column| Self::optimal_lambda(self.method, &column.to_vec()))
+            .collect();
+        let transformed = map_columns(x, |v, col| Self::forward(self.method, v, lambdas[col]));
+
+        self.standard_scaler = if self.standardize {
+            let mut scaler = StandardScaler::new();
+            scaler.fit(&transformed)?;
+            Some(scaler)
+        } else {
+            None
+        };
+        self.lambdas = Some(lambdas);
+        Ok(())
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let lambdas = self.lambdas.as_ref().ok_or("PowerTransformer has not been fitted")?;
+        check_columns("PowerTransformer", lambdas.len(), x)?;
+        if self.method == PowerMethod::BoxCox && x.iter().any(|&v| v <= 0.0) {
+            return Err("Box-Cox needs strictly positive data".into());
+        }
+        let transformed = map_columns(x, |v, col| Self::forward(self.method, v, lambdas[col]));
+        match &self.standard_scaler {
+            Some(scaler) => scaler.transform(&transformed),
+            None => Ok(transformed),
+        }
+    }
+}
+
+impl InverseTransform for PowerTransformer {
+    fn inverse_transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let lambdas = self.lambdas.as_ref().ok_or("PowerTransformer has not been fitted")?;
+        check_columns("PowerTransformer", lambdas.len(), x)?;
+        let unstandardized = match &self.standard_scaler {
+            Some(scaler) => scaler.inverse_transform(x)?,
+            None => x.clone(),
+        };
+        Ok(map_columns(&unstandardized, |v, col| Self::inverse(self.method, v, lambdas[col])))
+    }
+}
+
+/// Row norm used by `Normalizer`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Norm {
+    L1,
+    L2,
+    Max,
+}
+
+/// Scales every row to unit norm; stateless, so it cannot be inverted
+///
+/// All-zero rows are left unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Normalizer {
+    norm: Norm,
+}
+
+impl Normalizer {
+    pub fn new(norm: Norm) -> Self {
+        Self { norm }
+    }
+}
+
+impl Transform for Normalizer {
+    fn fit(&mut self, _x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let mut normalized = x.clone();
+        for mut row in normalized.axis_iter_mut(Axis(0)) {
+            let norm = match self.norm {
+                Norm::L1 => row.iter().map(|v| v.abs()).sum::<f64>(),
+                Norm::L2 => row.dot(&row).sqrt(),
+                Norm::Max => row.iter().fold(0.0, |a: f64, &b| a.max(b.abs())),
+            };
+            if norm != 0.0 {
+                row /= norm;
+            }
+        }
+        Ok(normalized)
+    }
+}
+
+impl InverseTransform for Normalizer {
+    fn inverse_transform(&self, _x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        Err("Row normalization cannot be inverted".into())
+    }
+}
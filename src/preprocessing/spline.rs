@@ -0,0 +1,192 @@
+use std::error::Error;
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use crate::stats::quantile;
+
+/// Placement of spline knots within the range of each column
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum KnotStrategy {
+    Uniform,  // Evenly spaced between the column minimum and maximum
+    Quantile, // At evenly spaced quantiles of the column
+}
+
+/// Family of the spline basis
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SplineBasis {
+    BSpline(usize), // B-splines of the given degree; inputs are clamped to the fitted range
+    NaturalCubic,   // Cubic, linear beyond the boundary knots
+}
+
+/// Expands every input column into a spline basis
+///
+/// A B-spline basis of degree `d` with `n` knots has `n + d - 1` columns per input
+/// and sums to one at every point; a natural cubic basis has `n - 1` columns, the
+/// first of which is the input itself. Neither includes a constant column.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplineTransformer {
+    n_knots: usize,
+    basis: SplineBasis,
+    knot_strategy: KnotStrategy,
+    knots: Option<Vec<Array1<f64>>>, // Knots of every column, boundary knots included
+}
+
+impl SplineTransformer {
+    /// Creates an unfitted transformer
+    ///
+    /// # Parameters:
+    /// - `n_knots: usize` - Number of knots per column, boundary knots included (at least 2, 3 for natural cubic)
+    /// - `basis: SplineBasis` - B-spline of some degree or natural cubic spline
+    /// - `knot_strategy: KnotStrategy` - Uniform or quantile knot placement
+    pub fn new(n_knots: usize, basis: SplineBasis, knot_strategy: KnotStrategy) -> Self {
+        Self {
+            n_knots,
+            basis,
+            knot_strategy,
+            knots: None,
+        }
+    }
+
+    /// Cubic B-splines with uniform knots
+    pub fn cubic(n_knots: usize) -> Self {
+        Self::new(n_knots, SplineBasis::BSpline(3), KnotStrategy::Uniform)
+    }
+
+    pub fn natural_cubic(n_knots: usize) -> Self {
+        Self::new(n_knots, SplineBasis::NaturalCubic, KnotStrategy::Uniform)
+    }
+
+    /// Places the knots of every column of `x`
+    pub fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>> {
+        let min_knots = if self.basis == SplineBasis::NaturalCubic { 3 } else { 2 };
+        if self.n_knots < min_knots {
+            return Err(format!("{:?} splines need at least {} knots", self.basis, min_knots).into());
+        }
+        if x.nrows() == 0 {
+            return Err("SplineTransformer needs at least one row to place knots".into());
+        }
+
+        let mut knots = Vec::with_capacity(x.ncols());
+        for (col, column) in x.axis_iter(Axis(1)).enumerate() {
+            let values: Vec<f64> = column.to_vec();
+            if values.iter().any(|v| v.is_nan()) {
+                return Err(format!("Column {} has missing values", col).into());
+            }
+            let steps = (0..self.n_knots).map(|i| i as f64 / (self.n_knots - 1) as f64);
+            let mut column_knots: Vec<f64> = match self.knot_strategy {
+                KnotStrategy::Uniform => {
+                    let (min, max) = (quantile(&values, 0.0), quantile(&values, 1.0));
+                    steps.map(|t| min + t * (max - min)).collect()
+                }
+                KnotStrategy::Quantile => steps.map(|t| quantile(&values, t)).collect(),
+            };
+            if column_knots.iter().any(|k| !k.is_finite()) {
+                return Err(format!("Column {} has infinite values, its knots are not finite", col).into());
+            }
+            column_knots.dedup();
+            if column_knots.len() != self.n_knots {
+                return Err(format!("Column {} has too few distinct values for {} knots", col, self.n_knots).into());
+            }
+            knots.push(Array1::from_vec(column_knots));
+        }
+
+        self.knots = Some(knots);
+        Ok(())
+    }
+
+    /// Evaluates the basis of every column of `x`, concatenated column by column
+    pub fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        let knots = self.knots.as_ref().ok_or("SplineTransformer has not been fitted")?;
+        if x.ncols() != knots.len() {
+            return Err(format!("SplineTransformer was fitted on {} columns but got {}", knots.len(), x.ncols()).into());
+        }
+
+        let width = self.n_basis_per_feature();
+        let mut expanded = Array2::<f64>::zeros((x.nrows(), width * x.ncols()));
+        for (col, column_knots) in knots.iter().enumerate() {
+            for (row, &value) in x.column(col).iter().enumerate() {
+                let basis = match self.basis {
+                    SplineBasis::BSpline(degree) => bspline_basis(value, column_knots, degree),
+                    SplineBasis::NaturalCubic => natural_cubic_basis(value, column_knots),
+                };
+                for (i, b) in basis.into_iter().enumerate() {
+                    expanded[(row, col * width + i)] = b;
+                }
+            }
+        }
+        Ok(expanded)
+    }
+
+    pub fn fit_transform(&mut self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.fit(x)?;
+        self.transform(x)
+    }
+
+    /// Names of the generated columns, `name_sp0`, `name_sp1`, ...
+    pub fn feature_names(&self, input_names: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+        let knots = self.knots.as_ref().ok_or("SplineTransformer has not been fitted")?;
+        if input_names.len() != knots.len() {
+            return Err(format!("Got {} names for {} input columns", input_names.len(), knots.len()).into());
+        }
+        Ok(input_names
+            .iter()
+            .flat_map(|name| (0..self.n_basis_per_feature()).map(move |i| format!("{}_sp{}", name, i)))
+            .collect())
+    }
+
+    /// Fitted knots of every column, `None` before `fit`
+    pub fn knots(&self) -> Option<&[Array1<f64>]> {
+        self.knots.as_deref()
+    }
+
+    pub fn n_basis_per_feature(&self) -> usize {
+        match self.basis {
+            SplineBasis::BSpline(degree) => self.n_knots + degree - 1,
+            SplineBasis::NaturalCubic => self.n_knots - 1,
+        }
+    }
+}
+
+/// B-spline basis at `x` (clamped to the boundary knots) via the Cox-de Boor recursion
+///
+/// The knot vector is extended by `degree` uniformly spaced knots on both sides.
+fn bspline_basis(x: f64, knots: &Array1<f64>, degree: usize) -> Vec<f64> {
+    let n = knots.len();
+    let (first, last) = (knots[0], knots[n - 1]);
+    let x = x.clamp(first, last);
+    let (step_low, step_high) = (knots[1] - first, last - knots[n - 2]);
+    let extended: Vec<f64> = (0..degree)
+        .rev()
+        .map(|i| first - (i + 1) as f64 * step_low)
+        .chain(knots.iter().copied())
+        .chain((0..degree).map(|i| last + (i + 1) as f64 * step_high))
+        .collect();
+
+    // Degree 0: indicator of the knot span containing x; without extension knots the last span is closed
+    let spans = extended.len() - 1;
+    let mut basis: Vec<f64> = (0..spans)
+        .map(|i| {
+            let inside = extended[i] <= x && x < extended[i + 1];
+            let closed_end = degree == 0 && i == spans - 1 && x == last;
+            if inside || closed_end { 1.0 } else { 0.0 }
+        })
+        .collect();
+    for d in 1..=degree {
+        basis = (0..spans - d)
+            .map(|i| {
+                let left = (x - extended[i]) / (extended[i + d] - extended[i]) * basis[i];
+                let right = (extended[i + d + 1] - x) / (extended[i + d + 1] - extended[i + 1]) * basis[i + 1];
+                left + right
+            })
+            .collect();
+    }
+    basis
+}
+
+/// Natural cubic spline basis (truncated power form): `x`, then `d_k(x) - d_{K-1}(x)`
+fn natural_cubic_basis(x: f64, knots: &Array1<f64>) -> Vec<f64> {
+    let k = knots.len();
+    let last = knots[k - 1];
+    let d = |j: usize| ((x - knots[j]).max(0.0).powi(3) - (x - last).max(0.0).powi(3)) / (last - knots[j]);
+    let d_penultimate = d(k - 2);
+    std::iter::once(x).chain((0..k - 2).map(|j| d(j) - d_penultimate)).collect()
+}
@@ -0,0 +1,35 @@
+/// Quantile `q` in [0, 1] of `values`, linearly interpolated between order statistics
+///
+/// `NaN` values are ignored; returns `NaN` when no values remain.
+pub fn quantile(values: &[f64], q: f64) -> f64 {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let position = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let (low, high) = (position.floor() as usize, position.ceil() as usize);
+    sorted[low] + (position - low as f64) * (sorted[high] - sorted[low])
+}
+
+/// Pearson correlation coefficient of two equally long samples
+///
+/// Returns `NaN` when either sample is constant or has fewer than 2 values.
+pub fn pearson_correlation(a: &[f64], b: &[f64]) -> f64 {
+    assert_eq!(a.len(), b.len(), "Samples have different lengths");
+    if a.len() < 2 {
+        return f64::NAN;
+    }
+    let n = a.len() as f64;
+    let (mean_a, mean_b) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        cov += (x - mean_a) * (y - mean_b);
+        var_a += (x - mean_a).powi(2);
+        var_b += (y - mean_b).powi(2);
+    }
+    if var_a == 0.0 || var_b == 0.0 {
+        return f64::NAN;
+    }
+    (cov / (var_a * var_b).sqrt()).clamp(-1.0, 1.0)
+}
@@ -0,0 +1,5 @@
+mod distributions;
+mod descriptive;
+
+pub use distributions::*;
+pub use descriptive::*;
@@ -0,0 +1,95 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use ndarray::{Array1, Array2};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::metrics::{r2_score, MultiOutput};
+
+/// Model learning a mapping from inputs to targets
+pub trait Fit {
+    /// Learns the parameters from the rows of `x` and the matching rows of `y`
+    fn fit(&mut self, x: &Array2<f64>, y: &Array2<f64>) -> Result<(), Box<dyn Error>>;
+}
+
+/// Model that can count some rows more than others while learning
+pub trait FitWeighted: Fit {
+    /// Learns like `fit`, every row counting its non-negative `sample_weight`
+    fn fit_weighted(&mut self, x: &Array2<f64>, y: &Array2<f64>, sample_weight: &Array1<f64>) -> Result<(), Box<dyn Error>>;
+}
+
+/// Fitted model producing one output row per input row
+pub trait Predict {
+    fn predict(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>>;
+}
+
+/// Transformation learned on input data alone and reapplied to new data
+pub trait Transform {
+    /// Learns the parameters from the rows of `x`
+    fn fit(&mut self, x: &Array2<f64>) -> Result<(), Box<dyn Error>>;
+
+    /// Applies the fitted transformation
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>>;
+
+    fn fit_transform(&mut self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.fit(x)?;
+        self.transform(x)
+    }
+}
+
+/// Transformation learned from inputs and their targets, then applied to inputs alone
+///
+/// Supervised feature selectors implement it: `fit` needs `y`, `transform` does not.
+pub trait SupervisedTransform: Fit {
+    /// Applies the fitted transformation
+    fn transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>>;
+
+    fn fit_transform(&mut self, x: &Array2<f64>, y: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>> {
+        self.fit(x, y)?;
+        self.transform(x)
+    }
+}
+
+/// Transformation that can be undone
+pub trait InverseTransform: Transform {
+    /// Maps transformed values back to the original space
+    fn inverse_transform(&self, x: &Array2<f64>) -> Result<Array2<f64>, Box<dyn Error>>;
+}
+
+/// Goodness of fit of a fitted model on labelled data, higher is better
+pub trait Score: Predict {
+    /// Uniformly averaged R² of the predictions of `x` against `y`
+    fn score(&self, x: &Array2<f64>, y: &Array2<f64>) -> Result<f64, Box<dyn Error>> {
+        let y_pred = self.predict(x)?;
+        if y_pred.dim() != y.dim() {
+            return Err(format!("Predictions have shape {:?} but targets have {:?}", y_pred.dim(), y.dim()).into());
+        }
+        Ok(r2_score(y, &y_pred, MultiOutput::UniformAverage))
+    }
+}
+
+/// Types saved to and loaded from a JSON file
+pub trait Persist: Serialize + DeserializeOwned {
+    /// Function to save the value as JSON
+    ///
+    /// # Parameters:
+    /// - `filepath: String` - Path of the JSON file
+    ///
+    /// # Returns:
+    /// - `Result<(), Box<dyn Error>>`
+    fn save(&self, filepath: String) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(BufWriter::new(File::create(filepath)?), self)?;
+        Ok(())
+    }
+
+    /// Function to load a value saved with `save`
+    ///
+    /// # Parameters:
+    /// - `filepath: String` - Path of the JSON file
+    ///
+    /// # Returns:
+    /// - `Result<Self, Box<dyn Error>>`
+    fn load(filepath: String) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(filepath)?))?)
+    }
+}
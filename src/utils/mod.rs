@@ -0,0 +1,10 @@
+use rand::rngs::StdRng;
+use rand::{thread_rng, SeedableRng};
+
+/// Seeded RNG, or a randomly seeded one when `seed` is `None`
+pub(crate) fn rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_rng(thread_rng()).expect("Failed to seed RNG"),
+    }
+}